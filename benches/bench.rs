@@ -2,6 +2,7 @@
 extern crate criterion;
 
 use criterion::{BenchmarkId, Criterion};
+use sfv::visitor::Ignored;
 use sfv::{
     integer, key_ref, string_ref, token_ref, Decimal, DictSerializer, ItemSerializer,
     ListSerializer, Parser, SerializeValue,
@@ -9,7 +10,13 @@ use sfv::{
 
 criterion_main!(parsing, serializing, ref_serializing);
 
-criterion_group!(parsing, parsing_item, parsing_list, parsing_dict);
+criterion_group!(
+    parsing,
+    parsing_item,
+    parsing_list,
+    parsing_dict,
+    parsing_long_bare_items
+);
 
 fn parsing_item(c: &mut Criterion) {
     let fixture =
@@ -45,6 +52,35 @@ fn parsing_dict(c: &mut Criterion) {
     );
 }
 
+fn parsing_long_bare_items(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parsing_long_bare_items");
+    for len in [16, 256, 4096] {
+        let fixtures = [
+            ("token", format!("a{}", "bc:d/e-f".repeat(len / 8))),
+            ("string", format!(r#""{}""#, "some text ".repeat(len / 10))),
+            (
+                "escaped_string",
+                format!(r#""{}""#, r#"some \"text\" "#.repeat(len / 16)),
+            ),
+            ("key", format!("a{}=1", "bc_d-e.f".repeat(len / 8))),
+        ];
+        for (name, fixture) in &fixtures {
+            group.bench_with_input(BenchmarkId::new(*name, len), fixture, |bench, input| {
+                if *name == "key" {
+                    bench.iter(|| {
+                        Parser::new(input)
+                            .parse_dictionary_with_visitor(&mut Ignored)
+                            .unwrap()
+                    });
+                } else {
+                    bench.iter(|| Parser::new(input).parse_item_with_visitor(Ignored).unwrap());
+                }
+            });
+        }
+    }
+    group.finish();
+}
+
 criterion_group!(
     serializing,
     serializing_item,
@@ -0,0 +1,576 @@
+//! Derive macros for the [`sfv`](https://docs.rs/sfv) crate.
+//!
+//! These macros are re-exported from `sfv::typed` when `sfv`'s `derive`
+//! feature is enabled, which is the intended way of using them. See the
+//! documentation of that module for the supported attributes.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Fields, GenericArgument, LitStr, Member, PathArguments, Type};
+
+/// Derives `sfv::typed::FromSfvDictionary` for a struct.
+#[proc_macro_derive(FromSfvDictionary, attributes(sfv))]
+pub fn derive_from_sfv_dictionary(input: TokenStream) -> TokenStream {
+    expand(input, from_dictionary)
+}
+
+/// Derives `sfv::typed::ToSfvDictionary` for a struct.
+#[proc_macro_derive(ToSfvDictionary, attributes(sfv))]
+pub fn derive_to_sfv_dictionary(input: TokenStream) -> TokenStream {
+    expand(input, to_dictionary)
+}
+
+/// Derives `sfv::typed::FromSfvList` for a struct with a single field.
+#[proc_macro_derive(FromSfvList, attributes(sfv))]
+pub fn derive_from_sfv_list(input: TokenStream) -> TokenStream {
+    expand(input, from_list)
+}
+
+/// Derives `sfv::typed::ToSfvList` for a struct with a single field.
+#[proc_macro_derive(ToSfvList, attributes(sfv))]
+pub fn derive_to_sfv_list(input: TokenStream) -> TokenStream {
+    expand(input, to_list)
+}
+
+/// Derives `sfv::typed::FromSfvItem` for a struct.
+#[proc_macro_derive(FromSfvItem, attributes(sfv))]
+pub fn derive_from_sfv_item(input: TokenStream) -> TokenStream {
+    expand(input, from_item)
+}
+
+/// Derives `sfv::typed::ToSfvItem` for a struct.
+#[proc_macro_derive(ToSfvItem, attributes(sfv))]
+pub fn derive_to_sfv_item(input: TokenStream) -> TokenStream {
+    expand(input, to_item)
+}
+
+fn expand(
+    input: TokenStream,
+    f: impl FnOnce(&DeriveInput, Vec<Field>) -> syn::Result<TokenStream2>,
+) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    fields(&input)
+        .and_then(|fields| f(&input, fields))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Default,
+    Token,
+    ByteSequence,
+}
+
+struct Field {
+    member: Member,
+    ty: Type,
+    // The `T` in a field of type `Option<T>`.
+    optional: Option<Type>,
+    key: Option<LitStr>,
+    default: bool,
+    param: bool,
+    kind: Kind,
+}
+
+impl Field {
+    fn key(&self) -> syn::Result<LitStr> {
+        match (&self.key, &self.member) {
+            (Some(key), _) => Ok(key.clone()),
+            (None, Member::Named(ident)) => {
+                let key = LitStr::new(&ident.unraw().to_string(), ident.span());
+                validate_key(&key)?;
+                Ok(key)
+            }
+            (None, Member::Unnamed(index)) => Err(syn::Error::new(
+                index.span(),
+                "unnamed fields require `#[sfv(key = \"...\")]`",
+            )),
+        }
+    }
+
+    // The type to convert from or to, which is the field's type with `Option`
+    // removed and wrapped according to the field's kind.
+    fn conversion_type(&self) -> TokenStream2 {
+        let ty = self.optional.as_ref().unwrap_or(&self.ty);
+        match self.kind {
+            Kind::Default => quote!(#ty),
+            Kind::Token => quote!(::sfv::typed::__private::Token<#ty>),
+            Kind::ByteSequence => quote!(::sfv::typed::__private::ByteSequence<#ty>),
+        }
+    }
+
+    // Converts an expression of type `&T`, where `T` is the field's type with
+    // `Option` removed, into one implementing the conversion traits.
+    fn wrap_ref(&self, value: TokenStream2) -> TokenStream2 {
+        match self.kind {
+            Kind::Default => value,
+            Kind::Token => quote!(&::sfv::typed::__private::Token(#value)),
+            Kind::ByteSequence => quote!(&::sfv::typed::__private::ByteSequence(#value)),
+        }
+    }
+
+    // Converts an expression of type `Option<conversion_type()>` into the
+    // field's value.
+    fn unwrap_option(&self, value: TokenStream2) -> TokenStream2 {
+        let value = match self.kind {
+            Kind::Default => value,
+            Kind::Token | Kind::ByteSequence => quote!(#value.map(|value| value.0)),
+        };
+        if self.default {
+            quote!(#value.unwrap_or_default())
+        } else {
+            value
+        }
+    }
+}
+
+fn fields(input: &DeriveInput) -> syn::Result<Vec<Field>> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "generic structs are not supported",
+        ));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "only structs are supported",
+            ))
+        }
+    };
+
+    let members = match fields {
+        Fields::Named(_) | Fields::Unnamed(_) => fields.members(),
+        Fields::Unit => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "unit structs are not supported",
+            ))
+        }
+    };
+
+    fields
+        .iter()
+        .zip(members)
+        .map(|(field, member)| {
+            let mut result = Field {
+                member,
+                ty: field.ty.clone(),
+                optional: option_type(&field.ty),
+                key: None,
+                default: false,
+                param: false,
+                kind: Kind::Default,
+            };
+
+            for attr in &field.attrs {
+                if !attr.path().is_ident("sfv") {
+                    continue;
+                }
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("key") {
+                        let key: LitStr = meta.value()?.parse()?;
+                        validate_key(&key)?;
+                        result.key = Some(key);
+                    } else if meta.path.is_ident("default") {
+                        result.default = true;
+                    } else if meta.path.is_ident("param") {
+                        result.param = true;
+                    } else if meta.path.is_ident("token") {
+                        result.kind = Kind::Token;
+                    } else if meta.path.is_ident("bytes") {
+                        result.kind = Kind::ByteSequence;
+                    } else {
+                        return Err(meta.error("unknown sfv attribute"));
+                    }
+                    Ok(())
+                })?;
+            }
+
+            if result.default && result.optional.is_some() {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "`#[sfv(default)]` cannot be used with `Option` fields",
+                ));
+            }
+            Ok(result)
+        })
+        .collect()
+}
+
+// Returns `T` if `ty` is spelled `Option<T>`.
+fn option_type(ty: &Type) -> Option<Type> {
+    let Type::Path(ty) = ty else {
+        return None;
+    };
+    if ty.qself.is_some() {
+        return None;
+    }
+    let segment = ty.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first() {
+        Some(GenericArgument::Type(ty)) if args.args.len() == 1 => Some(ty.clone()),
+        _ => None,
+    }
+}
+
+// Mirrors `sfv::KeyRef::from_str` so that invalid keys are compile errors.
+fn validate_key(key: &LitStr) -> syn::Result<()> {
+    let value = key.value();
+    let mut bytes = value.bytes();
+    let valid = match bytes.next() {
+        None => false,
+        Some(c) => {
+            (c.is_ascii_lowercase() || c == b'*')
+                && bytes.all(|c| {
+                    c.is_ascii_lowercase()
+                        || c.is_ascii_digit()
+                        || matches!(c, b'_' | b'-' | b'*' | b'.')
+                })
+        }
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(syn::Error::new(
+            key.span(),
+            format!("invalid structured field key: {value:?}"),
+        ))
+    }
+}
+
+// Returns the keys of the given fields, which must be distinct.
+fn distinct_keys<'a>(fields: impl IntoIterator<Item = &'a Field>) -> syn::Result<Vec<LitStr>> {
+    let keys = fields
+        .into_iter()
+        .map(Field::key)
+        .collect::<syn::Result<Vec<_>>>()?;
+    for (index, key) in keys.iter().enumerate() {
+        if keys[..index].iter().any(|k| k.value() == key.value()) {
+            return Err(syn::Error::new(key.span(), "duplicate key"));
+        }
+    }
+    Ok(keys)
+}
+
+fn reject_params(fields: &[Field]) -> syn::Result<()> {
+    match fields.iter().find(|field| field.param) {
+        Some(field) => Err(syn::Error::new(
+            field.member.span(),
+            "`#[sfv(param)]` is only supported for items",
+        )),
+        None => Ok(()),
+    }
+}
+
+fn key_ref(key: &LitStr) -> TokenStream2 {
+    quote!(::sfv::KeyRef::constant(#key))
+}
+
+fn from_dictionary(input: &DeriveInput, fields: Vec<Field>) -> syn::Result<TokenStream2> {
+    reject_params(&fields)?;
+
+    let ident = &input.ident;
+    let slots: Vec<_> = (0..fields.len())
+        .map(|index| format_ident!("__field{}", index))
+        .collect();
+    let keys = distinct_keys(&fields)?;
+
+    let values = fields
+        .iter()
+        .zip(&slots)
+        .zip(&keys)
+        .map(|((field, slot), key)| {
+            let member = &field.member;
+            let ty = field.conversion_type();
+            if field.optional.is_some() || field.default {
+                let value = field.unwrap_option(quote! {
+                    ::sfv::typed::__private::optional_member::<#ty>(members.#slot, #key)?
+                });
+                quote!(#member: #value)
+            } else if field.kind == Kind::Default {
+                quote!(#member: ::sfv::typed::__private::member::<#ty>(members.#slot, #key)?)
+            } else {
+                quote!(#member: ::sfv::typed::__private::member::<#ty>(members.#slot, #key)?.0)
+            }
+        });
+
+    Ok(quote! {
+        impl ::sfv::typed::FromSfvDictionary for #ident {
+            fn from_sfv_dictionary(
+                parser: ::sfv::Parser<'_>,
+            ) -> ::core::result::Result<Self, ::sfv::Error> {
+                #[derive(Default)]
+                struct Members<'a> {
+                    __ignored: ::core::option::Option<::sfv::borrowed::ListEntry<'a>>,
+                    #(#slots: ::core::option::Option<::sfv::borrowed::ListEntry<'a>>,)*
+                }
+
+                impl<'a> ::sfv::visitor::DictionaryVisitor<'a> for Members<'a> {
+                    type Error = ::core::convert::Infallible;
+
+                    fn entry(
+                        &mut self,
+                        key: &'a ::sfv::KeyRef,
+                    ) -> ::core::result::Result<impl ::sfv::visitor::EntryVisitor<'a>, Self::Error>
+                    {
+                        ::core::result::Result::Ok(match key.as_str() {
+                            #(#keys => &mut self.#slots,)*
+                            _ => &mut self.__ignored,
+                        })
+                    }
+                }
+
+                let mut members = Members::default();
+                parser.parse_dictionary_with_visitor(&mut members)?;
+                ::core::result::Result::Ok(Self {
+                    #(#values,)*
+                })
+            }
+        }
+    })
+}
+
+fn to_dictionary(input: &DeriveInput, fields: Vec<Field>) -> syn::Result<TokenStream2> {
+    reject_params(&fields)?;
+    distinct_keys(&fields)?;
+
+    let ident = &input.ident;
+    let members = fields
+        .iter()
+        .map(|field| {
+            let key = key_ref(&field.key()?);
+            let member = &field.member;
+            let serialize = |value| {
+                let value = field.wrap_ref(value);
+                quote! {
+                    ::sfv::typed::ToSfvEntry::serialize_entry(
+                        #value,
+                        ::sfv::typed::__private::EntrySerializer::Dictionary(&mut *ser, #key),
+                    )?;
+                }
+            };
+            Ok(if field.optional.is_some() {
+                let serialize = serialize(quote!(value));
+                quote! {
+                    if let ::core::option::Option::Some(value) = &self.#member {
+                        #serialize
+                    }
+                }
+            } else {
+                serialize(quote!(&self.#member))
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        impl ::sfv::typed::ToSfvDictionary for #ident {
+            fn serialize_dictionary<W: ::sfv::Output>(
+                &self,
+                ser: &mut ::sfv::DictSerializer<W>,
+            ) -> ::core::result::Result<(), ::sfv::Error> {
+                #(#members)*
+                ::core::result::Result::Ok(())
+            }
+        }
+    })
+}
+
+fn single_field<'a>(input: &DeriveInput, fields: &'a [Field]) -> syn::Result<&'a Field> {
+    match fields {
+        [field] if !field.param && !field.default && field.kind == Kind::Default => Ok(field),
+        [field] => Err(syn::Error::new(
+            field.member.span(),
+            "attributes are not supported for list fields",
+        )),
+        _ => Err(syn::Error::new(
+            input.ident.span(),
+            "lists must be structs with a single field",
+        )),
+    }
+}
+
+fn from_list(input: &DeriveInput, fields: Vec<Field>) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let field = single_field(input, &fields)?;
+    let member = &field.member;
+    let ty = &field.ty;
+
+    Ok(quote! {
+        impl ::sfv::typed::FromSfvList for #ident {
+            fn from_sfv_list(
+                parser: ::sfv::Parser<'_>,
+            ) -> ::core::result::Result<Self, ::sfv::Error> {
+                ::core::result::Result::Ok(Self {
+                    #member: <#ty as ::sfv::typed::FromSfvList>::from_sfv_list(parser)?,
+                })
+            }
+        }
+    })
+}
+
+fn to_list(input: &DeriveInput, fields: Vec<Field>) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let field = single_field(input, &fields)?;
+    let member = &field.member;
+    let ty = &field.ty;
+
+    Ok(quote! {
+        impl ::sfv::typed::ToSfvList for #ident {
+            fn serialize_list<W: ::sfv::Output>(
+                &self,
+                ser: &mut ::sfv::ListSerializer<W>,
+            ) -> ::core::result::Result<(), ::sfv::Error> {
+                <#ty as ::sfv::typed::ToSfvList>::serialize_list(&self.#member, ser)
+            }
+        }
+    })
+}
+
+// Returns the field holding an item's bare item.
+fn bare_item_field<'a>(input: &DeriveInput, fields: &'a [Field]) -> syn::Result<&'a Field> {
+    let mut bare_item_fields = fields.iter().filter(|field| !field.param);
+    match (bare_item_fields.next(), bare_item_fields.next()) {
+        (Some(field), None) => {
+            if field.optional.is_some() || field.default || field.key.is_some() {
+                Err(syn::Error::new(
+                    field.member.span(),
+                    "the bare item field cannot be optional or have a key",
+                ))
+            } else {
+                Ok(field)
+            }
+        }
+        (_, Some(field)) => Err(syn::Error::new(
+            field.member.span(),
+            "items must have exactly one field without `#[sfv(param)]`",
+        )),
+        (None, None) => Err(syn::Error::new(
+            input.ident.span(),
+            "items must have exactly one field without `#[sfv(param)]`",
+        )),
+    }
+}
+
+fn from_item(input: &DeriveInput, fields: Vec<Field>) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    bare_item_field(input, &fields)?;
+    distinct_keys(fields.iter().filter(|field| field.param))?;
+
+    let values = fields
+        .iter()
+        .map(|field| {
+            let member = &field.member;
+            let ty = field.conversion_type();
+            if !field.param {
+                return Ok(match field.kind {
+                    Kind::Default => quote! {
+                        #member: <#ty as ::sfv::typed::FromBareItem>::from_bare_item(item.bare_item)?
+                    },
+                    Kind::Token | Kind::ByteSequence => quote! {
+                        #member: <#ty as ::sfv::typed::FromBareItem>::from_bare_item(item.bare_item)?.0
+                    },
+                });
+            }
+
+            let key = field.key()?;
+            let value = quote!(::sfv::typed::__private::take_param(&mut params, #key));
+            Ok(if field.optional.is_some() || field.default {
+                let value = field.unwrap_option(quote! {
+                    ::sfv::typed::__private::optional_param::<#ty>(#value, #key)?
+                });
+                quote!(#member: #value)
+            } else if field.kind == Kind::Default {
+                quote!(#member: ::sfv::typed::__private::param::<#ty>(#value, #key)?)
+            } else {
+                quote!(#member: ::sfv::typed::__private::param::<#ty>(#value, #key)?.0)
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let params = if fields.len() > 1 {
+        quote!(let mut params = item.params;)
+    } else {
+        quote!()
+    };
+
+    Ok(quote! {
+        impl ::sfv::typed::FromSfvItem for #ident {
+            fn from_item_value(
+                item: ::sfv::borrowed::Item<'_>,
+            ) -> ::core::result::Result<Self, ::sfv::Error> {
+                #params
+                ::core::result::Result::Ok(Self {
+                    #(#values,)*
+                })
+            }
+        }
+    })
+}
+
+fn to_item(input: &DeriveInput, fields: Vec<Field>) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let bare_item_field = bare_item_field(input, &fields)?;
+    distinct_keys(fields.iter().filter(|field| field.param))?;
+    let bare_item = bare_item_field.wrap_ref({
+        let member = &bare_item_field.member;
+        quote!(&self.#member)
+    });
+
+    let params = fields
+        .iter()
+        .filter(|field| field.param)
+        .map(|field| {
+            let key = key_ref(&field.key()?);
+            let member = &field.member;
+            let serialize = |value| {
+                let value = field.wrap_ref(value);
+                quote! {
+                    let ser = ser.parameter(
+                        #key,
+                        ::sfv::typed::ToBareItem::to_bare_item(#value)?,
+                    );
+                }
+            };
+            Ok(if field.optional.is_some() {
+                let serialize = serialize(quote!(value));
+                quote! {
+                    let ser = match &self.#member {
+                        ::core::option::Option::Some(value) => {
+                            #serialize
+                            ser
+                        }
+                        ::core::option::Option::None => ser,
+                    };
+                }
+            } else {
+                serialize(quote!(&self.#member))
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        impl ::sfv::typed::ToSfvItem for #ident {
+            fn serialize_item<W: ::sfv::Output>(
+                &self,
+                bare_item: impl ::core::ops::FnOnce(::sfv::RefBareItem<'_>) -> ::sfv::ParameterSerializer<W>,
+            ) -> ::core::result::Result<::sfv::ParameterSerializer<W>, ::sfv::Error> {
+                let ser = bare_item(::sfv::typed::ToBareItem::to_bare_item(#bare_item)?);
+                #(#params)*
+                ::core::result::Result::Ok(ser)
+            }
+        }
+    })
+}
@@ -0,0 +1,250 @@
+use core::fmt;
+
+use crate::{
+    BareItem, Date, Decimal, Dictionary, Error, ErrorKind, GenericBareItem, InnerList, Integer,
+    Item, ListEntry, StringRef, TokenRef,
+};
+
+/// The type of a structured field value, as reported by [`TypeMismatch`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ValueKind {
+    /// A decimal.
+    Decimal,
+    /// An integer.
+    Integer,
+    /// A string.
+    String,
+    /// A byte sequence.
+    ByteSequence,
+    /// A boolean.
+    Boolean,
+    /// A token.
+    Token,
+    /// A date.
+    Date,
+    /// A display string.
+    DisplayString,
+    /// An item of any type.
+    Item,
+    /// An inner list.
+    InnerList,
+}
+
+impl ValueKind {
+    fn of_bare_item<S, B, T, D>(bare_item: &GenericBareItem<S, B, T, D>) -> Self {
+        match bare_item {
+            GenericBareItem::Decimal(_) => Self::Decimal,
+            GenericBareItem::Integer(_) => Self::Integer,
+            GenericBareItem::String(_) => Self::String,
+            GenericBareItem::ByteSequence(_) => Self::ByteSequence,
+            GenericBareItem::Boolean(_) => Self::Boolean,
+            GenericBareItem::Token(_) => Self::Token,
+            GenericBareItem::Date(_) => Self::Date,
+            GenericBareItem::DisplayString(_) => Self::DisplayString,
+        }
+    }
+}
+
+impl fmt::Display for ValueKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Decimal => "decimal",
+            Self::Integer => "integer",
+            Self::String => "string",
+            Self::ByteSequence => "byte sequence",
+            Self::Boolean => "boolean",
+            Self::Token => "token",
+            Self::Date => "date",
+            Self::DisplayString => "display string",
+            Self::Item => "item",
+            Self::InnerList => "inner list",
+        })
+    }
+}
+
+/// An error that occurs when a structured field value does not have the
+/// expected type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TypeMismatch {
+    expected: ValueKind,
+    found: ValueKind,
+}
+
+impl TypeMismatch {
+    /// Returns the type that was expected.
+    pub fn expected(&self) -> ValueKind {
+        self.expected
+    }
+
+    /// Returns the type that was found instead.
+    pub fn found(&self) -> ValueKind {
+        self.found
+    }
+}
+
+impl fmt::Display for TypeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected {}, found {}", self.expected, self.found)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TypeMismatch {}
+
+impl From<TypeMismatch> for Error {
+    fn from(err: TypeMismatch) -> Self {
+        Self::custom_with_kind(ErrorKind::TypeMismatch, err)
+    }
+}
+
+macro_rules! impl_try_from_bare_item {
+    ($($t:ty => $kind:ident($val:ident) => $expr:expr,)+) => {
+        $(
+            impl<'a> TryFrom<&'a BareItem> for $t {
+                type Error = TypeMismatch;
+
+                fn try_from(bare_item: &'a BareItem) -> Result<Self, TypeMismatch> {
+                    match bare_item {
+                        BareItem::$kind($val) => Ok($expr),
+                        _ => Err(TypeMismatch {
+                            expected: ValueKind::$kind,
+                            found: ValueKind::of_bare_item(bare_item),
+                        }),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_try_from_bare_item! {
+    Decimal => Decimal(val) => *val,
+    Integer => Integer(val) => *val,
+    &'a StringRef => String(val) => val,
+    &'a [u8] => ByteSequence(val) => val,
+    bool => Boolean(val) => *val,
+    &'a TokenRef => Token(val) => val,
+    Date => Date(val) => *val,
+}
+
+/// Provides typed access to the members of a [`Dictionary`].
+///
+/// Each method returns `Ok(None)` if the dictionary has no member with the
+/// given key, and a [`TypeMismatch`] error if the member does not have the
+/// requested type.
+///
+/// # Examples
+/// ```
+/// # use sfv::{DictionaryExt, Parser, ValueKind};
+/// # fn main() -> Result<(), sfv::Error> {
+/// let dict = Parser::new("u=2, i, n=(* foo 2)").parse_dictionary()?;
+///
+/// assert_eq!(dict.get_integer("u")?, Some(sfv::integer(2)));
+/// assert_eq!(dict.get_bool_flag("i")?, Some(true));
+/// assert_eq!(dict.get_inner_list("n")?.map(|n| n.items.len()), Some(3));
+/// assert_eq!(dict.get_token("x")?, None);
+///
+/// let err = dict.get_token("u").unwrap_err();
+/// assert_eq!(err.expected(), ValueKind::Token);
+/// assert_eq!(err.found(), ValueKind::Integer);
+/// assert_eq!(err.to_string(), "expected token, found integer");
+/// # Ok(())
+/// # }
+/// ```
+pub trait DictionaryExt {
+    /// Returns the item member with the given key.
+    fn get_item(&self, key: &str) -> Result<Option<&Item>, TypeMismatch>;
+
+    /// Returns the inner-list member with the given key.
+    fn get_inner_list(&self, key: &str) -> Result<Option<&InnerList>, TypeMismatch>;
+
+    /// Returns the bare item of the item member with the given key, converted
+    /// to `T`.
+    ///
+    /// The member's parameters are ignored.
+    fn get_as<'a, T>(&'a self, key: &str) -> Result<Option<T>, TypeMismatch>
+    where
+        T: TryFrom<&'a BareItem, Error = TypeMismatch>,
+    {
+        self.get_item(key)?
+            .map(|item| T::try_from(&item.bare_item))
+            .transpose()
+    }
+
+    /// Returns the integer member with the given key.
+    fn get_integer(&self, key: &str) -> Result<Option<Integer>, TypeMismatch> {
+        self.get_as(key)
+    }
+
+    /// Returns the decimal member with the given key.
+    fn get_decimal(&self, key: &str) -> Result<Option<Decimal>, TypeMismatch> {
+        self.get_as(key)
+    }
+
+    /// Returns the string member with the given key.
+    fn get_string(&self, key: &str) -> Result<Option<&StringRef>, TypeMismatch> {
+        self.get_as(key)
+    }
+
+    /// Returns the token member with the given key.
+    fn get_token(&self, key: &str) -> Result<Option<&TokenRef>, TypeMismatch> {
+        self.get_as(key)
+    }
+
+    /// Returns the byte-sequence member with the given key.
+    fn get_byte_sequence(&self, key: &str) -> Result<Option<&[u8]>, TypeMismatch> {
+        self.get_as(key)
+    }
+
+    /// Returns the boolean member with the given key.
+    ///
+    /// A member without a value, such as `a` in `a, b=2`, is `true`.
+    fn get_bool_flag(&self, key: &str) -> Result<Option<bool>, TypeMismatch> {
+        self.get_as(key)
+    }
+
+    /// Returns the date member with the given key.
+    fn get_date(&self, key: &str) -> Result<Option<Date>, TypeMismatch> {
+        self.get_as(key)
+    }
+
+    /// Returns the display-string member with the given key.
+    fn get_display_string(&self, key: &str) -> Result<Option<&str>, TypeMismatch> {
+        // There is no `TryFrom<&BareItem>` impl for `&str`, which could be
+        // mistaken for one that accepts strings or tokens.
+        self.get_item(key)?
+            .map(|item| match &item.bare_item {
+                BareItem::DisplayString(val) => Ok(val.as_str()),
+                bare_item => Err(TypeMismatch {
+                    expected: ValueKind::DisplayString,
+                    found: ValueKind::of_bare_item(bare_item),
+                }),
+            })
+            .transpose()
+    }
+}
+
+impl DictionaryExt for Dictionary {
+    fn get_item(&self, key: &str) -> Result<Option<&Item>, TypeMismatch> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(ListEntry::Item(item)) => Ok(Some(item)),
+            Some(ListEntry::InnerList(_)) => Err(TypeMismatch {
+                expected: ValueKind::Item,
+                found: ValueKind::InnerList,
+            }),
+        }
+    }
+
+    fn get_inner_list(&self, key: &str) -> Result<Option<&InnerList>, TypeMismatch> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(ListEntry::InnerList(inner_list)) => Ok(Some(inner_list)),
+            Some(ListEntry::Item(item)) => Err(TypeMismatch {
+                expected: ValueKind::InnerList,
+                found: ValueKind::of_bare_item(&item.bare_item),
+            }),
+        }
+    }
+}
@@ -0,0 +1,492 @@
+/*!
+Contains types for structured field values that borrow from the input.
+
+These are produced by [`Parser::parse_item_borrowed`],
+[`Parser::parse_list_borrowed`], and [`Parser::parse_dictionary_borrowed`].
+Keys and tokens, and strings without escape sequences, are borrowed from the
+input rather than allocated, which makes these types cheaper to parse than
+their owned counterparts when a value only needs to be inspected:
+
+```
+# use sfv::{BareItemFromInput, Parser};
+# fn main() -> Result<(), sfv::Error> {
+let input = "a=tok;x, b=(1 2)";
+let dict = Parser::new(input).parse_dictionary_borrowed()?;
+
+let a = dict.get("a").unwrap().as_item().unwrap();
+assert!(matches!(a.bare_item, BareItemFromInput::Token(token) if token.as_str() == "tok"));
+assert_eq!(a.params.get("x"), Some(&BareItemFromInput::Boolean(true)));
+# Ok(())
+# }
+```
+
+To look up a single member of a dictionary without collecting the others, use
+[`Parser::find_dictionary_member`].
+
+With the `serde` feature, each of these types implements
+[`serde::Deserializer`](https://docs.rs/serde/1/serde/trait.Deserializer.html),
+which is how `from_str_item` and its counterparts deserialize values.
+*/
+
+#[cfg(feature = "parsed-types")]
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::convert::Infallible;
+use core::fmt;
+use core::ops::Deref;
+
+use crate::parser::{parse_comma_separated, parse_dictionary_value, SeenKeys};
+use crate::visitor::*;
+use crate::{BareItemFromInput, KeyRef, Parser, SFVResult};
+
+/// An item that borrows from the input.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Item<'a> {
+    /// The item's value.
+    pub bare_item: BareItemFromInput<'a>,
+    /// The item's parameters, which can be empty.
+    pub params: Parameters<'a>,
+}
+
+/// An inner list that borrows from the input.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct InnerList<'a> {
+    /// The inner list's items, which can be empty.
+    pub items: Vec<Item<'a>>,
+    /// The inner list's parameters, which can be empty.
+    pub params: Parameters<'a>,
+}
+
+/// A member of a list or dictionary that borrows from the input.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ListEntry<'a> {
+    /// An item.
+    Item(Item<'a>),
+    /// An inner list.
+    InnerList(InnerList<'a>),
+}
+
+/// The parameters of an [`Item`] or [`InnerList`], in order of first
+/// appearance of each key.
+///
+/// As with `sfv::Parameters`, a later value for a duplicated key replaces the
+/// earlier one in place.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Parameters<'a>(Entries<'a, BareItemFromInput<'a>>);
+
+/// A list that borrows from the input.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct List<'a>(Vec<ListEntry<'a>>);
+
+/// A dictionary that borrows from the input, in order of first appearance of
+/// each key.
+///
+/// As with `sfv::Dictionary`, a later value for a duplicated key replaces the
+/// earlier one in place.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Dictionary<'a>(Entries<'a, ListEntry<'a>>);
+
+// Values by key, in order of first appearance of each key.
+//
+// Keys are looked up in a map, so that collecting a large dictionary or
+// parameters does not take time proportional to the square of the number of
+// keys.
+#[derive(Clone)]
+struct Entries<'a, T> {
+    entries: Vec<(&'a KeyRef, T)>,
+    indices: BTreeMap<&'a KeyRef, usize>,
+}
+
+impl<'a, T> Entries<'a, T> {
+    // Returns the value with the given key, inserting `default()` if there is
+    // none.
+    fn get_or_insert_with(&mut self, key: &'a KeyRef, default: impl FnOnce() -> T) -> &mut T {
+        let index = *self.indices.entry(key).or_insert_with(|| {
+            self.entries.push((key, default()));
+            self.entries.len() - 1
+        });
+        &mut self.entries[index].1
+    }
+
+    // Removes and returns the value with the given key.
+    fn remove(&mut self, key: &str) -> Option<T> {
+        let index = self.entries.iter().position(|(k, _)| k.as_str() == key)?;
+        self.indices.remove(self.entries[index].0);
+        for i in self.indices.values_mut() {
+            if *i > index {
+                *i -= 1;
+            }
+        }
+        Some(self.entries.remove(index).1)
+    }
+}
+
+impl<T> Default for Entries<'_, T> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            indices: BTreeMap::new(),
+        }
+    }
+}
+
+// The indices are derived from the entries, so only the entries are compared
+// and shown.
+impl<T: PartialEq> PartialEq for Entries<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries == other.entries
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Entries<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.entries.fmt(f)
+    }
+}
+
+impl<'a, T> Deref for Entries<'a, T> {
+    type Target = [(&'a KeyRef, T)];
+
+    fn deref(&self) -> &Self::Target {
+        &self.entries
+    }
+}
+
+impl<'a, T> IntoIterator for Entries<'a, T> {
+    type Item = (&'a KeyRef, T);
+    type IntoIter = alloc::vec::IntoIter<(&'a KeyRef, T)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a> Item<'a> {
+    /// Converts the item into an owned [`sfv::Item`][crate::Item].
+    #[cfg(feature = "parsed-types")]
+    pub fn into_owned(self) -> crate::Item {
+        crate::Item::with_params(self.bare_item, self.params.into_owned())
+    }
+}
+
+impl Default for Item<'_> {
+    fn default() -> Self {
+        Self {
+            bare_item: BareItemFromInput::Boolean(false),
+            params: Parameters::default(),
+        }
+    }
+}
+
+impl<'a> InnerList<'a> {
+    /// Converts the inner list into an owned
+    /// [`sfv::InnerList`][crate::InnerList].
+    #[cfg(feature = "parsed-types")]
+    pub fn into_owned(self) -> crate::InnerList {
+        crate::InnerList::with_params(
+            self.items.into_iter().map(Item::into_owned).collect(),
+            self.params.into_owned(),
+        )
+    }
+}
+
+impl<'a> ListEntry<'a> {
+    /// If the entry is an item, returns it; otherwise returns `None`.
+    pub fn as_item(&self) -> Option<&Item<'a>> {
+        match self {
+            Self::Item(item) => Some(item),
+            Self::InnerList(_) => None,
+        }
+    }
+
+    /// If the entry is an inner list, returns it; otherwise returns `None`.
+    pub fn as_inner_list(&self) -> Option<&InnerList<'a>> {
+        match self {
+            Self::Item(_) => None,
+            Self::InnerList(inner_list) => Some(inner_list),
+        }
+    }
+
+    /// Converts the entry into an owned [`sfv::ListEntry`][crate::ListEntry].
+    #[cfg(feature = "parsed-types")]
+    pub fn into_owned(self) -> crate::ListEntry {
+        match self {
+            Self::Item(item) => item.into_owned().into(),
+            Self::InnerList(inner_list) => inner_list.into_owned().into(),
+        }
+    }
+}
+
+impl<'a> Parameters<'a> {
+    /// Returns the value of the parameter with the given key.
+    pub fn get(&self, key: &str) -> Option<&BareItemFromInput<'a>> {
+        self.0
+            .iter()
+            .find(|(k, _)| k.as_str() == key)
+            .map(|(_, value)| value)
+    }
+
+    // Removes and returns the value of the parameter with the given key.
+    pub(crate) fn take(&mut self, key: &str) -> Option<BareItemFromInput<'a>> {
+        self.0.remove(key)
+    }
+
+    /// Converts the parameters into owned
+    /// [`sfv::Parameters`][crate::Parameters].
+    #[cfg(feature = "parsed-types")]
+    pub fn into_owned(self) -> crate::Parameters {
+        self.0
+            .into_iter()
+            .map(|(key, value)| (key.to_owned(), value.into()))
+            .collect()
+    }
+}
+
+impl<'a> List<'a> {
+    /// Converts the list into an owned [`sfv::List`][crate::List].
+    #[cfg(feature = "parsed-types")]
+    pub fn into_owned(self) -> crate::List {
+        self.0.into_iter().map(ListEntry::into_owned).collect()
+    }
+}
+
+impl<'a> Dictionary<'a> {
+    /// Returns the value of the member with the given key.
+    pub fn get(&self, key: &str) -> Option<&ListEntry<'a>> {
+        self.0
+            .iter()
+            .find(|(k, _)| k.as_str() == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Converts the dictionary into an owned
+    /// [`sfv::Dictionary`][crate::Dictionary].
+    #[cfg(feature = "parsed-types")]
+    pub fn into_owned(self) -> crate::Dictionary {
+        self.0
+            .into_iter()
+            .map(|(key, value)| (key.to_owned(), value.into_owned()))
+            .collect()
+    }
+}
+
+macro_rules! impl_collection {
+    ($ty:ident, $elem:ty) => {
+        impl<'a> Deref for $ty<'a> {
+            type Target = [$elem];
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl<'a> IntoIterator for $ty<'a> {
+            type Item = $elem;
+            type IntoIter = alloc::vec::IntoIter<$elem>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.0.into_iter()
+            }
+        }
+
+        impl<'a, 'b> IntoIterator for &'b $ty<'a> {
+            type Item = &'b $elem;
+            type IntoIter = core::slice::Iter<'b, $elem>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.0.iter()
+            }
+        }
+    };
+}
+
+impl_collection!(Parameters, (&'a KeyRef, BareItemFromInput<'a>));
+impl_collection!(List, ListEntry<'a>);
+impl_collection!(Dictionary, (&'a KeyRef, ListEntry<'a>));
+
+impl<'a> ParameterVisitor<'a> for &mut Parameters<'a> {
+    type Error = Infallible;
+
+    fn parameter(
+        &mut self,
+        key: &'a KeyRef,
+        value: BareItemFromInput<'a>,
+    ) -> Result<(), Self::Error> {
+        *self
+            .0
+            .get_or_insert_with(key, || BareItemFromInput::Boolean(false)) = value;
+        Ok(())
+    }
+}
+
+impl<'a> ItemVisitor<'a> for &mut Item<'a> {
+    type Error = Infallible;
+
+    fn bare_item(
+        self,
+        bare_item: BareItemFromInput<'a>,
+    ) -> Result<impl ParameterVisitor<'a>, Self::Error> {
+        self.bare_item = bare_item;
+        Ok(&mut self.params)
+    }
+}
+
+impl<'a> InnerListVisitor<'a> for &mut InnerList<'a> {
+    type Error = Infallible;
+
+    fn item(&mut self) -> Result<impl ItemVisitor<'a>, Self::Error> {
+        self.items.push(Item::default());
+        match self.items.last_mut() {
+            Some(item) => Ok(item),
+            None => unreachable!(),
+        }
+    }
+
+    fn finish(self) -> Result<impl ParameterVisitor<'a>, Self::Error> {
+        Ok(&mut self.params)
+    }
+}
+
+impl<'a> ItemVisitor<'a> for &mut ListEntry<'a> {
+    type Error = Infallible;
+
+    fn bare_item(
+        self,
+        bare_item: BareItemFromInput<'a>,
+    ) -> Result<impl ParameterVisitor<'a>, Self::Error> {
+        *self = ListEntry::Item(Item::default());
+        match self {
+            ListEntry::Item(item) => item.bare_item(bare_item),
+            ListEntry::InnerList(_) => unreachable!(),
+        }
+    }
+}
+
+impl<'a> EntryVisitor<'a> for &mut ListEntry<'a> {
+    fn inner_list(self) -> Result<impl InnerListVisitor<'a>, Self::Error> {
+        *self = ListEntry::InnerList(InnerList::default());
+        match self {
+            ListEntry::InnerList(inner_list) => Ok(inner_list),
+            ListEntry::Item(_) => unreachable!(),
+        }
+    }
+}
+
+impl<'a> ListVisitor<'a> for List<'a> {
+    type Error = Infallible;
+
+    fn entry(&mut self) -> Result<impl EntryVisitor<'a>, Self::Error> {
+        self.0.push(ListEntry::Item(Item::default()));
+        match self.0.last_mut() {
+            Some(entry) => Ok(entry),
+            None => unreachable!(),
+        }
+    }
+}
+
+impl<'a> DictionaryVisitor<'a> for Dictionary<'a> {
+    type Error = Infallible;
+
+    fn entry(&mut self, key: &'a KeyRef) -> Result<impl EntryVisitor<'a>, Self::Error> {
+        Ok(self
+            .0
+            .get_or_insert_with(key, || ListEntry::Item(Item::default())))
+    }
+}
+
+impl<'a> ItemVisitor<'a> for &mut Option<ListEntry<'a>> {
+    type Error = Infallible;
+
+    fn bare_item(
+        self,
+        bare_item: BareItemFromInput<'a>,
+    ) -> Result<impl ParameterVisitor<'a>, Self::Error> {
+        self.insert(ListEntry::Item(Item::default()))
+            .bare_item(bare_item)
+    }
+}
+
+impl<'a> EntryVisitor<'a> for &mut Option<ListEntry<'a>> {
+    fn inner_list(self) -> Result<impl InnerListVisitor<'a>, Self::Error> {
+        self.insert(ListEntry::InnerList(InnerList::default()))
+            .inner_list()
+    }
+}
+
+impl<'a> Parser<'a> {
+    /// Parses input into a structured field value of `Item` type, borrowing
+    /// from the input where possible.
+    pub fn parse_item_borrowed(self) -> SFVResult<Item<'a>> {
+        let mut item = Item::default();
+        self.parse_item_with_visitor(&mut item)?;
+        Ok(item)
+    }
+
+    /// Parses input into a structured field value of `List` type, borrowing
+    /// from the input where possible.
+    pub fn parse_list_borrowed(self) -> SFVResult<List<'a>> {
+        let mut list = List::default();
+        self.parse_list_with_visitor(&mut list)?;
+        Ok(list)
+    }
+
+    /// Parses input into a structured field value of `Dictionary` type,
+    /// borrowing from the input where possible.
+    pub fn parse_dictionary_borrowed(self) -> SFVResult<Dictionary<'a>> {
+        let mut dict = Dictionary::default();
+        self.parse_dictionary_with_visitor(&mut dict)?;
+        Ok(dict)
+    }
+
+    /// Parses input into a structured field value of `Dictionary` type, and
+    /// returns the value of the member with the given key, if any.
+    ///
+    /// The whole input is validated, but only the value of the requested
+    /// member is collected, and byte sequences in other members are not
+    /// decoded. This is cheaper than parsing the whole dictionary when only a
+    /// few members are needed.
+    ///
+    /// Duplicate keys are handled according to the parser's
+    /// [`DuplicateKeyPolicy`][crate::DuplicateKeyPolicy].
+    ///
+    /// ```
+    /// # use sfv::{BareItemFromInput, Parser};
+    /// # fn main() -> Result<(), sfv::Error> {
+    /// let input = "u=5, i, x=:AQID:";
+    ///
+    /// let urgency = Parser::new(input).find_dictionary_member("u")?.unwrap();
+    /// assert_eq!(urgency.as_item().unwrap().bare_item, BareItemFromInput::Integer(5.into()));
+    ///
+    /// assert_eq!(Parser::new(input).find_dictionary_member("y")?, None);
+    /// assert!(Parser::new("u=5, x=:#:").find_dictionary_member("u").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_dictionary_member(self, key: &str) -> SFVResult<Option<ListEntry<'a>>> {
+        // https://httpwg.org/specs/rfc9651.html#parse-dictionary
+        self.parse(|parser| {
+            let mut found = None;
+            let mut seen = SeenKeys::new();
+            parse_comma_separated(parser, |parser| {
+                let index = parser.index;
+                let member_key = parser.parse_key()?;
+
+                if parser.check_duplicate_key(&mut seen, member_key, index)?
+                    && member_key.as_str() == key
+                {
+                    return parse_dictionary_value(
+                        parser,
+                        found.insert(ListEntry::Item(Item::default())),
+                    );
+                }
+
+                parser.validate_only = true;
+                let result = parse_dictionary_value(parser, Ignored);
+                parser.validate_only = false;
+                result
+            })?;
+            Ok(found)
+        })
+    }
+}
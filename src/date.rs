@@ -1,6 +1,6 @@
 use crate::Integer;
 
-use std::fmt;
+use core::fmt;
 
 /// A structured field value [date].
 ///
@@ -0,0 +1,446 @@
+use crate::borrowed::{Dictionary, InnerList, Item, List, ListEntry, Parameters};
+use crate::{BareItemFromInput, Error, Parser, SFVResult};
+
+use serde::de::value::{BorrowedStrDeserializer, MapDeserializer, SeqDeserializer};
+use serde::de::{self, Deserialize, DeserializeSeed, IntoDeserializer, SeqAccess, Visitor};
+use serde::forward_to_deserialize_any;
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::custom(msg)
+    }
+}
+
+/// Deserializes an instance of `T` from a structured field value of
+/// `Dictionary` type.
+///
+/// Dictionary members are presented to `T` as a map, so `T` is typically a
+/// struct whose field names are the dictionary's keys. See the
+/// [crate-level documentation](crate#serde) for how the other parts of a
+/// structured field value are mapped to the serde data model.
+///
+/// ```
+/// # use serde::Deserialize;
+/// # fn main() -> Result<(), sfv::Error> {
+/// #[derive(Deserialize)]
+/// struct Priority {
+///     #[serde(default)]
+///     u: u8,
+///     #[serde(default)]
+///     i: bool,
+/// }
+///
+/// let priority: Priority = sfv::from_str_dictionary("u=5, i")?;
+/// assert_eq!(priority.u, 5);
+/// assert!(priority.i);
+/// # Ok(())
+/// # }
+/// ```
+pub fn from_str_dictionary<'de, T: Deserialize<'de>>(input: &'de str) -> SFVResult<T> {
+    let mut dict = Dictionary::default();
+    Parser::new(input).parse_dictionary_with_visitor(&mut dict)?;
+    T::deserialize(dict)
+}
+
+/// Deserializes an instance of `T` from a structured field value of `List`
+/// type.
+///
+/// List members are presented to `T` as a sequence. See the
+/// [crate-level documentation](crate#serde) for how the other parts of a
+/// structured field value are mapped to the serde data model.
+///
+/// ```
+/// # fn main() -> Result<(), sfv::Error> {
+/// let list: Vec<&str> = sfv::from_str_list("gzip, br;q=0.5, identity")?;
+/// assert_eq!(list, ["gzip", "br", "identity"]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn from_str_list<'de, T: Deserialize<'de>>(input: &'de str) -> SFVResult<T> {
+    let mut list = List::default();
+    Parser::new(input).parse_list_with_visitor(&mut list)?;
+    T::deserialize(list)
+}
+
+/// Deserializes an instance of `T` from a structured field value of `Item`
+/// type.
+///
+/// See the [crate-level documentation](crate#serde) for how the parts of a
+/// structured field value are mapped to the serde data model.
+///
+/// ```
+/// # use std::collections::BTreeMap;
+/// # fn main() -> Result<(), sfv::Error> {
+/// let (value, params): (&str, BTreeMap<&str, i64>) =
+///     sfv::from_str_item(r#""foo";a=1;b=2"#)?;
+/// assert_eq!(value, "foo");
+/// assert_eq!(params, BTreeMap::from([("a", 1), ("b", 2)]));
+/// # Ok(())
+/// # }
+/// ```
+pub fn from_str_item<'de, T: Deserialize<'de>>(input: &'de str) -> SFVResult<T> {
+    let mut item = Item::default();
+    Parser::new(input).parse_item_with_visitor(&mut item)?;
+    T::deserialize(item)
+}
+
+// Presents a value and its parameters as a two-element sequence.
+struct WithParams<V, P> {
+    value: Option<V>,
+    params: Option<P>,
+}
+
+impl<'de, V, P> SeqAccess<'de> for WithParams<V, P>
+where
+    V: de::Deserializer<'de, Error = Error>,
+    P: de::Deserializer<'de, Error = Error>,
+{
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if let Some(value) = self.value.take() {
+            seed.deserialize(value).map(Some)
+        } else if let Some(params) = self.params.take() {
+            seed.deserialize(params).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(usize::from(self.value.is_some()) + usize::from(self.params.is_some()))
+    }
+}
+
+struct BareItemValue<'de>(BareItemFromInput<'de>);
+
+impl<'de> de::Deserializer<'de> for BareItemValue<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            BareItemFromInput::Decimal(val) => visitor.visit_f64(val.into()),
+            BareItemFromInput::Integer(val) => visitor.visit_i64(val.into()),
+            BareItemFromInput::String(Cow::Borrowed(val)) => {
+                visitor.visit_borrowed_str(val.as_str())
+            }
+            BareItemFromInput::String(Cow::Owned(val)) => visitor.visit_string(val.into()),
+            BareItemFromInput::ByteSequence(val) => visitor.visit_byte_buf(val),
+            BareItemFromInput::Boolean(val) => visitor.visit_bool(val),
+            BareItemFromInput::Token(val) => visitor.visit_borrowed_str(val.as_str()),
+            BareItemFromInput::Date(val) => visitor.visit_i64(val.unix_seconds().into()),
+            BareItemFromInput::DisplayString(Cow::Borrowed(val)) => visitor.visit_borrowed_str(val),
+            BareItemFromInput::DisplayString(Cow::Owned(val)) => visitor.visit_string(val),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        // Allow byte sequences to be deserialized into e.g. `Vec<u8>` without
+        // requiring `serde_bytes`.
+        match self.0 {
+            BareItemFromInput::ByteSequence(val) => {
+                let mut seq = SeqDeserializer::<_, Error>::new(val.into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        // Strings and tokens can be deserialized as unit variants.
+        match self.0 {
+            BareItemFromInput::String(Cow::Borrowed(val)) => {
+                visitor.visit_enum(BorrowedStrDeserializer::new(val.as_str()))
+            }
+            BareItemFromInput::String(Cow::Owned(val)) => {
+                visitor.visit_enum(String::from(val).into_deserializer())
+            }
+            BareItemFromInput::Token(val) => {
+                visitor.visit_enum(BorrowedStrDeserializer::new(val.as_str()))
+            }
+            val => Self(val).deserialize_any(visitor),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for BareItemValue<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Parameters<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let mut map = MapDeserializer::new(self.into_iter().map(|(key, value)| {
+            (
+                BorrowedStrDeserializer::new(key.as_str()),
+                BareItemValue(value),
+            )
+        }));
+        let value = visitor.visit_map(&mut map)?;
+        map.end()?;
+        Ok(value)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Item<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        BareItemValue(self.bare_item).deserialize_any(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        BareItemValue(self.bare_item).deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        if len == 2 {
+            visitor.visit_seq(WithParams {
+                value: Some(BareItemValue(self.bare_item)),
+                params: Some(self.params),
+            })
+        } else {
+            BareItemValue(self.bare_item).deserialize_tuple(len, visitor)
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        BareItemValue(self.bare_item).deserialize_enum(name, variants, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct tuple_struct map struct identifier
+        ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Item<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> de::Deserializer<'de> for InnerList<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let mut seq = SeqDeserializer::new(self.items.into_iter());
+        let value = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(value)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        if len == 2 {
+            visitor.visit_seq(WithParams {
+                value: Some(InnerListItems(self.items)),
+                params: Some(self.params),
+            })
+        } else {
+            self.deserialize_any(visitor)
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple_struct map struct enum
+        identifier ignored_any
+    }
+}
+
+// The items of an inner list, without its parameters.
+struct InnerListItems<'de>(Vec<Item<'de>>);
+
+impl<'de> de::Deserializer<'de> for InnerListItems<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        InnerList {
+            items: self.0,
+            params: Parameters::default(),
+        }
+        .deserialize_any(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de> de::Deserializer<'de> for ListEntry<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Self::Item(item) => item.deserialize_any(visitor),
+            Self::InnerList(inner_list) => inner_list.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Self::Item(item) => item.deserialize_seq(visitor),
+            Self::InnerList(inner_list) => inner_list.deserialize_seq(visitor),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Self::Item(item) => item.deserialize_tuple(len, visitor),
+            Self::InnerList(inner_list) => inner_list.deserialize_tuple(len, visitor),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self {
+            Self::Item(item) => item.deserialize_enum(name, variants, visitor),
+            Self::InnerList(inner_list) => inner_list.deserialize_enum(name, variants, visitor),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct tuple_struct map struct identifier
+        ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for ListEntry<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Dictionary<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let mut map = MapDeserializer::new(
+            self.into_iter()
+                .map(|(key, value)| (BorrowedStrDeserializer::new(key.as_str()), value)),
+        );
+        let value = visitor.visit_map(&mut map)?;
+        map.end()?;
+        Ok(value)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de> de::Deserializer<'de> for List<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let mut seq = SeqDeserializer::new(self.into_iter());
+        let value = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(value)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
@@ -1,6 +1,6 @@
-use crate::{Error, Integer};
+use crate::{Error, ErrorKind, Integer};
 
-use std::fmt;
+use core::fmt;
 
 /// A structured field value [decimal].
 ///
@@ -153,15 +153,29 @@ impl TryFrom<f32> for Decimal {
     }
 }
 
+// `f64::round_ties_even` is not available without `std`. Values outside the
+// range of `i64` saturate, which is enough to reject them as out of range.
+fn round_ties_even(v: f64) -> i64 {
+    let trunc = v as i64;
+    let diff = v - trunc as f64;
+    if diff > 0.5 || (diff == 0.5 && trunc % 2 != 0) {
+        trunc.saturating_add(1)
+    } else if diff < -0.5 || (diff == -0.5 && trunc % 2 != 0) {
+        trunc.saturating_sub(1)
+    } else {
+        trunc
+    }
+}
+
 impl TryFrom<f64> for Decimal {
     type Error = Error;
 
     fn try_from(v: f64) -> Result<Decimal, Error> {
         if v.is_nan() {
-            return Err(Error::new("NaN"));
+            return Err(Error::new(ErrorKind::NaN, "NaN"));
         }
 
-        match Integer::try_from((v * 1000.0).round_ties_even() as i64) {
+        match Integer::try_from(round_ties_even(v * 1000.0)) {
             Ok(v) => Ok(Decimal(v)),
             Err(_) => Err(Error::out_of_range()),
         }
@@ -1,5 +1,8 @@
-use std::borrow::Cow;
-use std::fmt;
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String as StdString;
+use alloc::string::ToString;
+use core::fmt::{self, Write as _};
 
 /// An error that can occur in this crate.
 ///
@@ -10,40 +13,272 @@ use std::fmt;
 /// - Attempting to serialize an empty [list][crate::ListSerializer::finish] or
 ///   [dictionary][crate::DictSerializer::finish]
 ///
-/// Other than implementing the [`std::error::Error`], [`std::fmt::Debug`], and
-/// [`std::fmt::Display`] traits, this error type currently provides no
-/// introspection capabilities.
+/// The category of an error is available from [`Error::kind`], and the byte
+/// index in the input at which it occurred, if any, from [`Error::index`].
 #[derive(Debug)]
 #[cfg_attr(test, derive(PartialEq))]
 pub struct Error {
+    kind: ErrorKind,
     msg: Cow<'static, str>,
     index: Option<usize>,
+    previous_index: Option<usize>,
+}
+
+/// The category of an [`Error`].
+///
+/// More categories may be added in the future, and the category of a given
+/// error may become more specific.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Input remained after a complete item, list, or dictionary.
+    TrailingCharacters,
+    /// A list or dictionary member was followed by something other than a
+    /// comma.
+    ExpectedComma,
+    /// A list or dictionary ended with a comma.
+    TrailingComma,
+    /// An inner list was malformed or unterminated.
+    InvalidInnerList,
+    /// Input did not begin with a bare item of the expected type.
+    ExpectedBareItem,
+    /// A boolean was neither `?0` nor `?1`.
+    InvalidBoolean,
+    /// A string contained a character that is not allowed in one.
+    InvalidStringCharacter,
+    /// A string or display string contained an invalid or unterminated escape
+    /// sequence.
+    InvalidEscapeSequence,
+    /// A string or display string was not terminated.
+    UnterminatedString,
+    /// A display string was malformed or was not valid UTF-8.
+    InvalidDisplayString,
+    /// A token contained a character that is not allowed in one.
+    InvalidTokenCharacter,
+    /// A key contained a character that is not allowed in one.
+    InvalidKeyCharacter,
+    /// A key or token was empty.
+    Empty,
+    /// A byte sequence was not terminated.
+    UnterminatedByteSequence,
+    /// A byte sequence did not contain valid base64.
+    InvalidBase64,
+    /// A number did not begin with a digit.
+    ExpectedDigit,
+    /// An integer had more than 15 digits.
+    IntegerTooLong,
+    /// A decimal had too many digits or ended with a decimal point.
+    InvalidDecimal,
+    /// A date was not an integer.
+    InvalidDate,
+    /// A bare item type is not supported by the parser's
+    /// [`Version`][crate::Version].
+    UnsupportedByVersion,
+    /// A numeric value was outside of the range of the target type.
+    OutOfRange,
+    /// A floating-point value was NaN.
+    NaN,
+    /// An attempt was made to serialize an empty list.
+    EmptyList,
+    /// An attempt was made to serialize an empty dictionary.
+    EmptyDictionary,
+    /// A value did not have the type required by a conversion.
+    TypeMismatch,
+    /// A key appeared more than once in the same dictionary or parameters,
+    /// and the parser's [`DuplicateKeyPolicy`][crate::DuplicateKeyPolicy]
+    /// rejects duplicates.
+    DuplicateKey,
+    /// The input exceeded one of the parser's
+    /// [`ParserLimits`][crate::ParserLimits].
+    LimitExceeded,
+    /// The input passed to a [`StreamingParser`][crate::StreamingParser] so
+    /// far is a valid prefix of a structured field value, but more input is
+    /// needed to complete it.
+    NeedMoreInput,
+    /// An error reported by a visitor or by another crate's conversion code,
+    /// such as `serde`.
+    Custom,
 }
 
 impl Error {
-    pub(crate) fn new(msg: &'static str) -> Self {
+    pub(crate) fn new(kind: ErrorKind, msg: &'static str) -> Self {
         Self {
+            kind,
             msg: Cow::Borrowed(msg),
             index: None,
+            previous_index: None,
         }
     }
 
-    pub(crate) fn with_index(msg: &'static str, index: usize) -> Self {
+    pub(crate) fn with_index(kind: ErrorKind, msg: &'static str, index: usize) -> Self {
         Self {
+            kind,
             msg: Cow::Borrowed(msg),
             index: Some(index),
+            previous_index: None,
+        }
+    }
+
+    pub(crate) fn duplicate_key(index: usize, previous_index: usize) -> Self {
+        Self {
+            kind: ErrorKind::DuplicateKey,
+            msg: Cow::Borrowed("duplicate key"),
+            index: Some(index),
+            previous_index: Some(previous_index),
         }
     }
 
     pub(crate) fn out_of_range() -> Self {
-        Self::new("out of range")
+        Self::new(ErrorKind::OutOfRange, "out of range")
     }
 
     pub(crate) fn custom(msg: impl fmt::Display) -> Self {
+        Self::custom_with_kind(ErrorKind::Custom, msg)
+    }
+
+    pub(crate) fn custom_with_kind(kind: ErrorKind, msg: impl fmt::Display) -> Self {
         Self {
+            kind,
             msg: Cow::Owned(msg.to_string()),
             index: None,
+            previous_index: None,
+        }
+    }
+
+    /// Returns the category of the error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns the byte index in the input at which the error occurred, if
+    /// any.
+    ///
+    /// This is only available for errors that occur while parsing or while
+    /// validating a string-like value.
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// Returns the byte index in the input of the previous occurrence of a
+    /// duplicate key, for errors of kind [`ErrorKind::DuplicateKey`].
+    ///
+    /// The index of the rejected occurrence is available from
+    /// [`Error::index`].
+    pub fn previous_index(&self) -> Option<usize> {
+        self.previous_index
+    }
+
+    /// Renders a human-readable report of the error for the input that caused
+    /// it.
+    ///
+    /// The report shows the input with a caret under the byte at
+    /// [`Error::index`], followed by a hint about what was expected there, if
+    /// any. Long inputs are clipped to the context surrounding the index, and
+    /// bytes that are not printable ASCII are escaped.
+    ///
+    /// ```
+    /// # use sfv::{visitor::Ignored, Parser};
+    /// let input = "a=1, b=2,";
+    /// let err = Parser::new(input)
+    ///     .parse_dictionary_with_visitor(&mut Ignored)
+    ///     .unwrap_err();
+    /// assert_eq!(
+    ///     err.render(input),
+    ///     "\
+    /// error: trailing comma at index 8
+    ///   | a=1, b=2,
+    ///   |         ^ expected a member after ','
+    /// "
+    /// );
+    /// ```
+    pub fn render(&self, input: &(impl ?Sized + AsRef<[u8]>)) -> StdString {
+        let input = input.as_ref();
+        let mut output = format!("error: {self}\n");
+
+        let index = self.index.map(|index| index.min(input.len()));
+        let (start, end) = match index {
+            Some(index) => (
+                index.saturating_sub(RENDER_CONTEXT),
+                input.len().min(index + RENDER_CONTEXT),
+            ),
+            None => (0, input.len().min(2 * RENDER_CONTEXT)),
+        };
+
+        let mut line = StdString::from("  | ");
+        let mut caret = line.len();
+        if start > 0 {
+            line.push_str("...");
+        }
+        for (i, &byte) in input[start..end].iter().enumerate() {
+            if index == Some(start + i) {
+                caret = line.len();
+            }
+            if byte.is_ascii_graphic() || byte == b' ' {
+                line.push(char::from(byte));
+            } else {
+                let _ = write!(line, "\\x{byte:02x}");
+            }
+        }
+        if index == Some(end) {
+            caret = line.len();
+        }
+        if end < input.len() {
+            line.push_str("...");
         }
+        output.push_str(&line);
+        output.push('\n');
+
+        if index.is_some() {
+            output.push_str("  |");
+            output.push_str(&" ".repeat(caret - 3));
+            output.push('^');
+            if let Some(hint) = self.kind.hint() {
+                output.push(' ');
+                output.push_str(hint);
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+// The number of bytes of input shown on either side of an error's index by
+// `Error::render`.
+const RENDER_CONTEXT: usize = 32;
+
+impl ErrorKind {
+    fn hint(self) -> Option<&'static str> {
+        Some(match self {
+            Self::TrailingCharacters => "expected end of input",
+            Self::ExpectedComma => "expected ',' or end of input",
+            Self::TrailingComma => "expected a member after ','",
+            Self::InvalidInnerList => "expected ' ' or ')'",
+            Self::ExpectedBareItem => "expected an integer, decimal, string, token, byte sequence, boolean, date, or display string",
+            Self::InvalidBoolean => "expected '?0' or '?1'",
+            Self::InvalidStringCharacter => "expected printable ASCII",
+            Self::InvalidEscapeSequence => r#"expected '\"' or '\\' in a string, or two lowercase hex digits after '%' in a display string"#,
+            Self::UnterminatedString => r#"expected closing '"'"#,
+            Self::InvalidDisplayString => r#"expected '"' followed by printable ASCII and percent-encoded UTF-8"#,
+            Self::InvalidTokenCharacter => "expected a token character",
+            Self::InvalidKeyCharacter => "expected 'a'-'z', '0'-'9', '_', '-', '.', or '*', starting with 'a'-'z' or '*'",
+            Self::UnterminatedByteSequence => "expected closing ':'",
+            Self::InvalidBase64 => "expected padded base64",
+            Self::ExpectedDigit => "expected '0'-'9'",
+            Self::IntegerTooLong => "expected at most 15 digits",
+            Self::InvalidDecimal => "expected at most 12 digits before the decimal point and 1 to 3 after it",
+            Self::InvalidDate => "expected an integer number of seconds",
+            Self::UnsupportedByVersion => "this type requires RFC 9651",
+            Self::DuplicateKey => "expected a key that has not appeared in this scope",
+            Self::NeedMoreInput => "expected more input",
+            Self::Empty
+            | Self::OutOfRange
+            | Self::NaN
+            | Self::EmptyList
+            | Self::EmptyDictionary
+            | Self::TypeMismatch
+            | Self::LimitExceeded
+            | Self::Custom => return None,
+        })
     }
 }
 
@@ -56,19 +291,25 @@ impl fmt::Display for Error {
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for Error {}
 
 pub(crate) struct NonEmptyStringError {
+    kind: ErrorKind,
     byte_index: Option<usize>,
 }
 
 impl NonEmptyStringError {
     pub(crate) const fn empty() -> Self {
-        Self { byte_index: None }
+        Self {
+            kind: ErrorKind::Empty,
+            byte_index: None,
+        }
     }
 
-    pub(crate) const fn invalid_character(byte_index: usize) -> Self {
+    pub(crate) const fn invalid_character(kind: ErrorKind, byte_index: usize) -> Self {
         Self {
+            kind,
             byte_index: Some(byte_index),
         }
     }
@@ -84,8 +325,10 @@ impl NonEmptyStringError {
 impl From<NonEmptyStringError> for Error {
     fn from(err: NonEmptyStringError) -> Error {
         Error {
+            kind: err.kind,
             msg: Cow::Borrowed(err.msg()),
             index: err.byte_index,
+            previous_index: None,
         }
     }
 }
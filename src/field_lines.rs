@@ -0,0 +1,150 @@
+use crate::{Error, Parser};
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+/// A structured field value that was split across multiple field lines.
+///
+/// As required by [RFC 9651], the lines are combined into a single value,
+/// separated by `", "`, which is then parsed as a whole. For example, a
+/// dictionary member on a later line overrides one with the same key on an
+/// earlier line. Errors can be traced back to the line that caused them with
+/// [`FieldLines::error_location`].
+///
+/// Created by [`Parser::from_field_lines`].
+///
+/// [RFC 9651]: <https://httpwg.org/specs/rfc9651.html#text-parse>
+#[derive(Debug, Clone)]
+pub struct FieldLines {
+    input: Vec<u8>,
+    lines: Vec<Range<usize>>,
+}
+
+/// The location of a byte within one of several field lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineLocation {
+    pub(crate) line: usize,
+    pub(crate) offset: usize,
+}
+
+impl LineLocation {
+    /// Returns the zero-based index of the line.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the zero-based byte offset within the line.
+    ///
+    /// This is equal to the line's length if the location is at the end of
+    /// the line, including within the separator between it and the next line.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl fmt::Display for LineLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, offset {}", self.line, self.offset)
+    }
+}
+
+impl FieldLines {
+    /// Returns a parser for the combined value.
+    pub fn parser(&self) -> Parser<'_> {
+        Parser::new(&self.input)
+    }
+
+    /// Returns the combined value.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.input
+    }
+
+    /// Returns the number of field lines.
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Returns the location within the original field lines of the byte at
+    /// the given index into the combined value.
+    ///
+    /// Returns `None` if there are no field lines.
+    pub fn location(&self, index: usize) -> Option<LineLocation> {
+        let line = self
+            .lines
+            .partition_point(|range| range.start <= index)
+            .checked_sub(1)?;
+        let range = &self.lines[line];
+        Some(LineLocation {
+            line,
+            offset: index.min(range.end) - range.start,
+        })
+    }
+
+    /// Returns the location within the original field lines of the given
+    /// error, which must have been returned by this value's parser.
+    ///
+    /// Returns `None` if the error has no index or if there are no field
+    /// lines.
+    #[cfg_attr(
+        feature = "parsed-types",
+        doc = r##"
+
+# Examples
+```
+# use sfv::Parser;
+let lines = Parser::from_field_lines(["a=1, b=2", "c=(1 2", "d"]);
+
+let err = lines.parser().parse_dictionary().unwrap_err();
+let location = lines.error_location(&err).unwrap();
+assert_eq!(location.line(), 1);
+assert_eq!(location.offset(), 6);
+```
+"##
+    )]
+    pub fn error_location(&self, error: &Error) -> Option<LineLocation> {
+        self.location(error.index()?)
+    }
+}
+
+impl Parser<'_> {
+    /// Combines multiple field lines into a single structured field value.
+    ///
+    /// The returned value owns the combined input, from which a parser can be
+    /// obtained using [`FieldLines::parser`].
+    #[cfg_attr(
+        feature = "parsed-types",
+        doc = r##"
+
+# Examples
+```
+# use sfv::{Parser, SerializeValue};
+# fn main() -> Result<(), sfv::Error> {
+let lines = Parser::from_field_lines(["a=1, b=2", "a=3"]);
+let dict = lines.parser().parse_dictionary()?;
+
+assert_eq!(dict.serialize_value()?, "a=3, b=2");
+# Ok(())
+# }
+```
+"##
+    )]
+    pub fn from_field_lines(lines: impl IntoIterator<Item = impl AsRef<[u8]>>) -> FieldLines {
+        let mut input = Vec::new();
+        let mut ranges = Vec::new();
+
+        for line in lines {
+            if !ranges.is_empty() {
+                input.extend_from_slice(b", ");
+            }
+            let start = input.len();
+            input.extend_from_slice(line.as_ref());
+            ranges.push(start..input.len());
+        }
+
+        FieldLines {
+            input,
+            lines: ranges,
+        }
+    }
+}
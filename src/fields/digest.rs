@@ -0,0 +1,244 @@
+use crate::borrowed;
+use crate::typed::{FromBareItem, FromSfvDictionary, ToSfvDictionary};
+use crate::{
+    key_ref, BareItemFromInput, DictSerializer, Error, ErrorKind, Integer, Key, KeyRef, Output,
+    Parser, SFVResult,
+};
+
+use alloc::borrow::ToOwned;
+use alloc::vec::Vec;
+
+/// A digest algorithm from the [Hash Algorithms for HTTP Digest Fields]
+/// registry whose digests are validated by [`Digest`].
+///
+/// Algorithms that the registry marks as deprecated are not included.
+///
+/// [Hash Algorithms for HTTP Digest Fields]: <https://www.iana.org/assignments/http-digest-hash-alg/http-digest-hash-alg.xhtml>
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DigestAlgorithm {
+    /// SHA-256 (`sha-256`).
+    Sha256,
+    /// SHA-512 (`sha-512`).
+    Sha512,
+}
+
+impl DigestAlgorithm {
+    /// Returns the algorithm with the given key, if it is a known algorithm.
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "sha-256" => Some(Self::Sha256),
+            "sha-512" => Some(Self::Sha512),
+            _ => None,
+        }
+    }
+
+    /// Returns the key that identifies the algorithm in digest fields.
+    pub fn key(self) -> &'static KeyRef {
+        match self {
+            Self::Sha256 => key_ref("sha-256"),
+            Self::Sha512 => key_ref("sha-512"),
+        }
+    }
+
+    /// Returns the length of the algorithm's digests in bytes.
+    pub fn digest_len(self) -> usize {
+        match self {
+            Self::Sha256 => 32,
+            Self::Sha512 => 64,
+        }
+    }
+}
+
+fn validate_digest(algorithm: &KeyRef, value: &[u8]) -> SFVResult<()> {
+    match DigestAlgorithm::from_key(algorithm.as_str()) {
+        Some(algorithm) if algorithm.digest_len() != value.len() => Err(Error::new(
+            ErrorKind::TypeMismatch,
+            "digest length does not match algorithm",
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// The `Content-Digest` or `Repr-Digest` field, which maps digest algorithms
+/// to digests.
+///
+/// Parameters of the members are ignored. Members that are not byte sequences,
+/// and digests whose length does not match a known [`DigestAlgorithm`], result
+/// in an [`ErrorKind::TypeMismatch`] error. Digests for other algorithms are
+/// kept as is.
+///
+/// See [RFC 9530].
+///
+/// [RFC 9530]: <https://httpwg.org/specs/rfc9530.html#content-digest>
+///
+/// # Examples
+/// ```
+/// # use sfv::fields::Digest;
+/// # use sfv::typed::FromSfvDictionary;
+/// # use sfv::Parser;
+/// # fn main() -> Result<(), sfv::Error> {
+/// let digest = Digest::from_sfv_dictionary(Parser::new(
+///     "sha-256=:RK/0qy18MlBSVnWgjwz6lZEWjP/lF5HF9bvEF8FabDg=:, unixsum=:MzAy:",
+/// ))?;
+///
+/// assert_eq!(digest.digests.len(), 2);
+/// assert_eq!(digest.get("sha-256").unwrap().len(), 32);
+///
+/// assert!(Digest::from_sfv_dictionary(Parser::new("sha-512=:MzAy:")).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Digest {
+    /// The digests, by algorithm, in order.
+    pub digests: Vec<(Key, Vec<u8>)>,
+}
+
+impl Digest {
+    /// Returns the digest for the given algorithm.
+    pub fn get(&self, algorithm: &str) -> Option<&[u8]> {
+        self.digests
+            .iter()
+            .find(|(key, _)| key.as_str() == algorithm)
+            .map(|(_, value)| value.as_slice())
+    }
+}
+
+impl FromSfvDictionary for Digest {
+    fn from_sfv_dictionary(parser: Parser<'_>) -> SFVResult<Self> {
+        let mut dict = borrowed::Dictionary::default();
+        parser.parse_dictionary_with_visitor(&mut dict)?;
+
+        let digests = dict
+            .into_iter()
+            .map(|(algorithm, entry)| match entry {
+                borrowed::ListEntry::Item(borrowed::Item {
+                    bare_item: BareItemFromInput::ByteSequence(value),
+                    ..
+                }) => {
+                    validate_digest(algorithm, &value)?;
+                    Ok((algorithm.to_owned(), value))
+                }
+                _ => Err(Error::new(
+                    ErrorKind::TypeMismatch,
+                    "expected byte sequence",
+                )),
+            })
+            .collect::<SFVResult<_>>()?;
+        Ok(Self { digests })
+    }
+}
+
+impl ToSfvDictionary for Digest {
+    fn serialize_dictionary<W: Output>(&self, ser: &mut DictSerializer<W>) -> SFVResult<()> {
+        for (algorithm, value) in &self.digests {
+            validate_digest(algorithm, value)?;
+        }
+        for (algorithm, value) in &self.digests {
+            ser.bare_item(algorithm, value.as_slice());
+        }
+        Ok(())
+    }
+}
+
+/// The `Want-Content-Digest` or `Want-Repr-Digest` field, which maps digest
+/// algorithms to preference weights.
+///
+/// Weights range from 0, meaning that the algorithm is not acceptable, to
+/// [`WantDigest::MAX_WEIGHT`], the most preferred. Parameters of the members
+/// are ignored. Members that are not integers result in an
+/// [`ErrorKind::TypeMismatch`] error, and weights outside of that range result
+/// in an [`ErrorKind::OutOfRange`] error.
+///
+/// See [RFC 9530].
+///
+/// [RFC 9530]: <https://httpwg.org/specs/rfc9530.html#want-fields>
+///
+/// # Examples
+/// ```
+/// # use sfv::fields::{DigestAlgorithm, WantDigest};
+/// # use sfv::typed::FromSfvDictionary;
+/// # use sfv::Parser;
+/// # fn main() -> Result<(), sfv::Error> {
+/// let want = WantDigest::from_sfv_dictionary(Parser::new("sha-512=3, sha-256=10, unixsum=0"))?;
+///
+/// assert_eq!(want.weight("sha-512"), Some(3));
+/// assert_eq!(want.preferred(), Some(DigestAlgorithm::Sha256));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WantDigest {
+    /// The weights, by algorithm, in order.
+    pub preferences: Vec<(Key, u8)>,
+}
+
+impl WantDigest {
+    /// The maximum weight, which is also the most preferred.
+    pub const MAX_WEIGHT: u8 = 10;
+
+    /// Returns the weight of the given algorithm.
+    pub fn weight(&self, algorithm: &str) -> Option<u8> {
+        self.preferences
+            .iter()
+            .find(|(key, _)| key.as_str() == algorithm)
+            .map(|(_, weight)| *weight)
+    }
+
+    /// Returns the known algorithm with the highest non-zero weight, if any.
+    ///
+    /// Of several such algorithms with the same weight, the first is returned.
+    pub fn preferred(&self) -> Option<DigestAlgorithm> {
+        self.preferences
+            .iter()
+            .filter(|(_, weight)| *weight > 0)
+            .filter_map(|(key, weight)| Some((DigestAlgorithm::from_key(key.as_str())?, *weight)))
+            .fold(None, |best, (algorithm, weight)| match best {
+                Some((_, best_weight)) if best_weight >= weight => best,
+                _ => Some((algorithm, weight)),
+            })
+            .map(|(algorithm, _)| algorithm)
+    }
+}
+
+fn validate_weight(weight: u8) -> SFVResult<u8> {
+    if weight > WantDigest::MAX_WEIGHT {
+        return Err(Error::out_of_range());
+    }
+    Ok(weight)
+}
+
+impl FromSfvDictionary for WantDigest {
+    fn from_sfv_dictionary(parser: Parser<'_>) -> SFVResult<Self> {
+        let mut dict = borrowed::Dictionary::default();
+        parser.parse_dictionary_with_visitor(&mut dict)?;
+
+        let preferences = dict
+            .into_iter()
+            .map(|(algorithm, entry)| match entry {
+                borrowed::ListEntry::Item(item) => {
+                    let weight = Integer::from_bare_item(item.bare_item)?;
+                    let weight = u8::try_from(weight).map_err(|_| Error::out_of_range())?;
+                    Ok((algorithm.to_owned(), validate_weight(weight)?))
+                }
+                borrowed::ListEntry::InnerList(_) => Err(Error::new(
+                    ErrorKind::TypeMismatch,
+                    "expected integer, found inner list",
+                )),
+            })
+            .collect::<SFVResult<_>>()?;
+        Ok(Self { preferences })
+    }
+}
+
+impl ToSfvDictionary for WantDigest {
+    fn serialize_dictionary<W: Output>(&self, ser: &mut DictSerializer<W>) -> SFVResult<()> {
+        for (_, weight) in &self.preferences {
+            validate_weight(*weight)?;
+        }
+        for (algorithm, weight) in &self.preferences {
+            ser.bare_item(algorithm, Integer::from(*weight));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+//! Contains typed models of HTTP fields that are defined as structured
+//! fields.
+//!
+//! Each type applies the field's own rules, such as ignoring unknown members,
+//! on top of [RFC 9651], and is parsed and serialized with the traits in the
+//! [`typed`][crate::typed] module.
+//!
+//! - [`ProxyStatus`] -- Members of the `Proxy-Status` field from [RFC 9209].
+//! - [`CacheStatus`] -- Members of the `Cache-Status` field from [RFC 9211].
+//! - [`Priority`] -- The `Priority` field from [RFC 9218].
+//! - [`SignatureInput`] and [`Signature`] -- The `Signature-Input` and
+//!   `Signature` fields from [RFC 9421].
+//! - [`Digest`] and [`WantDigest`] -- The `Content-Digest`, `Repr-Digest`,
+//!   `Want-Content-Digest`, and `Want-Repr-Digest` fields from [RFC 9530].
+//!
+//! [RFC 9209]: <https://httpwg.org/specs/rfc9209.html>
+//! [RFC 9211]: <https://httpwg.org/specs/rfc9211.html>
+//! [RFC 9218]: <https://httpwg.org/specs/rfc9218.html>
+//! [RFC 9421]: <https://httpwg.org/specs/rfc9421.html>
+//! [RFC 9530]: <https://httpwg.org/specs/rfc9530.html>
+//! [RFC 9651]: <https://httpwg.org/specs/rfc9651.html>
+
+mod digest;
+mod priority;
+mod signature;
+mod status;
+
+pub use digest::{Digest, DigestAlgorithm, WantDigest};
+pub use priority::Priority;
+pub use signature::{ComponentIdentifier, Signature, SignatureInput, SignatureParams};
+pub use status::{CacheStatus, ForwardReason, Identifier, ProxyErrorType, ProxyStatus};
+
+#[cfg(test)]
+mod test_digest;
+#[cfg(test)]
+mod test_priority;
+#[cfg(test)]
+mod test_signature;
+#[cfg(test)]
+mod test_status;
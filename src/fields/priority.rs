@@ -0,0 +1,194 @@
+use crate::typed::{FromSfvDictionary, ToSfvDictionary};
+use crate::visitor::{
+    DictionaryVisitor, EntryVisitor, Ignored, InnerListVisitor, ItemVisitor, ParameterVisitor,
+};
+use crate::{
+    key_ref, BareItemFromInput, DictSerializer, Error, Integer, KeyRef, Output, Parser, SFVResult,
+};
+
+use core::convert::Infallible;
+
+/// The priority of an HTTP response, as signaled by the [`Priority`] field or
+/// by the value of a `PRIORITY_UPDATE` frame.
+///
+/// Parsing follows the rules of [RFC 9218]: unknown members, parameters, and
+/// invalid values of known members are ignored, so that the affected
+/// parameters keep their default values.
+///
+/// Serialization omits parameters that have their default values. As empty
+/// dictionaries cannot be serialized, serializing the default priority fails;
+/// use [`Priority::is_default`] to omit the field entirely instead.
+///
+/// [RFC 9218]: <https://httpwg.org/specs/rfc9218.html>
+/// [`Priority`]: <https://httpwg.org/specs/rfc9218.html#header-field>
+///
+/// # Examples
+/// ```
+/// # use sfv::fields::Priority;
+/// # use sfv::typed::{FromSfvDictionary, ToSfvDictionary};
+/// # use sfv::Parser;
+/// # fn main() -> Result<(), sfv::Error> {
+/// let priority = Priority::from_sfv_dictionary(Parser::new("u=5, i, x=?0"))?;
+/// assert_eq!(priority, Priority { urgency: 5, incremental: true });
+///
+/// let priority = Priority::from_sfv_dictionary(Parser::new("u=9, i=1"))?;
+/// assert_eq!(priority, Priority::default());
+///
+/// let priority = Priority { urgency: 3, incremental: true };
+/// assert_eq!(priority.to_sfv_dictionary()?, "i");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Priority {
+    /// The urgency (`u`), from 0 for the most urgent responses to
+    /// [`Priority::MAX_URGENCY`] for the least urgent ones.
+    pub urgency: u8,
+    /// Whether the response can be processed incrementally (`i`).
+    pub incremental: bool,
+}
+
+impl Priority {
+    /// The default urgency.
+    pub const DEFAULT_URGENCY: u8 = 3;
+
+    /// The maximum urgency, which is also the least urgent.
+    pub const MAX_URGENCY: u8 = 7;
+
+    /// Returns whether all parameters have their default values.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Overrides the parameters that are present in the given `Priority` field
+    /// value, leaving the others unchanged.
+    ///
+    /// This can be used to combine the priority signaled by a request's
+    /// `Priority` field with a later signal for the same request, such as the
+    /// value of a `PRIORITY_UPDATE` frame or the `Priority` field of the
+    /// response, as described in [RFC 9218].
+    ///
+    /// As with parsing, invalid values are ignored. If the field value cannot
+    /// be parsed at all, `self` is left unchanged.
+    ///
+    /// [RFC 9218]: <https://httpwg.org/specs/rfc9218.html#merging>
+    ///
+    /// # Examples
+    /// ```
+    /// # use sfv::fields::Priority;
+    /// # use sfv::typed::FromSfvDictionary;
+    /// # use sfv::Parser;
+    /// # fn main() -> Result<(), sfv::Error> {
+    /// let mut priority = Priority::from_sfv_dictionary(Parser::new("u=5, i"))?;
+    /// priority.merge(Parser::new("u=1"))?;
+    ///
+    /// assert_eq!(priority, Priority { urgency: 1, incremental: true });
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn merge(&mut self, parser: Parser<'_>) -> SFVResult<()> {
+        let mut params = Params::default();
+        parser.parse_dictionary_with_visitor(&mut params)?;
+
+        if let Some(urgency) = params.urgency {
+            self.urgency = urgency;
+        }
+        if let Some(incremental) = params.incremental {
+            self.incremental = incremental;
+        }
+        Ok(())
+    }
+}
+
+impl Default for Priority {
+    fn default() -> Self {
+        Self {
+            urgency: Self::DEFAULT_URGENCY,
+            incremental: false,
+        }
+    }
+}
+
+impl FromSfvDictionary for Priority {
+    fn from_sfv_dictionary(parser: Parser<'_>) -> SFVResult<Self> {
+        let mut priority = Self::default();
+        priority.merge(parser)?;
+        Ok(priority)
+    }
+}
+
+impl ToSfvDictionary for Priority {
+    fn serialize_dictionary<W: Output>(&self, ser: &mut DictSerializer<W>) -> SFVResult<()> {
+        if self.urgency > Self::MAX_URGENCY {
+            return Err(Error::out_of_range());
+        }
+        if self.urgency != Self::DEFAULT_URGENCY {
+            ser.bare_item(key_ref("u"), Integer::from(self.urgency));
+        }
+        if self.incremental {
+            ser.bare_item(key_ref("i"), true);
+        }
+        Ok(())
+    }
+}
+
+// The valid parameters present in a field value. Each member resets its
+// parameter, so that an invalid value for a duplicated key discards an
+// earlier valid one.
+#[derive(Default)]
+struct Params {
+    urgency: Option<u8>,
+    incremental: Option<bool>,
+}
+
+enum Param<'p> {
+    Urgency(&'p mut Option<u8>),
+    Incremental(&'p mut Option<bool>),
+}
+
+impl<'a> DictionaryVisitor<'a> for Params {
+    type Error = Infallible;
+
+    fn entry(&mut self, key: &'a KeyRef) -> Result<impl EntryVisitor<'a>, Self::Error> {
+        Ok(ParamVisitor(match key.as_str() {
+            "u" => {
+                self.urgency = None;
+                Some(Param::Urgency(&mut self.urgency))
+            }
+            "i" => {
+                self.incremental = None;
+                Some(Param::Incremental(&mut self.incremental))
+            }
+            _ => None,
+        }))
+    }
+}
+
+struct ParamVisitor<'p>(Option<Param<'p>>);
+
+impl<'a> ItemVisitor<'a> for ParamVisitor<'_> {
+    type Error = Infallible;
+
+    fn bare_item(
+        self,
+        bare_item: BareItemFromInput<'a>,
+    ) -> Result<impl ParameterVisitor<'a>, Self::Error> {
+        match self.0 {
+            Some(Param::Urgency(urgency)) => {
+                *urgency = bare_item
+                    .as_integer()
+                    .and_then(|v| u8::try_from(v).ok())
+                    .filter(|v| *v <= Priority::MAX_URGENCY);
+            }
+            Some(Param::Incremental(incremental)) => *incremental = bare_item.as_boolean(),
+            None => {}
+        }
+        Ok(Ignored)
+    }
+}
+
+impl<'a> EntryVisitor<'a> for ParamVisitor<'_> {
+    fn inner_list(self) -> Result<impl InnerListVisitor<'a>, Self::Error> {
+        Ok(Ignored)
+    }
+}
@@ -0,0 +1,330 @@
+use crate::borrowed;
+use crate::typed::{FromBareItem, FromSfvDictionary, ToSfvDictionary};
+use crate::{
+    key_ref, BareItem, BareItemFromInput, DictSerializer, Error, ErrorKind, InnerListSerializer,
+    Integer, Key, KeyRef, ListSerializer, Output, Parser, RefBareItem, SFVResult, String,
+};
+
+use alloc::borrow::ToOwned;
+use alloc::string::String as StdString;
+use alloc::vec::Vec;
+
+/// A component identifier covered by an HTTP message signature, such as
+/// `"@method"` or `"example-dict";sf`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComponentIdentifier {
+    /// The component name, which is a lowercase field name or a derived
+    /// component name starting with `@`.
+    pub name: String,
+    /// The component's parameters, such as `sf` or `key`, in order.
+    pub parameters: Vec<(Key, BareItem)>,
+}
+
+impl ComponentIdentifier {
+    /// Creates a component identifier without parameters.
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            parameters: Vec::new(),
+        }
+    }
+
+    fn from_item_value(item: borrowed::Item<'_>) -> SFVResult<Self> {
+        Ok(Self {
+            name: String::from_bare_item(item.bare_item)?,
+            parameters: owned_parameters(item.params),
+        })
+    }
+}
+
+fn owned_parameters(params: borrowed::Parameters<'_>) -> Vec<(Key, BareItem)> {
+    params
+        .into_iter()
+        .map(|(key, value)| (key.to_owned(), value.into()))
+        .collect()
+}
+
+/// The parameters of an HTTP message signature: the covered components and
+/// the signature parameters, such as `created` and `keyid`.
+///
+/// This is the value of a member of the `Signature-Input` field, as well as
+/// the value of the `@signature-params` component in the signature base
+/// defined by [RFC 9421]. The latter must be reproduced exactly, so parameters
+/// parsed from a field value are serialized in the order in which they
+/// appeared, followed by any other parameters that have been set since.
+///
+/// Equality ignores that order, so equal values can serialize differently.
+/// Compare the output of [`SignatureParams::to_sfv_inner_list`] instead where
+/// the exact `@signature-params` value matters.
+///
+/// [RFC 9421]: <https://httpwg.org/specs/rfc9421.html#signature-params>
+///
+/// # Examples
+/// ```
+/// # use sfv::fields::{ComponentIdentifier, SignatureInput, SignatureParams};
+/// # use sfv::typed::FromSfvDictionary;
+/// # use sfv::{integer, string_ref, Parser};
+/// # fn main() -> Result<(), sfv::Error> {
+/// let input = SignatureInput::from_sfv_dictionary(Parser::new(
+///     r#"sig1=("@method" "content-type";sf);keyid="test-key";created=1618884473"#,
+/// ))?;
+/// let params = input.get("sig1").unwrap();
+/// assert_eq!(params.created, Some(integer(1618884473)));
+///
+/// let base_line = format!("\"@signature-params\": {}", params.to_sfv_inner_list());
+/// assert_eq!(
+///     base_line,
+///     r#""@signature-params": ("@method" "content-type";sf);keyid="test-key";created=1618884473"#,
+/// );
+///
+/// let mut params = SignatureParams::new(vec![
+///     ComponentIdentifier::new(string_ref("@path").to_owned()),
+/// ]);
+/// params.alg = Some(string_ref("ed25519").to_owned());
+/// params.created = Some(integer(1618884473));
+/// assert_eq!(
+///     params.to_sfv_inner_list(),
+///     r#"("@path");created=1618884473;alg="ed25519""#,
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SignatureParams {
+    /// The covered components, in order.
+    pub components: Vec<ComponentIdentifier>,
+    /// The creation time as a UNIX timestamp (`created`).
+    pub created: Option<Integer>,
+    /// The expiration time as a UNIX timestamp (`expires`).
+    pub expires: Option<Integer>,
+    /// A random unique value for the signature (`nonce`).
+    pub nonce: Option<String>,
+    /// The signature algorithm (`alg`).
+    pub alg: Option<String>,
+    /// The identifier of the signing key (`keyid`).
+    pub keyid: Option<String>,
+    /// An application-specific tag for the signature (`tag`).
+    pub tag: Option<String>,
+    /// Any other parameters, in order.
+    ///
+    /// Parameters with the keys of those above are ignored when serializing.
+    pub extensions: Vec<(Key, BareItem)>,
+    // The keys of the parsed parameters, in order of appearance.
+    order: Vec<Key>,
+}
+
+// Parameters are compared regardless of the order in which they were parsed.
+impl PartialEq for SignatureParams {
+    fn eq(&self, other: &Self) -> bool {
+        self.components == other.components
+            && self.created == other.created
+            && self.expires == other.expires
+            && self.nonce == other.nonce
+            && self.alg == other.alg
+            && self.keyid == other.keyid
+            && self.tag == other.tag
+            && self.extensions == other.extensions
+    }
+}
+
+impl SignatureParams {
+    /// Creates signature parameters covering the given components, without
+    /// any other parameters.
+    pub fn new(components: Vec<ComponentIdentifier>) -> Self {
+        Self {
+            components,
+            ..Self::default()
+        }
+    }
+
+    fn from_inner_list(inner_list: borrowed::InnerList<'_>) -> SFVResult<Self> {
+        let mut params = Self::new(
+            inner_list
+                .items
+                .into_iter()
+                .map(ComponentIdentifier::from_item_value)
+                .collect::<SFVResult<_>>()?,
+        );
+
+        for (key, value) in inner_list.params {
+            params.order.push(key.to_owned());
+            match key.as_str() {
+                "created" => params.created = Some(Integer::from_bare_item(value)?),
+                "expires" => params.expires = Some(Integer::from_bare_item(value)?),
+                "nonce" => params.nonce = Some(String::from_bare_item(value)?),
+                "alg" => params.alg = Some(String::from_bare_item(value)?),
+                "keyid" => params.keyid = Some(String::from_bare_item(value)?),
+                "tag" => params.tag = Some(String::from_bare_item(value)?),
+                _ => params.extensions.push((key.to_owned(), value.into())),
+            }
+        }
+        Ok(params)
+    }
+
+    fn parameters(&self) -> Vec<(&KeyRef, RefBareItem<'_>)> {
+        let known = [
+            ("created", self.created.map(RefBareItem::Integer)),
+            ("expires", self.expires.map(RefBareItem::Integer)),
+            ("nonce", self.nonce.as_deref().map(RefBareItem::String)),
+            ("alg", self.alg.as_deref().map(RefBareItem::String)),
+            ("keyid", self.keyid.as_deref().map(RefBareItem::String)),
+            ("tag", self.tag.as_deref().map(RefBareItem::String)),
+        ];
+
+        let is_known = |key: &KeyRef| known.iter().any(|(k, _)| *k == key.as_str());
+
+        let mut params: Vec<_> = known
+            .iter()
+            .filter_map(|(key, value)| Some((key_ref(key), (*value)?)))
+            .chain(
+                self.extensions
+                    .iter()
+                    .filter(|(key, _)| !is_known(key))
+                    .map(|(key, value)| (&**key, RefBareItem::from(value))),
+            )
+            .collect();
+
+        // The sort is stable, so parameters that were not parsed keep the
+        // order above.
+        params.sort_by_key(|(key, _)| {
+            self.order
+                .iter()
+                .position(|k| k.as_str() == key.as_str())
+                .unwrap_or(usize::MAX)
+        });
+        params
+    }
+
+    /// Serializes the covered components and the parameters into the given
+    /// inner list.
+    pub fn serialize_inner_list<W: Output>(&self, mut ser: InnerListSerializer<'_, W>) {
+        for component in &self.components {
+            ser.bare_item(&component.name)
+                .parameters(component.parameters.iter().map(|(key, value)| (key, value)));
+        }
+        ser.finish().parameters(self.parameters());
+    }
+
+    /// Serializes the covered components and the parameters as an inner
+    /// list, which is the value of the `@signature-params` component.
+    pub fn to_sfv_inner_list(&self) -> StdString {
+        let mut ser = ListSerializer::new();
+        self.serialize_inner_list(ser.inner_list());
+        match ser.finish() {
+            Ok(output) => output,
+            Err(_) => unreachable!(),
+        }
+    }
+}
+
+/// The `Signature-Input` field, which maps signature labels to the
+/// parameters of the corresponding signatures.
+///
+/// Members that are not inner lists, components that are not strings, and
+/// known signature parameters with the wrong type result in an
+/// [`ErrorKind::TypeMismatch`] error.
+///
+/// See [RFC 9421].
+///
+/// [RFC 9421]: <https://httpwg.org/specs/rfc9421.html#signature-input-header>
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SignatureInput {
+    /// The signature parameters, by label, in order.
+    pub signatures: Vec<(Key, SignatureParams)>,
+}
+
+impl SignatureInput {
+    /// Returns the parameters of the signature with the given label.
+    pub fn get(&self, label: &str) -> Option<&SignatureParams> {
+        self.signatures
+            .iter()
+            .find(|(key, _)| key.as_str() == label)
+            .map(|(_, params)| params)
+    }
+}
+
+impl FromSfvDictionary for SignatureInput {
+    fn from_sfv_dictionary(parser: Parser<'_>) -> SFVResult<Self> {
+        let mut dict = borrowed::Dictionary::default();
+        parser.parse_dictionary_with_visitor(&mut dict)?;
+
+        let signatures = dict
+            .into_iter()
+            .map(|(label, entry)| match entry {
+                borrowed::ListEntry::InnerList(inner_list) => Ok((
+                    label.to_owned(),
+                    SignatureParams::from_inner_list(inner_list)?,
+                )),
+                borrowed::ListEntry::Item(_) => Err(Error::new(
+                    ErrorKind::TypeMismatch,
+                    "expected inner list, found item",
+                )),
+            })
+            .collect::<SFVResult<_>>()?;
+        Ok(Self { signatures })
+    }
+}
+
+impl ToSfvDictionary for SignatureInput {
+    fn serialize_dictionary<W: Output>(&self, ser: &mut DictSerializer<W>) -> SFVResult<()> {
+        for (label, params) in &self.signatures {
+            params.serialize_inner_list(ser.inner_list(label));
+        }
+        Ok(())
+    }
+}
+
+/// The `Signature` field, which maps signature labels to signature values.
+///
+/// Parameters of the members are ignored. Members that are not byte
+/// sequences result in an [`ErrorKind::TypeMismatch`] error.
+///
+/// See [RFC 9421].
+///
+/// [RFC 9421]: <https://httpwg.org/specs/rfc9421.html#signature-header>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Signature {
+    /// The signature values, by label, in order.
+    pub signatures: Vec<(Key, Vec<u8>)>,
+}
+
+impl Signature {
+    /// Returns the value of the signature with the given label.
+    pub fn get(&self, label: &str) -> Option<&[u8]> {
+        self.signatures
+            .iter()
+            .find(|(key, _)| key.as_str() == label)
+            .map(|(_, value)| value.as_slice())
+    }
+}
+
+impl FromSfvDictionary for Signature {
+    fn from_sfv_dictionary(parser: Parser<'_>) -> SFVResult<Self> {
+        let mut dict = borrowed::Dictionary::default();
+        parser.parse_dictionary_with_visitor(&mut dict)?;
+
+        let signatures = dict
+            .into_iter()
+            .map(|(label, entry)| match entry {
+                borrowed::ListEntry::Item(borrowed::Item {
+                    bare_item: BareItemFromInput::ByteSequence(value),
+                    ..
+                }) => Ok((label.to_owned(), value)),
+                _ => Err(Error::new(
+                    ErrorKind::TypeMismatch,
+                    "expected byte sequence",
+                )),
+            })
+            .collect::<SFVResult<_>>()?;
+        Ok(Self { signatures })
+    }
+}
+
+impl ToSfvDictionary for Signature {
+    fn serialize_dictionary<W: Output>(&self, ser: &mut DictSerializer<W>) -> SFVResult<()> {
+        for (label, value) in &self.signatures {
+            ser.bare_item(label, value.as_slice());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,563 @@
+use crate::borrowed;
+use crate::typed::{FromBareItem, FromSfvItem, ToBareItem, ToSfvItem};
+use crate::{
+    key_ref, BareItem, BareItemFromInput, Error, ErrorKind, Integer, Key, KeyRef, ListSerializer,
+    Output, ParameterSerializer, RefBareItem, SFVResult, String, Token, TokenRef,
+};
+
+use alloc::borrow::ToOwned;
+use alloc::string::String as StdString;
+use alloc::vec::Vec;
+
+/// The name of a cache or proxy, or another value that can be either a token
+/// or a string.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Identifier {
+    /// A token.
+    Token(Token),
+    /// A string.
+    String(String),
+}
+
+impl Identifier {
+    /// Returns the identifier as a string slice.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Token(token) => token.as_str(),
+            Self::String(string) => string.as_str(),
+        }
+    }
+}
+
+impl From<Token> for Identifier {
+    fn from(token: Token) -> Self {
+        Self::Token(token)
+    }
+}
+
+impl From<String> for Identifier {
+    fn from(string: String) -> Self {
+        Self::String(string)
+    }
+}
+
+impl FromBareItem for Identifier {
+    fn from_bare_item(bare_item: BareItemFromInput<'_>) -> SFVResult<Self> {
+        match bare_item {
+            BareItemFromInput::Token(token) => Ok(Self::Token(token.to_owned())),
+            BareItemFromInput::String(string) => Ok(Self::String(string.into_owned())),
+            _ => Err(Error::new(
+                ErrorKind::TypeMismatch,
+                "expected token or string",
+            )),
+        }
+    }
+}
+
+impl ToBareItem for Identifier {
+    fn to_bare_item(&self) -> SFVResult<RefBareItem<'_>> {
+        Ok(match self {
+            Self::Token(token) => RefBareItem::Token(token),
+            Self::String(string) => RefBareItem::String(string),
+        })
+    }
+}
+
+// Defines an enum of the registered values of a token parameter, with a
+// catch-all variant for other values.
+macro_rules! token_enum {
+    (
+        $(#[$attr:meta])*
+        pub enum $name:ident {
+            $($(#[$variant_attr:meta])* $variant:ident = $token:literal,)+
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$variant_attr])* $variant,)+
+            /// Any other value.
+            Other(Token),
+        }
+
+        impl $name {
+            /// Returns the value with the given token.
+            pub fn from_token(token: &TokenRef) -> Self {
+                match token.as_str() {
+                    $($token => Self::$variant,)+
+                    _ => Self::Other(token.to_owned()),
+                }
+            }
+
+            /// Returns the value's token.
+            pub fn as_token(&self) -> &TokenRef {
+                match self {
+                    $(Self::$variant => TokenRef::constant($token),)+
+                    Self::Other(token) => token,
+                }
+            }
+        }
+
+        impl FromBareItem for $name {
+            fn from_bare_item(bare_item: BareItemFromInput<'_>) -> SFVResult<Self> {
+                Token::from_bare_item(bare_item).map(|token| Self::from_token(&token))
+            }
+        }
+
+        impl ToBareItem for $name {
+            fn to_bare_item(&self) -> SFVResult<RefBareItem<'_>> {
+                Ok(RefBareItem::Token(self.as_token()))
+            }
+        }
+    };
+}
+
+token_enum! {
+    /// The reason that a request was forwarded by a cache, from the `fwd`
+    /// parameter of the `Cache-Status` field.
+    pub enum ForwardReason {
+        /// The cache was configured to not handle the request.
+        Bypass = "bypass",
+        /// The request method's semantics require it to be forwarded.
+        Method = "method",
+        /// The cache did not contain any responses that matched the request
+        /// URI.
+        UriMiss = "uri-miss",
+        /// The cache contained a response that matched the request URI but
+        /// not its selecting header fields.
+        VaryMiss = "vary-miss",
+        /// The cache did not contain any responses that could be used to
+        /// satisfy the request.
+        Miss = "miss",
+        /// The cache was able to select a fresh response, but the request's
+        /// semantics did not allow its use.
+        Request = "request",
+        /// The cache was able to select a response, but it was stale.
+        Stale = "stale",
+        /// The cache was able to select a partial response, but it did not
+        /// contain all of the requested ranges.
+        Partial = "partial",
+    }
+}
+
+token_enum! {
+    /// The type of error encountered by a proxy, from the `error` parameter of
+    /// the `Proxy-Status` field.
+    pub enum ProxyErrorType {
+        /// DNS Timeout.
+        DnsTimeout = "dns_timeout",
+        /// DNS Error.
+        DnsError = "dns_error",
+        /// Destination Not Found.
+        DestinationNotFound = "destination_not_found",
+        /// Destination Unavailable.
+        DestinationUnavailable = "destination_unavailable",
+        /// Destination IP Prohibited.
+        DestinationIpProhibited = "destination_ip_prohibited",
+        /// Destination IP Unroutable.
+        DestinationIpUnroutable = "destination_ip_unroutable",
+        /// Connection Refused.
+        ConnectionRefused = "connection_refused",
+        /// Connection Terminated.
+        ConnectionTerminated = "connection_terminated",
+        /// Connection Timeout.
+        ConnectionTimeout = "connection_timeout",
+        /// Connection Read Timeout.
+        ConnectionReadTimeout = "connection_read_timeout",
+        /// Connection Write Timeout.
+        ConnectionWriteTimeout = "connection_write_timeout",
+        /// Connection Pool Exhausted.
+        ConnectionLimitReached = "connection_limit_reached",
+        /// TLS Protocol Error.
+        TlsProtocolError = "tls_protocol_error",
+        /// TLS Certificate Error.
+        TlsCertificateError = "tls_certificate_error",
+        /// TLS Alert Received.
+        TlsAlertReceived = "tls_alert_received",
+        /// HTTP Request Error.
+        HttpRequestError = "http_request_error",
+        /// HTTP Request Denied.
+        HttpRequestDenied = "http_request_denied",
+        /// HTTP Incomplete Response.
+        HttpResponseIncomplete = "http_response_incomplete",
+        /// HTTP Response Header Section Too Large.
+        HttpResponseHeaderSectionSize = "http_response_header_section_size",
+        /// HTTP Response Header Field Line Too Large.
+        HttpResponseHeaderSize = "http_response_header_size",
+        /// HTTP Response Body Too Large.
+        HttpResponseBodySize = "http_response_body_size",
+        /// HTTP Response Trailer Section Too Large.
+        HttpResponseTrailerSectionSize = "http_response_trailer_section_size",
+        /// HTTP Response Trailer Field Line Too Large.
+        HttpResponseTrailerSize = "http_response_trailer_size",
+        /// HTTP Response Transfer-Coding Error.
+        HttpResponseTransferCoding = "http_response_transfer_coding",
+        /// HTTP Response Content-Coding Error.
+        HttpResponseContentCoding = "http_response_content_coding",
+        /// HTTP Response Timeout.
+        HttpResponseTimeout = "http_response_timeout",
+        /// HTTP Upgrade Failed.
+        HttpUpgradeFailed = "http_upgrade_failed",
+        /// HTTP Protocol Error.
+        HttpProtocolError = "http_protocol_error",
+        /// Proxy Internal Response.
+        ProxyInternalResponse = "proxy_internal_response",
+        /// Proxy Internal Error.
+        ProxyInternalError = "proxy_internal_error",
+        /// Proxy Configuration Error.
+        ProxyConfigurationError = "proxy_configuration_error",
+        /// Proxy Loop Detected.
+        ProxyLoopDetected = "proxy_loop_detected",
+    }
+}
+
+fn owned_parameter(key: &KeyRef, value: BareItemFromInput<'_>) -> (Key, BareItem) {
+    (key.to_owned(), value.into())
+}
+
+// Serializes the extension parameters, skipping those with the keys of known
+// parameters, which have already been serialized.
+fn serialize_extensions<W: Output>(
+    ser: ParameterSerializer<W>,
+    extensions: &[(Key, BareItem)],
+    known: &[&str],
+) -> ParameterSerializer<W> {
+    ser.parameters(
+        extensions
+            .iter()
+            .filter(|(key, _)| !known.contains(&key.as_str()))
+            .map(|(key, value)| (key, value)),
+    )
+}
+
+// Serializes the member separately so that the field value is left unchanged
+// on failure.
+fn append_member(field_value: &mut StdString, member: &impl ToSfvItem) -> SFVResult<()> {
+    let mut ser = ListSerializer::new();
+    member.serialize_item(|bare_item| ser.bare_item(bare_item))?;
+    let member = ser.finish()?;
+
+    if !field_value.is_empty() {
+        field_value.push_str(", ");
+    }
+    field_value.push_str(&member);
+    Ok(())
+}
+
+/// A member of the `Cache-Status` field, which describes how a cache handled
+/// a response.
+///
+/// Unknown parameters are kept in [`CacheStatus::extensions`], and known
+/// parameters with the wrong type result in an [`ErrorKind::TypeMismatch`]
+/// error.
+///
+/// Each cache appends a member to the field, so the field's value is parsed
+/// and serialized as a `Vec<CacheStatus>`, whose first member is the cache
+/// closest to the origin server.
+///
+/// See [RFC 9211].
+///
+/// [RFC 9211]: <https://httpwg.org/specs/rfc9211.html>
+///
+/// # Examples
+/// ```
+/// # use sfv::fields::{CacheStatus, ForwardReason};
+/// # use sfv::typed::FromSfvList;
+/// # use sfv::{integer, token_ref, Parser};
+/// # fn main() -> Result<(), sfv::Error> {
+/// let statuses = Vec::<CacheStatus>::from_sfv_list(Parser::new(
+///     r#"OriginCache; hit; ttl=1100, "CDN Company Here"; fwd=uri-miss; stored"#,
+/// ))?;
+/// assert!(statuses[0].hit);
+/// assert_eq!(statuses[1].fwd, Some(ForwardReason::UriMiss));
+///
+/// let mut field_value = String::from("OriginCache;hit");
+/// let mut status = CacheStatus::new(token_ref("ExampleCache").to_owned().into());
+/// status.fwd = Some(ForwardReason::Stale);
+/// status.fwd_status = Some(304);
+/// status.ttl = Some(integer(-30));
+/// status.append_to(&mut field_value)?;
+///
+/// assert_eq!(field_value, "OriginCache;hit, ExampleCache;fwd=stale;fwd-status=304;ttl=-30");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct CacheStatus {
+    /// The identifier of the cache.
+    pub cache: Identifier,
+    /// Whether the request was satisfied by the cache (`hit`).
+    pub hit: bool,
+    /// Why the request was forwarded towards the origin (`fwd`).
+    pub fwd: Option<ForwardReason>,
+    /// The status code of the forwarded request's response (`fwd-status`).
+    pub fwd_status: Option<u16>,
+    /// The response's remaining freshness lifetime in seconds, which is
+    /// negative if it is stale (`ttl`).
+    pub ttl: Option<Integer>,
+    /// Whether the cache stored the forwarded request's response (`stored`).
+    pub stored: bool,
+    /// Whether the request was collapsed with another one (`collapsed`).
+    pub collapsed: bool,
+    /// A representation of the cache key used for the response (`key`).
+    pub key: Option<String>,
+    /// Implementation-specific details (`detail`).
+    pub detail: Option<Identifier>,
+    /// Any other parameters, in order.
+    ///
+    /// Parameters with the keys of those above are ignored when serializing.
+    pub extensions: Vec<(Key, BareItem)>,
+}
+
+impl CacheStatus {
+    /// Creates a member for the given cache without any parameters.
+    pub fn new(cache: Identifier) -> Self {
+        Self {
+            cache,
+            hit: false,
+            fwd: None,
+            fwd_status: None,
+            ttl: None,
+            stored: false,
+            collapsed: false,
+            key: None,
+            detail: None,
+            extensions: Vec::new(),
+        }
+    }
+
+    /// Appends `self` as the last member of the given `Cache-Status` field
+    /// value, which may be empty.
+    pub fn append_to(&self, field_value: &mut StdString) -> SFVResult<()> {
+        append_member(field_value, self)
+    }
+}
+
+impl FromSfvItem for CacheStatus {
+    fn from_item_value(item: borrowed::Item<'_>) -> SFVResult<Self> {
+        let mut status = Self::new(Identifier::from_bare_item(item.bare_item)?);
+        for (key, value) in item.params {
+            match key.as_str() {
+                "hit" => status.hit = bool::from_bare_item(value)?,
+                "fwd" => status.fwd = Some(ForwardReason::from_bare_item(value)?),
+                "fwd-status" => status.fwd_status = Some(u16::from_bare_item(value)?),
+                "ttl" => status.ttl = Some(Integer::from_bare_item(value)?),
+                "stored" => status.stored = bool::from_bare_item(value)?,
+                "collapsed" => status.collapsed = bool::from_bare_item(value)?,
+                "key" => status.key = Some(String::from_bare_item(value)?),
+                "detail" => status.detail = Some(Identifier::from_bare_item(value)?),
+                _ => status.extensions.push(owned_parameter(key, value)),
+            }
+        }
+        Ok(status)
+    }
+}
+
+impl ToSfvItem for CacheStatus {
+    fn serialize_item<W: Output>(
+        &self,
+        bare_item: impl FnOnce(RefBareItem<'_>) -> ParameterSerializer<W>,
+    ) -> SFVResult<ParameterSerializer<W>> {
+        let mut ser = bare_item(self.cache.to_bare_item()?);
+        if self.hit {
+            ser = ser.parameter(key_ref("hit"), true);
+        }
+        if let Some(fwd) = &self.fwd {
+            ser = ser.parameter(key_ref("fwd"), fwd.as_token());
+        }
+        if let Some(fwd_status) = self.fwd_status {
+            ser = ser.parameter(key_ref("fwd-status"), Integer::from(fwd_status));
+        }
+        if let Some(ttl) = self.ttl {
+            ser = ser.parameter(key_ref("ttl"), ttl);
+        }
+        if self.stored {
+            ser = ser.parameter(key_ref("stored"), true);
+        }
+        if self.collapsed {
+            ser = ser.parameter(key_ref("collapsed"), true);
+        }
+        if let Some(key) = &self.key {
+            ser = ser.parameter(key_ref("key"), key);
+        }
+        if let Some(detail) = &self.detail {
+            ser = ser.parameter(key_ref("detail"), detail.to_bare_item()?);
+        }
+        Ok(serialize_extensions(
+            ser,
+            &self.extensions,
+            &[
+                "hit",
+                "fwd",
+                "fwd-status",
+                "ttl",
+                "stored",
+                "collapsed",
+                "key",
+                "detail",
+            ],
+        ))
+    }
+}
+
+/// A member of the `Proxy-Status` field, which describes how a proxy handled
+/// a response.
+///
+/// Unknown parameters, including those specific to an error type such as
+/// `rcode` or `alert-id`, are kept in [`ProxyStatus::extensions`], and known
+/// parameters with the wrong type result in an [`ErrorKind::TypeMismatch`]
+/// error.
+///
+/// Each proxy appends a member to the field, so the field's value is parsed
+/// and serialized as a `Vec<ProxyStatus>`, whose first member is the proxy
+/// closest to the origin server.
+///
+/// See [RFC 9209].
+///
+/// [RFC 9209]: <https://httpwg.org/specs/rfc9209.html>
+///
+/// # Examples
+/// ```
+/// # use sfv::fields::{ProxyErrorType, ProxyStatus};
+/// # use sfv::typed::FromSfvList;
+/// # use sfv::{string_ref, token_ref, Parser};
+/// # fn main() -> Result<(), sfv::Error> {
+/// let statuses = Vec::<ProxyStatus>::from_sfv_list(Parser::new(
+///     r#"ExampleCDN; error=http_protocol_error; details="Malformed response header: space before colon""#,
+/// ))?;
+/// assert_eq!(statuses[0].error, Some(ProxyErrorType::HttpProtocolError));
+///
+/// let mut field_value = String::new();
+/// let mut status = ProxyStatus::new(string_ref("proxy.example.net").to_owned().into());
+/// status.next_hop = Some(token_ref("backend.example.org").to_owned().into());
+/// status.next_protocol = Some(b"h2".to_vec());
+/// status.received_status = Some(503);
+/// status.append_to(&mut field_value)?;
+///
+/// assert_eq!(
+///     field_value,
+///     r#""proxy.example.net";next-hop=backend.example.org;next-protocol=h2;received-status=503"#,
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProxyStatus {
+    /// The identifier of the proxy.
+    pub proxy: Identifier,
+    /// The type of error encountered by the proxy (`error`).
+    pub error: Option<ProxyErrorType>,
+    /// The identity of the next hop server (`next-hop`).
+    pub next_hop: Option<Identifier>,
+    /// The ALPN protocol identifier used to connect to the next hop
+    /// (`next-protocol`).
+    ///
+    /// This is serialized as a token if possible, and as a byte sequence
+    /// otherwise.
+    pub next_protocol: Option<Vec<u8>>,
+    /// The status code the proxy received from the next hop
+    /// (`received-status`).
+    pub received_status: Option<u16>,
+    /// Additional, implementation-specific details (`details`).
+    pub details: Option<String>,
+    /// Any other parameters, in order.
+    ///
+    /// Parameters with the keys of those above are ignored when serializing.
+    pub extensions: Vec<(Key, BareItem)>,
+}
+
+impl ProxyStatus {
+    /// Creates a member for the given proxy without any parameters.
+    pub fn new(proxy: Identifier) -> Self {
+        Self {
+            proxy,
+            error: None,
+            next_hop: None,
+            next_protocol: None,
+            received_status: None,
+            details: None,
+            extensions: Vec::new(),
+        }
+    }
+
+    /// Appends `self` as the last member of the given `Proxy-Status` field
+    /// value, which may be empty.
+    pub fn append_to(&self, field_value: &mut StdString) -> SFVResult<()> {
+        append_member(field_value, self)
+    }
+}
+
+fn next_protocol_from_bare_item(bare_item: BareItemFromInput<'_>) -> SFVResult<Vec<u8>> {
+    match bare_item {
+        BareItemFromInput::Token(token) => Ok(token.as_str().as_bytes().to_vec()),
+        BareItemFromInput::ByteSequence(bytes) => Ok(bytes),
+        _ => Err(Error::new(
+            ErrorKind::TypeMismatch,
+            "expected token or byte sequence",
+        )),
+    }
+}
+
+fn next_protocol_to_bare_item(protocol: &[u8]) -> RefBareItem<'_> {
+    match core::str::from_utf8(protocol).map(TokenRef::from_str) {
+        Ok(Ok(token)) => RefBareItem::Token(token),
+        _ => RefBareItem::ByteSequence(protocol),
+    }
+}
+
+impl FromSfvItem for ProxyStatus {
+    fn from_item_value(item: borrowed::Item<'_>) -> SFVResult<Self> {
+        let mut status = Self::new(Identifier::from_bare_item(item.bare_item)?);
+        for (key, value) in item.params {
+            match key.as_str() {
+                "error" => status.error = Some(ProxyErrorType::from_bare_item(value)?),
+                "next-hop" => status.next_hop = Some(Identifier::from_bare_item(value)?),
+                "next-protocol" => {
+                    status.next_protocol = Some(next_protocol_from_bare_item(value)?)
+                }
+                "received-status" => status.received_status = Some(u16::from_bare_item(value)?),
+                "details" => status.details = Some(String::from_bare_item(value)?),
+                _ => status.extensions.push(owned_parameter(key, value)),
+            }
+        }
+        Ok(status)
+    }
+}
+
+impl ToSfvItem for ProxyStatus {
+    fn serialize_item<W: Output>(
+        &self,
+        bare_item: impl FnOnce(RefBareItem<'_>) -> ParameterSerializer<W>,
+    ) -> SFVResult<ParameterSerializer<W>> {
+        let mut ser = bare_item(self.proxy.to_bare_item()?);
+        if let Some(error) = &self.error {
+            ser = ser.parameter(key_ref("error"), error.as_token());
+        }
+        if let Some(next_hop) = &self.next_hop {
+            ser = ser.parameter(key_ref("next-hop"), next_hop.to_bare_item()?);
+        }
+        if let Some(next_protocol) = &self.next_protocol {
+            ser = ser.parameter(
+                key_ref("next-protocol"),
+                next_protocol_to_bare_item(next_protocol),
+            );
+        }
+        if let Some(received_status) = self.received_status {
+            ser = ser.parameter(key_ref("received-status"), Integer::from(received_status));
+        }
+        if let Some(details) = &self.details {
+            ser = ser.parameter(key_ref("details"), details);
+        }
+        Ok(serialize_extensions(
+            ser,
+            &self.extensions,
+            &[
+                "error",
+                "next-hop",
+                "next-protocol",
+                "received-status",
+                "details",
+            ],
+        ))
+    }
+}
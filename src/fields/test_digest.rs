@@ -0,0 +1,135 @@
+use crate::fields::{Digest, DigestAlgorithm, WantDigest};
+use crate::typed::{FromSfvDictionary, ToSfvDictionary};
+use crate::{key_ref, ErrorKind, Parser, SFVResult};
+
+// From https://httpwg.org/specs/rfc9530.html#name-server-returns-full-represe
+const SHA_256: &str = "sha-256=:X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=:";
+const SHA_512: &str = "sha-512=:WZDPaVn/7XgHaAy8pmojAkGWoRx2UFChF41A2svX+TaPm+AbwAgBWnrIiYllu7BNNyealdVLvRwEmTHWXvJwew==:";
+
+fn parse_digest(input: &str) -> SFVResult<Digest> {
+    Digest::from_sfv_dictionary(Parser::new(input))
+}
+
+fn parse_want(input: &str) -> SFVResult<WantDigest> {
+    WantDigest::from_sfv_dictionary(Parser::new(input))
+}
+
+#[test]
+fn test_digest_algorithm() {
+    for algorithm in [DigestAlgorithm::Sha256, DigestAlgorithm::Sha512] {
+        assert_eq!(
+            DigestAlgorithm::from_key(algorithm.key().as_str()),
+            Some(algorithm)
+        );
+    }
+    assert_eq!(DigestAlgorithm::Sha256.digest_len(), 32);
+    assert_eq!(DigestAlgorithm::Sha512.digest_len(), 64);
+    assert_eq!(DigestAlgorithm::from_key("md5"), None);
+    assert_eq!(DigestAlgorithm::from_key("SHA-256"), None);
+}
+
+#[test]
+fn test_parse_digest() -> SFVResult<()> {
+    let input = format!("{SHA_256};x=1, {SHA_512}, unixsum=:MzAy:");
+    let digest = parse_digest(&input)?;
+
+    assert_eq!(digest.digests.len(), 3);
+    assert_eq!(digest.get("sha-256").unwrap().len(), 32);
+    assert_eq!(digest.get("sha-512").unwrap().len(), 64);
+    assert_eq!(digest.get("unixsum"), Some(&b"302"[..]));
+    assert_eq!(digest.get("md5"), None);
+
+    assert_eq!(
+        digest.to_sfv_dictionary()?,
+        format!("{SHA_256}, {SHA_512}, unixsum=:MzAy:")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_parse_digest_errors() {
+    for input in ["sha-256=:MzAy:", "sha-512=:MzAy:", "sha-256=::"] {
+        let err = parse_digest(input).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TypeMismatch, "{input}");
+        assert!(err.to_string().starts_with("digest length"), "{input}");
+    }
+    for input in ["sha-256=\"abc\"", "sha-256", "sha-256=(:MzAy:)", "md5=1"] {
+        assert_eq!(
+            parse_digest(input).unwrap_err().kind(),
+            ErrorKind::TypeMismatch,
+            "{input}"
+        );
+    }
+}
+
+#[test]
+fn test_serialize_digest_errors() {
+    let digest = Digest {
+        digests: vec![
+            (key_ref("unixsum").to_owned(), b"302".to_vec()),
+            (key_ref("sha-256").to_owned(), vec![0; 31]),
+        ],
+    };
+    let err = digest.to_sfv_dictionary().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TypeMismatch);
+    assert_eq!(err.to_string(), "digest length does not match algorithm");
+    assert_eq!(
+        Digest::default().to_sfv_dictionary().unwrap_err().kind(),
+        ErrorKind::EmptyDictionary
+    );
+}
+
+#[test]
+fn test_parse_want_digest() -> SFVResult<()> {
+    let want = parse_want("sha-256=1, sha-512=3;q, unixsum=10, sha-256=5")?;
+    assert_eq!(want.weight("sha-256"), Some(5));
+    assert_eq!(want.weight("sha-512"), Some(3));
+    assert_eq!(want.weight("unixsum"), Some(10));
+    assert_eq!(want.weight("md5"), None);
+    assert_eq!(want.preferred(), Some(DigestAlgorithm::Sha256));
+    assert_eq!(
+        want.to_sfv_dictionary()?,
+        "sha-256=5, sha-512=3, unixsum=10"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_want_digest_preferred() -> SFVResult<()> {
+    assert_eq!(
+        parse_want("sha-512=2, sha-256=2")?.preferred(),
+        Some(DigestAlgorithm::Sha512)
+    );
+    assert_eq!(
+        parse_want("sha-512=0, sha-256=1")?.preferred(),
+        Some(DigestAlgorithm::Sha256)
+    );
+    assert_eq!(parse_want("sha-512=0, unixsum=10")?.preferred(), None);
+    Ok(())
+}
+
+#[test]
+fn test_want_digest_errors() {
+    for input in ["sha-256=11", "sha-256=-1", "sha-256=1000"] {
+        assert_eq!(
+            parse_want(input).unwrap_err().kind(),
+            ErrorKind::OutOfRange,
+            "{input}"
+        );
+    }
+    for input in ["sha-256", "sha-256=1.0", "sha-256=(1)", "sha-256=a"] {
+        assert_eq!(
+            parse_want(input).unwrap_err().kind(),
+            ErrorKind::TypeMismatch,
+            "{input}"
+        );
+    }
+
+    let want = WantDigest {
+        preferences: vec![(key_ref("sha-256").to_owned(), 11)],
+    };
+    assert_eq!(
+        want.to_sfv_dictionary().unwrap_err().kind(),
+        ErrorKind::OutOfRange
+    );
+}
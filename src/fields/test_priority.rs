@@ -0,0 +1,168 @@
+use crate::fields::Priority;
+use crate::typed::{FromSfvDictionary, ToSfvDictionary};
+use crate::{ErrorKind, Parser, SFVResult};
+
+fn parse(input: &str) -> SFVResult<Priority> {
+    Priority::from_sfv_dictionary(Parser::new(input))
+}
+
+#[test]
+fn test_parse() -> SFVResult<()> {
+    assert_eq!(parse("")?, Priority::default());
+    assert_eq!(
+        parse("u=0")?,
+        Priority {
+            urgency: 0,
+            incremental: false,
+        }
+    );
+    assert_eq!(
+        parse("i")?,
+        Priority {
+            urgency: 3,
+            incremental: true,
+        }
+    );
+    assert_eq!(
+        parse("u=7, i=?1")?,
+        Priority {
+            urgency: 7,
+            incremental: true,
+        }
+    );
+    assert_eq!(
+        parse("i=?0, u=1")?,
+        Priority {
+            urgency: 1,
+            incremental: false,
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_parse_ignores_unknown() -> SFVResult<()> {
+    assert_eq!(
+        parse("x=1, u=2;a=b, y=(1 2), i;z")?,
+        Priority {
+            urgency: 2,
+            incremental: true,
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_parse_ignores_invalid() -> SFVResult<()> {
+    for input in [
+        "u=8", "u=-1", "u=256", "u=1.0", "u=\"1\"", "u=a", "u=(1)", "i=1", "i=a", "i=(?1)",
+    ] {
+        assert_eq!(parse(input)?, Priority::default(), "{input}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_parse_duplicate_keys() -> SFVResult<()> {
+    assert_eq!(parse("u=1, u=5")?.urgency, 5);
+    assert_eq!(parse("u=1, u=9")?.urgency, 3);
+    assert!(!parse("i, i=?0")?.incremental);
+    assert!(!parse("i, i=1")?.incremental);
+    Ok(())
+}
+
+#[test]
+fn test_parse_invalid_dictionary() {
+    assert_eq!(parse("u=1,").unwrap_err().kind(), ErrorKind::TrailingComma);
+    assert!(parse("u=1 i").is_err());
+}
+
+#[test]
+fn test_merge() -> SFVResult<()> {
+    let mut priority = parse("u=5, i")?;
+
+    priority.merge(Parser::new(""))?;
+    assert_eq!(
+        priority,
+        Priority {
+            urgency: 5,
+            incremental: true,
+        }
+    );
+
+    priority.merge(Parser::new("u=0"))?;
+    assert_eq!(
+        priority,
+        Priority {
+            urgency: 0,
+            incremental: true,
+        }
+    );
+
+    priority.merge(Parser::new("u=8, i=?0"))?;
+    assert_eq!(
+        priority,
+        Priority {
+            urgency: 0,
+            incremental: false,
+        }
+    );
+
+    assert!(priority.merge(Parser::new("u=1, i, ")).is_err());
+    assert_eq!(
+        priority,
+        Priority {
+            urgency: 0,
+            incremental: false,
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_serialize() -> SFVResult<()> {
+    assert_eq!(
+        Priority {
+            urgency: 1,
+            incremental: false,
+        }
+        .to_sfv_dictionary()?,
+        "u=1"
+    );
+    assert_eq!(
+        Priority {
+            urgency: 3,
+            incremental: true,
+        }
+        .to_sfv_dictionary()?,
+        "i"
+    );
+    assert_eq!(
+        Priority {
+            urgency: 7,
+            incremental: true,
+        }
+        .to_sfv_dictionary()?,
+        "u=7, i"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_serialize_errors() {
+    assert!(Priority::default().is_default());
+    assert_eq!(
+        Priority::default().to_sfv_dictionary().unwrap_err().kind(),
+        ErrorKind::EmptyDictionary
+    );
+    assert_eq!(
+        Priority {
+            urgency: 8,
+            incremental: false,
+        }
+        .to_sfv_dictionary()
+        .unwrap_err()
+        .kind(),
+        ErrorKind::OutOfRange
+    );
+}
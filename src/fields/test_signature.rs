@@ -0,0 +1,213 @@
+use crate::fields::{ComponentIdentifier, Signature, SignatureInput, SignatureParams};
+use crate::typed::{FromSfvDictionary, ToSfvDictionary};
+use crate::{integer, key_ref, string_ref, BareItem, ErrorKind, Parser, SFVResult};
+
+// From https://httpwg.org/specs/rfc9421.html#name-minimal-signature-using-rsa
+const RFC_INPUT: &str =
+    r#"sig-b21=();created=1618884473;keyid="test-key-rsa-pss";nonce="b3k2pp5k7z-50gnwp.yemd""#;
+
+#[test]
+fn test_parse_signature_input() -> SFVResult<()> {
+    let input = SignatureInput::from_sfv_dictionary(Parser::new(
+        r#"sig1=("@method" "@authority" "example-dict";sf;key="a");created=1618884473;keyid="test-key";alg="ed25519", sig2=("@path");tag="app";x=?1;expires=1618884480"#,
+    ))?;
+    assert_eq!(input.signatures.len(), 2);
+
+    let sig1 = input.get("sig1").unwrap();
+    let names: Vec<_> = sig1.components.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, ["@method", "@authority", "example-dict"]);
+    assert_eq!(
+        sig1.components[2].parameters,
+        [
+            (key_ref("sf").to_owned(), BareItem::Boolean(true)),
+            (
+                key_ref("key").to_owned(),
+                BareItem::String(string_ref("a").to_owned())
+            ),
+        ]
+    );
+    assert_eq!(sig1.created, Some(integer(1618884473)));
+    assert_eq!(sig1.expires, None);
+    assert_eq!(sig1.keyid.as_deref(), Some(string_ref("test-key")));
+    assert_eq!(sig1.alg.as_deref(), Some(string_ref("ed25519")));
+    assert!(sig1.extensions.is_empty());
+
+    let sig2 = input.get("sig2").unwrap();
+    assert_eq!(sig2.tag.as_deref(), Some(string_ref("app")));
+    assert_eq!(sig2.expires, Some(integer(1618884480)));
+    assert_eq!(
+        sig2.extensions,
+        [(key_ref("x").to_owned(), BareItem::Boolean(true))]
+    );
+
+    assert!(input.get("sig3").is_none());
+    Ok(())
+}
+
+#[test]
+fn test_signature_params_round_trip() -> SFVResult<()> {
+    let input = SignatureInput::from_sfv_dictionary(Parser::new(RFC_INPUT))?;
+    let params = input.get("sig-b21").unwrap();
+    assert!(params.components.is_empty());
+    assert_eq!(
+        params.to_sfv_inner_list(),
+        RFC_INPUT.strip_prefix("sig-b21=").unwrap()
+    );
+    assert_eq!(input.to_sfv_dictionary()?, RFC_INPUT);
+
+    // Parameter order, including that of unknown parameters, is preserved.
+    for value in [
+        r#"sig=("@method" "a";bs;req);x=1;keyid="k";created=2;y"#,
+        r#"sig=();tag="t";expires=3;nonce="n";alg="a""#,
+        r#"sig=("content-type");created=1, other=("@query-param";name="q")"#,
+    ] {
+        let input = SignatureInput::from_sfv_dictionary(Parser::new(value))?;
+        assert_eq!(input.to_sfv_dictionary()?, value);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_signature_params_duplicate_parameters() -> SFVResult<()> {
+    let input = SignatureInput::from_sfv_dictionary(Parser::new(
+        r#"sig=();created=1;keyid="a";created=2"#,
+    ))?;
+    let params = input.get("sig").unwrap();
+    assert_eq!(params.created, Some(integer(2)));
+    assert_eq!(params.to_sfv_inner_list(), r#"();created=2;keyid="a""#);
+    Ok(())
+}
+
+#[test]
+fn test_signature_params_modified() -> SFVResult<()> {
+    let input =
+        SignatureInput::from_sfv_dictionary(Parser::new(r#"sig=("@path");keyid="k";created=1"#))?;
+    let mut params = input.get("sig").unwrap().clone();
+
+    params.created = None;
+    params.tag = Some(string_ref("t").to_owned());
+    params.nonce = Some(string_ref("n").to_owned());
+    params
+        .components
+        .push(ComponentIdentifier::new(string_ref("@method").to_owned()));
+    assert_eq!(
+        params.to_sfv_inner_list(),
+        r#"("@path" "@method");keyid="k";nonce="n";tag="t""#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_signature_params_new() -> SFVResult<()> {
+    let mut params = SignatureParams::new(vec![ComponentIdentifier {
+        name: string_ref("example-dict").to_owned(),
+        parameters: vec![(key_ref("key").to_owned(), string_ref("b").into())],
+    }]);
+    assert_eq!(params.to_sfv_inner_list(), r#"("example-dict";key="b")"#);
+
+    params
+        .extensions
+        .push((key_ref("ext").to_owned(), integer(5).into()));
+    params.expires = Some(integer(10));
+    params.created = Some(integer(1));
+    params.keyid = Some(string_ref("k").to_owned());
+    assert_eq!(
+        params.to_sfv_inner_list(),
+        r#"("example-dict";key="b");created=1;expires=10;keyid="k";ext=5"#
+    );
+
+    let input = SignatureInput {
+        signatures: vec![(key_ref("sig").to_owned(), params)],
+    };
+    assert_eq!(
+        input.to_sfv_dictionary()?,
+        r#"sig=("example-dict";key="b");created=1;expires=10;keyid="k";ext=5"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_signature_params_known_extensions() {
+    let mut params = SignatureParams::new(Vec::new());
+    params.created = Some(integer(1));
+    params
+        .extensions
+        .push((key_ref("created").to_owned(), integer(2).into()));
+    params
+        .extensions
+        .push((key_ref("tag").to_owned(), integer(3).into()));
+    assert_eq!(params.to_sfv_inner_list(), "();created=1");
+}
+
+#[test]
+fn test_signature_params_eq() -> SFVResult<()> {
+    let parse = |value| -> SFVResult<SignatureParams> {
+        let input = SignatureInput::from_sfv_dictionary(Parser::new(value))?;
+        Ok(input.signatures.into_iter().next().unwrap().1)
+    };
+
+    let parsed = parse(r#"sig=();keyid="k";created=1"#)?;
+    let reordered = parse(r#"sig=();created=1;keyid="k""#)?;
+    assert_eq!(parsed, reordered);
+    assert_ne!(parsed.to_sfv_inner_list(), reordered.to_sfv_inner_list());
+
+    let mut params = SignatureParams::new(Vec::new());
+    params.created = Some(integer(1));
+    params.keyid = Some(string_ref("k").to_owned());
+    assert_eq!(parsed, params);
+
+    params.tag = Some(string_ref("t").to_owned());
+    assert_ne!(parsed, params);
+    Ok(())
+}
+
+#[test]
+fn test_parse_signature_input_errors() {
+    for value in [
+        r#"sig=:AAAA:"#,
+        r#"sig=(method)"#,
+        r#"sig=("@method" 1)"#,
+        r#"sig=();created="1""#,
+        r#"sig=();expires=1.5"#,
+        r#"sig=();keyid=k"#,
+        r#"sig=();alg=?1"#,
+        r#"sig=();nonce=:AAAA:"#,
+        r#"sig=();tag=1"#,
+    ] {
+        assert_eq!(
+            SignatureInput::from_sfv_dictionary(Parser::new(value))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::TypeMismatch,
+            "{value}"
+        );
+    }
+
+    assert_eq!(
+        SignatureInput::from_sfv_dictionary(Parser::new("sig=(\"a\""))
+            .unwrap_err()
+            .kind(),
+        ErrorKind::InvalidInnerList
+    );
+}
+
+#[test]
+fn test_signature() -> SFVResult<()> {
+    let signature =
+        Signature::from_sfv_dictionary(Parser::new("sig1=:AQID:, sig2=:BAU=:;x=1, sig1=:Bg==:"))?;
+    assert_eq!(signature.get("sig1"), Some(&[6][..]));
+    assert_eq!(signature.get("sig2"), Some(&[4, 5][..]));
+    assert_eq!(signature.get("sig3"), None);
+    assert_eq!(signature.to_sfv_dictionary()?, "sig1=:Bg==:, sig2=:BAU=:");
+
+    for value in [r#"sig="AQID""#, "sig=(:AQID:)", "sig=?1"] {
+        assert_eq!(
+            Signature::from_sfv_dictionary(Parser::new(value))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::TypeMismatch,
+            "{value}"
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,231 @@
+use crate::fields::{CacheStatus, ForwardReason, Identifier, ProxyErrorType, ProxyStatus};
+use crate::typed::{FromSfvList, ToSfvList};
+use crate::{integer, key_ref, string_ref, token_ref, BareItem, ErrorKind, Parser, SFVResult};
+
+fn parse_cache_status(input: &str) -> SFVResult<Vec<CacheStatus>> {
+    Vec::from_sfv_list(Parser::new(input))
+}
+
+fn parse_proxy_status(input: &str) -> SFVResult<Vec<ProxyStatus>> {
+    Vec::from_sfv_list(Parser::new(input))
+}
+
+#[test]
+fn test_identifier() {
+    let token = Identifier::from(token_ref("ExampleCache").to_owned());
+    let string = Identifier::from(string_ref("Example Cache").to_owned());
+    assert_eq!(token.as_str(), "ExampleCache");
+    assert_eq!(string.as_str(), "Example Cache");
+    assert_ne!(
+        token,
+        Identifier::from(string_ref("ExampleCache").to_owned())
+    );
+}
+
+#[test]
+fn test_token_enums() {
+    assert_eq!(
+        ForwardReason::from_token(token_ref("vary-miss")),
+        ForwardReason::VaryMiss
+    );
+    assert_eq!(ForwardReason::VaryMiss.as_token(), token_ref("vary-miss"));
+
+    let other = ForwardReason::from_token(token_ref("custom"));
+    assert_eq!(other, ForwardReason::Other(token_ref("custom").to_owned()));
+    assert_eq!(other.as_token(), token_ref("custom"));
+
+    assert_eq!(
+        ProxyErrorType::from_token(token_ref("connection_limit_reached")),
+        ProxyErrorType::ConnectionLimitReached
+    );
+    assert_eq!(
+        ProxyErrorType::ProxyLoopDetected.as_token(),
+        token_ref("proxy_loop_detected")
+    );
+}
+
+#[test]
+fn test_parse_cache_status() -> SFVResult<()> {
+    let statuses = parse_cache_status(
+        r#"ReverseProxyCache; hit, ForwardProxyCache; fwd=uri-miss; collapsed; stored, "BrowserCache"; fwd=vary-miss; fwd-status=200; ttl=-10; key="/a"; detail=abc; x=1"#,
+    )?;
+    assert_eq!(statuses.len(), 3);
+
+    let mut expected = CacheStatus::new(token_ref("ReverseProxyCache").to_owned().into());
+    expected.hit = true;
+    assert_eq!(statuses[0], expected);
+
+    let mut expected = CacheStatus::new(token_ref("ForwardProxyCache").to_owned().into());
+    expected.fwd = Some(ForwardReason::UriMiss);
+    expected.collapsed = true;
+    expected.stored = true;
+    assert_eq!(statuses[1], expected);
+
+    let mut expected = CacheStatus::new(string_ref("BrowserCache").to_owned().into());
+    expected.fwd = Some(ForwardReason::VaryMiss);
+    expected.fwd_status = Some(200);
+    expected.ttl = Some(integer(-10));
+    expected.key = Some(string_ref("/a").to_owned());
+    expected.detail = Some(token_ref("abc").to_owned().into());
+    expected.extensions = vec![(key_ref("x").to_owned(), BareItem::Integer(integer(1)))];
+    assert_eq!(statuses[2], expected);
+
+    assert_eq!(
+        statuses.to_sfv_list()?,
+        r#"ReverseProxyCache;hit, ForwardProxyCache;fwd=uri-miss;stored;collapsed, "BrowserCache";fwd=vary-miss;fwd-status=200;ttl=-10;key="/a";detail=abc;x=1"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_parse_cache_status_false_booleans() -> SFVResult<()> {
+    let statuses = parse_cache_status("a;hit=?0;stored=?0;collapsed=?0;hit")?;
+    assert!(statuses[0].hit);
+    assert!(!statuses[0].stored);
+    assert!(!statuses[0].collapsed);
+    assert_eq!(statuses.to_sfv_list()?, "a;hit");
+    Ok(())
+}
+
+#[test]
+fn test_parse_cache_status_errors() {
+    for (input, kind) in [
+        ("1", ErrorKind::TypeMismatch),
+        ("(a)", ErrorKind::TypeMismatch),
+        ("a;hit=1", ErrorKind::TypeMismatch),
+        ("a;fwd=\"miss\"", ErrorKind::TypeMismatch),
+        ("a;fwd-status=a", ErrorKind::TypeMismatch),
+        ("a;fwd-status=70000", ErrorKind::OutOfRange),
+        ("a;ttl=1.5", ErrorKind::TypeMismatch),
+        ("a;key=a", ErrorKind::TypeMismatch),
+        ("a;detail=1", ErrorKind::TypeMismatch),
+    ] {
+        assert_eq!(
+            parse_cache_status(input).unwrap_err().kind(),
+            kind,
+            "{input}"
+        );
+    }
+}
+
+#[test]
+fn test_cache_status_append_to() -> SFVResult<()> {
+    let mut field_value = String::new();
+
+    let mut status = CacheStatus::new(token_ref("Origin").to_owned().into());
+    status.fwd = Some(ForwardReason::Other(token_ref("custom").to_owned()));
+    status.append_to(&mut field_value)?;
+    assert_eq!(field_value, "Origin;fwd=custom");
+
+    let mut status = CacheStatus::new(string_ref("CDN Cache").to_owned().into());
+    status.hit = true;
+    status.ttl = Some(integer(30));
+    status.append_to(&mut field_value)?;
+    assert_eq!(field_value, r#"Origin;fwd=custom, "CDN Cache";hit;ttl=30"#);
+
+    assert_eq!(parse_cache_status(&field_value)?.len(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_parse_proxy_status() -> SFVResult<()> {
+    let statuses = parse_proxy_status(
+        r#"SomeCDN; error=dns_error; rcode="NXDOMAIN", "proxy.example.org"; error=connection_timeout; next-hop="192.0.2.1"; next-protocol=:AAEC:; received-status=502; details="timeout", other; error=custom_error; next-protocol=h2"#,
+    )?;
+    assert_eq!(statuses.len(), 3);
+
+    let mut expected = ProxyStatus::new(token_ref("SomeCDN").to_owned().into());
+    expected.error = Some(ProxyErrorType::DnsError);
+    expected.extensions = vec![(
+        key_ref("rcode").to_owned(),
+        BareItem::String(string_ref("NXDOMAIN").to_owned()),
+    )];
+    assert_eq!(statuses[0], expected);
+
+    let mut expected = ProxyStatus::new(string_ref("proxy.example.org").to_owned().into());
+    expected.error = Some(ProxyErrorType::ConnectionTimeout);
+    expected.next_hop = Some(string_ref("192.0.2.1").to_owned().into());
+    expected.next_protocol = Some(vec![0, 1, 2]);
+    expected.received_status = Some(502);
+    expected.details = Some(string_ref("timeout").to_owned());
+    assert_eq!(statuses[1], expected);
+
+    assert_eq!(
+        statuses[2].error,
+        Some(ProxyErrorType::Other(token_ref("custom_error").to_owned()))
+    );
+    assert_eq!(statuses[2].next_protocol.as_deref(), Some(&b"h2"[..]));
+
+    assert_eq!(
+        statuses.to_sfv_list()?,
+        r#"SomeCDN;error=dns_error;rcode="NXDOMAIN", "proxy.example.org";error=connection_timeout;next-hop="192.0.2.1";next-protocol=:AAEC:;received-status=502;details="timeout", other;error=custom_error;next-protocol=h2"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_parse_proxy_status_errors() {
+    for (input, kind) in [
+        ("?1", ErrorKind::TypeMismatch),
+        ("a;error=\"dns_error\"", ErrorKind::TypeMismatch),
+        ("a;next-hop=1", ErrorKind::TypeMismatch),
+        ("a;next-protocol=\"h2\"", ErrorKind::TypeMismatch),
+        ("a;received-status=-1", ErrorKind::OutOfRange),
+        ("a;details=a", ErrorKind::TypeMismatch),
+    ] {
+        assert_eq!(
+            parse_proxy_status(input).unwrap_err().kind(),
+            kind,
+            "{input}"
+        );
+    }
+}
+
+#[test]
+fn test_proxy_status_append_to() -> SFVResult<()> {
+    let mut field_value = String::from("origin;error=http_request_denied");
+
+    let mut status = ProxyStatus::new(token_ref("edge").to_owned().into());
+    status.next_protocol = Some(b"http/1.1".to_vec());
+    status.append_to(&mut field_value)?;
+    assert_eq!(
+        field_value,
+        "origin;error=http_request_denied, edge;next-protocol=http/1.1"
+    );
+
+    status.next_protocol = Some(vec![0x16, 0x03]);
+    status.extensions = vec![(key_ref("alert-id").to_owned(), integer(40).into())];
+    status.append_to(&mut field_value)?;
+    assert_eq!(
+        field_value,
+        "origin;error=http_request_denied, edge;next-protocol=http/1.1, edge;next-protocol=:FgM=:;alert-id=40"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_known_extensions() -> SFVResult<()> {
+    let mut field_value = String::new();
+
+    let mut status = CacheStatus::new(token_ref("cache").to_owned().into());
+    status.hit = true;
+    status.extensions = vec![
+        (key_ref("hit").to_owned(), BareItem::Boolean(false)),
+        (key_ref("ttl").to_owned(), integer(5).into()),
+        (key_ref("x").to_owned(), integer(1).into()),
+    ];
+    status.append_to(&mut field_value)?;
+    assert_eq!(field_value, "cache;hit;x=1");
+
+    let mut field_value = String::new();
+
+    let mut status = ProxyStatus::new(token_ref("proxy").to_owned().into());
+    status.error = Some(ProxyErrorType::DnsTimeout);
+    status.extensions = vec![(
+        key_ref("error").to_owned(),
+        BareItem::Token(token_ref("other").to_owned()),
+    )];
+    status.append_to(&mut field_value)?;
+    assert_eq!(field_value, "proxy;error=dns_timeout");
+    Ok(())
+}
@@ -0,0 +1,128 @@
+#[cfg(feature = "parsed-types")]
+use crate::{Dictionary, Item, List, Parser, SFVResult};
+
+use http::header::HeaderValue;
+#[cfg(feature = "parsed-types")]
+use http::header::{AsHeaderName, HeaderMap};
+
+use std::borrow::Cow;
+
+/// Combines the given field lines into a single field value, as described in
+/// [RFC 9651].
+///
+/// The lines are joined with `", "`. A single line is returned without
+/// copying.
+///
+/// The result can be passed to [`Parser::new`][crate::Parser::new], which
+/// also accepts a single [`HeaderValue`] directly.
+///
+/// [RFC 9651]: <https://httpwg.org/specs/rfc9651.html#text-parse>
+///
+/// # Examples
+/// ```
+/// # use http::header::{HeaderMap, HeaderValue};
+/// # use sfv::Parser;
+/// # fn main() -> Result<(), sfv::Error> {
+/// let mut headers = HeaderMap::new();
+/// headers.append("example", HeaderValue::from_static("a, b"));
+/// headers.append("example", HeaderValue::from_static("c"));
+///
+/// let combined = sfv::combine_field_lines(headers.get_all("example"));
+/// assert_eq!(&*combined, b"a, b, c");
+///
+/// let list = Parser::new(&combined).parse_list()?;
+/// assert_eq!(list.len(), 3);
+/// # Ok(())
+/// # }
+/// ```
+pub fn combine_field_lines<'a>(lines: impl IntoIterator<Item = &'a HeaderValue>) -> Cow<'a, [u8]> {
+    let mut lines = lines.into_iter();
+
+    let Some(first) = lines.next() else {
+        return Cow::Borrowed(b"");
+    };
+
+    let Some(second) = lines.next() else {
+        return Cow::Borrowed(first.as_bytes());
+    };
+
+    let mut output = first.as_bytes().to_owned();
+    for line in [second].into_iter().chain(lines) {
+        output.extend_from_slice(b", ");
+        output.extend_from_slice(line.as_bytes());
+    }
+    Cow::Owned(output)
+}
+
+/// Parses structured fields from a [`HeaderMap`].
+///
+/// Every field line with the given name is combined using
+/// [`combine_field_lines`] before parsing. Each method returns `Ok(None)` if
+/// the map has no such field lines.
+///
+/// # Examples
+/// ```
+/// # use http::header::{HeaderMap, HeaderValue};
+/// # use sfv::HeaderMapExt;
+/// # fn main() -> Result<(), sfv::Error> {
+/// let mut headers = HeaderMap::new();
+/// headers.append("example-dict", HeaderValue::from_static("a=1"));
+/// headers.append("example-dict", HeaderValue::from_static("b=2"));
+///
+/// let dict = headers.parse_sfv_dictionary("example-dict")?.unwrap();
+/// assert_eq!(dict.len(), 2);
+///
+/// assert_eq!(headers.parse_sfv_list("example-list")?, None);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "parsed-types")]
+pub trait HeaderMapExt {
+    /// Parses the field with the given name as an `Item`.
+    fn parse_sfv_item(&self, name: impl AsHeaderName) -> SFVResult<Option<Item>>;
+
+    /// Parses the field with the given name as a `List`.
+    fn parse_sfv_list(&self, name: impl AsHeaderName) -> SFVResult<Option<List>>;
+
+    /// Parses the field with the given name as a `Dictionary`.
+    fn parse_sfv_dictionary(&self, name: impl AsHeaderName) -> SFVResult<Option<Dictionary>>;
+}
+
+#[cfg(feature = "parsed-types")]
+fn parse_field<T>(
+    headers: &HeaderMap,
+    name: impl AsHeaderName,
+    f: impl FnOnce(Parser<'_>) -> SFVResult<T>,
+) -> SFVResult<Option<T>> {
+    let mut lines = headers.get_all(name).into_iter().peekable();
+    if lines.peek().is_none() {
+        return Ok(None);
+    }
+    f(Parser::new(&combine_field_lines(lines))).map(Some)
+}
+
+#[cfg(feature = "parsed-types")]
+impl HeaderMapExt for HeaderMap {
+    fn parse_sfv_item(&self, name: impl AsHeaderName) -> SFVResult<Option<Item>> {
+        parse_field(self, name, |parser| parser.parse_item())
+    }
+
+    fn parse_sfv_list(&self, name: impl AsHeaderName) -> SFVResult<Option<List>> {
+        parse_field(self, name, |parser| parser.parse_list())
+    }
+
+    fn parse_sfv_dictionary(&self, name: impl AsHeaderName) -> SFVResult<Option<Dictionary>> {
+        parse_field(self, name, |parser| parser.parse_dictionary())
+    }
+}
+
+// The conversion reuses the string's buffer rather than copying it, but still
+// checks every byte. Serializer output only contains visible ASCII characters
+// and spaces, so the check cannot fail.
+#[cfg(feature = "parsed-types")]
+pub(crate) fn to_header_value(output: String) -> HeaderValue {
+    match HeaderValue::try_from(output) {
+        Ok(value) => value,
+        Err(_) => unreachable!(),
+    }
+}
@@ -1,8 +1,8 @@
 use crate::{Error, GenericBareItem};
 
-use std::fmt;
+use core::fmt;
 
-const RANGE_I64: std::ops::RangeInclusive<i64> = -999_999_999_999_999..=999_999_999_999_999;
+const RANGE_I64: core::ops::RangeInclusive<i64> = -999_999_999_999_999..=999_999_999_999_999;
 
 /// A structured field value [integer].
 ///
@@ -1,8 +1,10 @@
-use crate::error::{Error, NonEmptyStringError};
+use crate::error::{Error, ErrorKind, NonEmptyStringError};
 use crate::utils;
 
-use std::borrow::Borrow;
-use std::fmt;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use core::borrow::Borrow;
+use core::fmt;
 
 /// An owned structured field value [key].
 ///
@@ -37,14 +39,20 @@ const fn validate(v: &[u8]) -> Result<(), NonEmptyStringError> {
     }
 
     if !utils::is_allowed_start_key_char(v[0]) {
-        return Err(NonEmptyStringError::invalid_character(0));
+        return Err(NonEmptyStringError::invalid_character(
+            ErrorKind::InvalidKeyCharacter,
+            0,
+        ));
     }
 
     let mut index = 1;
 
     while index < v.len() {
         if !utils::is_allowed_inner_key_char(v[index]) {
-            return Err(NonEmptyStringError::invalid_character(index));
+            return Err(NonEmptyStringError::invalid_character(
+                ErrorKind::InvalidKeyCharacter,
+                index,
+            ));
         }
         index += 1;
     }
@@ -106,7 +114,7 @@ impl Borrow<KeyRef> for Key {
     }
 }
 
-impl std::ops::Deref for Key {
+impl core::ops::Deref for Key {
     type Target = KeyRef;
 
     fn deref(&self) -> &KeyRef {
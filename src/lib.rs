@@ -69,6 +69,19 @@ match dict.get("u") {
 # Ok(())
 # }
 ```
+
+Members of an expected type can be accessed more concisely with [`DictionaryExt`]:
+```
+use sfv::{DictionaryExt, Parser};
+# fn main() -> Result<(), sfv::Error> {
+let input = "u=2, n=(* foo 2)";
+let dict = Parser::new(input).parse_dictionary()?;
+
+if let Some(u) = dict.get_integer("u")? { /* ... */ }
+if let Some(n) = dict.get_inner_list("n")? { /* ... */ }
+# Ok(())
+# }
+```
 "##
 )]
 /*!
@@ -142,67 +155,192 @@ assert_eq!(
 
 # Crate features
 
+- `std` (enabled by default) -- Implements `std::error::Error` for this crate's
+  error types. When disabled, the crate is `no_std` and only requires `alloc`,
+  and the error types of [visitors](visitor) need only implement `Debug` and
+  `Display`.
+
 - `parsed-types` (enabled by default) -- When enabled, exposes fully owned types
   `Item`, `Dictionary`, `List`, and their components, which can be obtained from
   `Parser::parse_item`, etc. These types are implemented using the
   [`indexmap`](https://crates.io/crates/indexmap) crate, so disabling this
   feature can avoid that dependency if parsing using a visitor
-  ([`Parser::parse_item_with_visitor`], etc.) is sufficient.
+  ([`Parser::parse_item_with_visitor`], etc.) is sufficient. When the `std`
+  feature is disabled, the maps use a hasher from the
+  [`foldhash`](https://crates.io/crates/foldhash) crate, so they must be
+  created with `Default::default` rather than `new`.
 
 - `arbitrary` -- Implements the
   [`Arbitrary`](https://docs.rs/arbitrary/1.4.1/arbitrary/trait.Arbitrary.html)
   trait for this crate's types, making them easier to use with fuzzing.
+
+- `serde` -- Exposes `from_str_dictionary`, `from_str_list`, and
+  `from_str_item` for deserializing structured field values into any type
+  implementing [`serde::Deserialize`](https://docs.rs/serde/1/serde/trait.Deserialize.html),
+  and `to_string_dictionary`, `to_string_list`, and `to_string_item` for
+  serializing any type implementing
+  [`serde::Serialize`](https://docs.rs/serde/1/serde/trait.Serialize.html) as a
+  structured field value.
+
+- `derive` -- Exposes derive macros for the traits in the [`typed`] module,
+  which convert between structured field values and Rust structs without
+  hand-written visitors. The macros are implemented in the `sfv-derive` crate.
+
+- `http` -- Exposes `combine_field_lines` and `HeaderMapExt` for parsing fields
+  from an [`http::HeaderMap`](https://docs.rs/http/1/http/header/struct.HeaderMap.html)
+  whose values span multiple field lines, and
+  `SerializeValue::serialize_header_value` for serializing directly into an
+  `http::HeaderValue`. Implies `std`. Note that [`Parser::new`] accepts an
+  `http::HeaderValue` regardless of this feature.
+
+# Serde
+
+With the `serde` feature enabled, structured field values are mapped to and
+from the serde data model as follows:
+
+- A `Dictionary` is a map from keys to members, so it can be represented by a
+  struct whose field names are the dictionary's keys. `None` members are
+  omitted when serializing.
+- A `List` is a sequence of members.
+- An `Item` is its bare item. Its parameters are ignored, unless it is
+  represented by a 2-tuple, in which case it is `(bare_item, parameters)`.
+- An `InnerList` is a sequence of items. Its parameters are ignored, unless it
+  is represented by a 2-tuple, in which case it is `(items, parameters)`. Note
+  that serde represents arrays such as `[T; 2]` as tuples, so they are
+  serialized as `(value, parameters)` pairs rather than as inner lists.
+- `Parameters` are a map from keys to bare items. `None` parameters are omitted
+  when serializing.
+- Integers and dates (as seconds from the Unix epoch) are `i64`, decimals are
+  `f64`, and booleans are `bool`. Integers and floating-point numbers that are
+  out of range fail to serialize.
+- Strings, tokens, and display strings are strings, borrowed from the input when
+  possible. Strings and tokens can also be deserialized as unit enum variants.
+  Strings are always serialized as strings, and unit enum variants as tokens.
+- Byte sequences are byte buffers, and can also be deserialized as sequences of
+  `u8`. Note that serde represents `&[u8]` and `Vec<u8>` as sequences, so they
+  must be serialized using e.g. the
+  [`serde_bytes`](https://crates.io/crates/serde_bytes) crate to produce byte
+  sequences.
 */
 
 #![deny(missing_docs)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
 
+#[cfg(feature = "parsed-types")]
+mod accessors;
+pub mod borrowed;
 mod date;
+#[cfg(feature = "serde")]
+mod de;
 mod decimal;
 mod error;
+mod field_lines;
+pub mod fields;
+#[cfg(feature = "http")]
+mod headers;
 mod integer;
 mod key;
 #[cfg(feature = "parsed-types")]
+mod macros;
+mod output;
+#[cfg(feature = "parsed-types")]
 mod parsed;
 mod parser;
 mod ref_serializer;
+#[cfg(feature = "parsed-types")]
+pub mod registry;
+#[cfg(feature = "serde")]
+mod ser;
 mod serializer;
+pub mod spanned;
+mod streaming;
 mod string;
 mod token;
+pub mod typed;
 mod utils;
+mod validate;
 pub mod visitor;
 
+#[cfg(all(test, feature = "parsed-types"))]
+mod test_accessors;
+#[cfg(test)]
+mod test_borrowed;
+#[cfg(all(test, feature = "serde"))]
+mod test_de;
 #[cfg(test)]
 mod test_decimal;
 #[cfg(test)]
+mod test_error;
+#[cfg(test)]
+mod test_field_lines;
+#[cfg(all(test, feature = "http"))]
+mod test_headers;
+#[cfg(test)]
 mod test_integer;
 #[cfg(test)]
 mod test_key;
+#[cfg(all(test, feature = "parsed-types"))]
+mod test_macros;
 #[cfg(test)]
 mod test_parser;
 #[cfg(test)]
 mod test_ref_serializer;
+#[cfg(all(test, feature = "parsed-types"))]
+mod test_registry;
+#[cfg(all(test, feature = "serde"))]
+mod test_ser;
 #[cfg(test)]
 mod test_serializer;
 #[cfg(test)]
+mod test_spanned;
+#[cfg(test)]
+mod test_streaming;
+#[cfg(test)]
 mod test_string;
 #[cfg(test)]
 mod test_token;
+#[cfg(test)]
+mod test_validate;
 
-use std::borrow::{Borrow, Cow};
-use std::fmt;
-use std::string::String as StdString;
+use alloc::borrow::{Cow, ToOwned};
+use alloc::string::String as StdString;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::fmt;
 
 pub use date::Date;
 pub use decimal::Decimal;
-pub use error::Error;
+pub use error::{Error, ErrorKind};
+pub use field_lines::{FieldLines, LineLocation};
 pub use integer::{integer, Integer};
 pub use key::{key_ref, Key, KeyRef};
+pub use output::{FmtOutput, Output};
 pub use parser::Parser;
 pub use ref_serializer::{
     DictSerializer, InnerListSerializer, ItemSerializer, ListSerializer, ParameterSerializer,
 };
+pub use streaming::StreamingParser;
 pub use string::{string_ref, String, StringRef};
 pub use token::{token_ref, Token, TokenRef};
+pub use validate::{Diagnostic, DiagnosticKind};
+
+#[cfg(feature = "std")]
+pub use output::IoOutput;
+
+#[cfg(feature = "http")]
+pub use headers::combine_field_lines;
+
+#[cfg(all(feature = "http", feature = "parsed-types"))]
+pub use headers::HeaderMapExt;
+
+#[cfg(feature = "parsed-types")]
+#[doc(hidden)]
+pub use macros::__macro;
+
+#[cfg(feature = "parsed-types")]
+pub use accessors::{DictionaryExt, TypeMismatch, ValueKind};
 
 #[cfg(feature = "parsed-types")]
 pub use parsed::{Dictionary, InnerList, Item, List, ListEntry, Parameters};
@@ -210,7 +348,13 @@ pub use parsed::{Dictionary, InnerList, Item, List, ListEntry, Parameters};
 #[cfg(feature = "parsed-types")]
 pub use serializer::SerializeValue;
 
-type SFVResult<T> = std::result::Result<T, Error>;
+#[cfg(feature = "serde")]
+pub use de::{from_str_dictionary, from_str_item, from_str_list};
+
+#[cfg(feature = "serde")]
+pub use ser::{to_string_dictionary, to_string_item, to_string_list};
+
+type SFVResult<T> = core::result::Result<T, Error>;
 
 /// An abstraction over multiple kinds of ownership of a [bare item].
 ///
@@ -628,3 +772,121 @@ impl fmt::Display for Version {
         })
     }
 }
+
+/// How a [`Parser`] handles a key that appears more than once in the same
+/// dictionary or parameters.
+///
+/// [RFC 9651] requires later values to replace earlier ones, but a field value
+/// with duplicate keys may be interpreted differently by implementations that
+/// do not follow that rule. Security-sensitive consumers can choose to reject
+/// such values instead.
+///
+/// The spanned parse methods, such as [`Parser::parse_dictionary_spanned`],
+/// also follow this policy, except that under the default they retain every
+/// occurrence rather than replacing earlier ones.
+///
+/// [RFC 9651]: <https://httpwg.org/specs/rfc9651.html#parse-dictionary>
+#[cfg_attr(
+    feature = "parsed-types",
+    doc = r##"
+
+```
+# use sfv::{DuplicateKeyPolicy, ErrorKind, Parser, SerializeValue};
+# fn main() -> Result<(), sfv::Error> {
+let input = "a=1, b=2, a=3";
+
+let dict = Parser::new(input).parse_dictionary()?;
+assert_eq!(dict.serialize_value()?, "a=3, b=2");
+
+let dict = Parser::new(input)
+    .with_duplicate_key_policy(DuplicateKeyPolicy::FirstWins)
+    .parse_dictionary()?;
+assert_eq!(dict.serialize_value()?, "a=1, b=2");
+
+let err = Parser::new(input)
+    .with_duplicate_key_policy(DuplicateKeyPolicy::Reject)
+    .parse_dictionary()
+    .unwrap_err();
+assert_eq!(err.kind(), ErrorKind::DuplicateKey);
+assert_eq!(err.index(), Some(10));
+assert_eq!(err.previous_index(), Some(0));
+# Ok(())
+# }
+```
+"##
+)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DuplicateKeyPolicy {
+    /// Later values replace earlier ones, as required by RFC 9651.
+    ///
+    /// The parser passes every occurrence to the visitor, which is
+    /// responsible for applying this policy.
+    #[default]
+    LastWins,
+    /// Later values are ignored.
+    ///
+    /// The parser validates them but does not pass them to the visitor.
+    FirstWins,
+    /// Duplicate keys cause an [`ErrorKind::DuplicateKey`] error carrying the
+    /// indices of both occurrences.
+    Reject,
+}
+
+/// Limits on the size and complexity of input accepted by a [`Parser`] or a
+/// [`StreamingParser`].
+///
+/// Exceeding a limit results in an [`ErrorKind::LimitExceeded`] error at the
+/// index of the offending part of the input, before any memory is allocated
+/// for that part. Limits on counts include members and parameters whose keys
+/// are duplicated.
+///
+/// The limits also apply to lenient parsing and to validation with
+/// [`Parser::validate_item`] and related methods. These stop at input that is
+/// too long or has too many members, but skip members that exceed other
+/// limits.
+///
+/// The default is no limits.
+///
+/// ```
+/// # use sfv::{visitor::Ignored, ErrorKind, Parser, ParserLimits};
+/// let limits = ParserLimits {
+///     max_members: 2,
+///     ..ParserLimits::default()
+/// };
+///
+/// let err = Parser::new("a, b, c")
+///     .with_limits(limits)
+///     .parse_list_with_visitor(&mut Ignored)
+///     .unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::LimitExceeded);
+/// assert_eq!(err.index(), Some(6));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParserLimits {
+    /// The maximum length of the input in bytes.
+    pub max_input_len: usize,
+    /// The maximum number of members of a list or dictionary.
+    pub max_members: usize,
+    /// The maximum number of items in an inner list.
+    pub max_inner_list_len: usize,
+    /// The maximum number of parameters of an item or inner list.
+    pub max_params: usize,
+    /// The maximum length of a byte sequence in bytes, after decoding.
+    pub max_byte_sequence_len: usize,
+    /// The maximum length of a string or display string in bytes, after
+    /// unescaping.
+    pub max_string_len: usize,
+}
+
+impl Default for ParserLimits {
+    fn default() -> Self {
+        Self {
+            max_input_len: usize::MAX,
+            max_members: usize::MAX,
+            max_inner_list_len: usize::MAX,
+            max_params: usize::MAX,
+            max_byte_sequence_len: usize::MAX,
+            max_string_len: usize::MAX,
+        }
+    }
+}
@@ -0,0 +1,359 @@
+/// Creates an [`Item`][crate::Item] from structured-field-like syntax.
+///
+/// Bare items are written as follows:
+///
+/// - integers and decimals as numeric literals, such as `1`, `-2`, or `3.25`
+/// - strings as string literals, such as `"a"`
+/// - tokens as identifiers, such as `abc`
+/// - booleans as `?0` or `?1`
+/// - dates as `@` followed by an integer literal, such as `@1659578233`
+/// - display strings as `%` followed by a string literal, such as `%"ü"`
+/// - anything else, such as byte sequences or tokens that are not Rust
+///   identifiers, as an expression in braces that converts into a
+///   [`BareItem`][crate::BareItem], such as `{vec![1, 2]}` or
+///   `{sfv::token_ref("a/b")}`
+///
+/// Parameters follow the bare item as `;key=value` or `;key`, where `key` is an
+/// identifier or a string literal such as `"max-age"`.
+///
+/// Keys, tokens, strings, and numbers written as literals are validated at
+/// compile time.
+///
+/// # Examples
+/// ```
+/// # use sfv::{sfv_item, SerializeValue};
+/// # fn main() -> Result<(), sfv::Error> {
+/// let item = sfv_item!(12.5; a = tok; b; "c-d" = ?0);
+///
+/// assert_eq!(item.serialize_value()?, "12.5;a=tok;b;c-d=?0");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Invalid literals are rejected at compile time:
+/// ```compile_fail
+/// let item = sfv::sfv_item!("\n");
+/// ```
+/// ```compile_fail
+/// let item = sfv::sfv_item!(1; A = 2);
+/// ```
+/// ```compile_fail
+/// let item = sfv::sfv_item!(1.2345);
+/// ```
+#[macro_export]
+macro_rules! sfv_item {
+    ($($input:tt)+) => {
+        $crate::__sfv!(@bare [@item] $($input)+)
+    };
+}
+
+/// Creates a [`List`][crate::List] from structured-field-like syntax.
+///
+/// Members are separated by commas, and inner lists are written in
+/// parentheses with their items separated by whitespace. See [`sfv_item!`] for
+/// the syntax of items.
+///
+/// # Examples
+/// ```
+/// # use sfv::{sfv_list, SerializeValue};
+/// # fn main() -> Result<(), sfv::Error> {
+/// let list = sfv_list!["a", (b ?1;c=-3 @5);d, {vec![1, 2]}];
+///
+/// assert_eq!(list.serialize_value()?, r#""a", (b ?1;c=-3 @5);d, :AQI=:"#);
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! sfv_list {
+    ($($input:tt)*) => {
+        $crate::__sfv!(@list [] $($input)*)
+    };
+}
+
+/// Creates a [`Dictionary`][crate::Dictionary] from structured-field-like
+/// syntax.
+///
+/// Members are separated by commas, and are written as `key=value` or as
+/// `key`, optionally followed by parameters, for a boolean `true` value. See
+/// [`sfv_list!`] for the syntax of values.
+///
+/// # Examples
+/// ```
+/// # use sfv::{sfv_dict, SerializeValue};
+/// # fn main() -> Result<(), sfv::Error> {
+/// let dict = sfv_dict! { a = 1, b = (tok "s"); p = ?1, c;q, "d-e" = %"ü" };
+///
+/// assert_eq!(
+///     dict.serialize_value()?,
+///     r#"a=1, b=(tok "s");p, c;q, d-e=%"%c3%bc""#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! sfv_dict {
+    ($($input:tt)*) => {
+        $crate::__sfv!(@dict [] $($input)*)
+    };
+}
+
+// The rules below parse their input from the front, passing the result to a
+// continuation: for example, `@bare [@k ...] ?1 rest` takes the bare item `?1`
+// and expands to `@k ... [<expression>] rest`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __sfv {
+    (@bare [$($k:tt)*] ?0 $($rest:tt)*) => {
+        $crate::__sfv!($($k)* [$crate::BareItem::Boolean(false)] $($rest)*)
+    };
+    (@bare [$($k:tt)*] ?1 $($rest:tt)*) => {
+        $crate::__sfv!($($k)* [$crate::BareItem::Boolean(true)] $($rest)*)
+    };
+    (@bare [$($k:tt)*] @ - $v:literal $($rest:tt)*) => {
+        $crate::__sfv!($($k)* [$crate::__sfv!(@date true $v)] $($rest)*)
+    };
+    (@bare [$($k:tt)*] @ $v:literal $($rest:tt)*) => {
+        $crate::__sfv!($($k)* [$crate::__sfv!(@date false $v)] $($rest)*)
+    };
+    (@bare [$($k:tt)*] % $v:literal $($rest:tt)*) => {
+        $crate::__sfv!($($k)* [$crate::BareItem::DisplayString($crate::__macro::String::from($v))] $($rest)*)
+    };
+    (@bare [$($k:tt)*] - $v:literal $($rest:tt)*) => {
+        $crate::__sfv!($($k)* [$crate::__sfv!(@literal true $v)] $($rest)*)
+    };
+    (@bare [$($k:tt)*] $v:literal $($rest:tt)*) => {
+        $crate::__sfv!($($k)* [$crate::__sfv!(@literal false $v)] $($rest)*)
+    };
+    (@bare [$($k:tt)*] $v:ident $($rest:tt)*) => {
+        $crate::__sfv!($($k)* [$crate::BareItem::Token($crate::__macro::ToOwned::to_owned({
+            const TOKEN: &$crate::TokenRef = $crate::TokenRef::constant(stringify!($v));
+            TOKEN
+        }))] $($rest)*)
+    };
+    (@bare [$($k:tt)*] { $($v:tt)* } $($rest:tt)*) => {
+        $crate::__sfv!($($k)* [$crate::BareItem::from({ $($v)* })] $($rest)*)
+    };
+
+    (@literal $negative:tt $v:literal) => {{
+        const LITERAL: $crate::__macro::Literal =
+            $crate::__macro::literal($negative, stringify!($v), concat!($v));
+        LITERAL
+    }
+    .into_bare_item()};
+    (@date $negative:tt $v:literal) => {
+        $crate::BareItem::Date({
+            const DATE: $crate::Date = $crate::__macro::date($crate::__macro::literal(
+                $negative,
+                stringify!($v),
+                concat!($v),
+            ));
+            DATE
+        })
+    };
+
+    (@key $key:ident) => {
+        $crate::__macro::ToOwned::to_owned({
+            const KEY: &$crate::KeyRef = $crate::KeyRef::constant(stringify!($key));
+            KEY
+        })
+    };
+    (@key $key:literal) => {
+        $crate::__macro::ToOwned::to_owned({
+            const KEY: &$crate::KeyRef = $crate::KeyRef::constant($key);
+            KEY
+        })
+    };
+
+    (@params [$($k:tt)*] [$($acc:tt)*] ; $key:tt = $($rest:tt)+) => {
+        $crate::__sfv!(@bare [@param [$($k)*] [$($acc)*] $key] $($rest)+)
+    };
+    (@params [$($k:tt)*] [$($acc:tt)*] ; $key:tt $($rest:tt)*) => {
+        $crate::__sfv!(@params [$($k)*] [$($acc)* ($key [$crate::BareItem::Boolean(true)])] $($rest)*)
+    };
+    (@params [$($k:tt)*] [$(($key:tt [$($v:tt)*]))*] $($rest:tt)*) => {
+        $crate::__sfv!($($k)* [{
+            #[allow(unused_mut)]
+            let mut params = $crate::Parameters::default();
+            $(params.insert($crate::__sfv!(@key $key), $($v)*);)*
+            params
+        }] $($rest)*)
+    };
+    (@param [$($k:tt)*] [$($acc:tt)*] $key:tt [$($v:tt)*] $($rest:tt)*) => {
+        $crate::__sfv!(@params [$($k)*] [$($acc)* ($key [$($v)*])] $($rest)*)
+    };
+
+    (@item [$($bare:tt)*] $($rest:tt)*) => {
+        $crate::__sfv!(@params [@item_done [$($bare)*]] [] $($rest)*)
+    };
+    (@item_done [$($bare:tt)*] [$($params:tt)*]) => {
+        $crate::Item::with_params($($bare)*, $($params)*)
+    };
+
+    (@items [$($k:tt)*] [$([$($item:tt)*])*]) => {
+        $crate::__sfv!($($k)* [$crate::__macro::vec![$($($item)*),*]])
+    };
+    (@items [$($k:tt)*] [$($acc:tt)*] $($rest:tt)+) => {
+        $crate::__sfv!(@bare [@items_bare [$($k)*] [$($acc)*]] $($rest)+)
+    };
+    (@items_bare [$($k:tt)*] [$($acc:tt)*] [$($bare:tt)*] $($rest:tt)*) => {
+        $crate::__sfv!(@params [@items_item [$($k)*] [$($acc)*] [$($bare)*]] [] $($rest)*)
+    };
+    (@items_item [$($k:tt)*] [$($acc:tt)*] [$($bare:tt)*] [$($params:tt)*] $($rest:tt)*) => {
+        $crate::__sfv!(@items [$($k)*] [$($acc)* [$crate::Item::with_params($($bare)*, $($params)*)]] $($rest)*)
+    };
+
+    (@entry [$($k:tt)*] ( $($items:tt)* ) $($rest:tt)*) => {
+        $crate::__sfv!(@items [@inner_list [$($k)*] [$($rest)*]] [] $($items)*)
+    };
+    (@entry [$($k:tt)*] $($rest:tt)+) => {
+        $crate::__sfv!(@bare [@entry_bare [$($k)*]] $($rest)+)
+    };
+    (@inner_list [$($k:tt)*] [$($rest:tt)*] [$($items:tt)*]) => {
+        $crate::__sfv!(@params [@inner_list_done [$($k)*] [$($items)*]] [] $($rest)*)
+    };
+    (@inner_list_done [$($k:tt)*] [$($items:tt)*] [$($params:tt)*] $($rest:tt)*) => {
+        $crate::__sfv!($($k)* [$crate::ListEntry::InnerList(
+            $crate::InnerList::with_params($($items)*, $($params)*)
+        )] $($rest)*)
+    };
+    (@entry_bare [$($k:tt)*] [$($bare:tt)*] $($rest:tt)*) => {
+        $crate::__sfv!(@params [@entry_done [$($k)*] [$($bare)*]] [] $($rest)*)
+    };
+    (@entry_done [$($k:tt)*] [$($bare:tt)*] [$($params:tt)*] $($rest:tt)*) => {
+        $crate::__sfv!($($k)* [$crate::ListEntry::Item(
+            $crate::Item::with_params($($bare)*, $($params)*)
+        )] $($rest)*)
+    };
+
+    (@list [$([$($entry:tt)*])*]) => {
+        $crate::__macro::vec![$($($entry)*),*] as $crate::List
+    };
+    (@list [$($acc:tt)*] $($rest:tt)+) => {
+        $crate::__sfv!(@entry [@list_member [$($acc)*]] $($rest)+)
+    };
+    (@list_member [$($acc:tt)*] [$($entry:tt)*]) => {
+        $crate::__sfv!(@list [$($acc)* [$($entry)*]])
+    };
+    (@list_member [$($acc:tt)*] [$($entry:tt)*] , $($rest:tt)*) => {
+        $crate::__sfv!(@list [$($acc)* [$($entry)*]] $($rest)*)
+    };
+
+    (@dict [$(($key:tt [$($entry:tt)*]))*]) => {{
+        #[allow(unused_mut)]
+        let mut dict = $crate::Dictionary::default();
+        $(dict.insert($crate::__sfv!(@key $key), $($entry)*);)*
+        dict
+    }};
+    (@dict [$($acc:tt)*] $key:tt = $($rest:tt)+) => {
+        $crate::__sfv!(@entry [@dict_member [$($acc)*] $key] $($rest)+)
+    };
+    (@dict [$($acc:tt)*] $key:tt $($rest:tt)*) => {
+        $crate::__sfv!(@params [@dict_flag [$($acc)*] $key] [] $($rest)*)
+    };
+    (@dict_flag [$($acc:tt)*] $key:tt [$($params:tt)*] $($rest:tt)*) => {
+        $crate::__sfv!(@dict_member [$($acc)*] $key [$crate::ListEntry::Item(
+            $crate::Item::with_params(true, $($params)*)
+        )] $($rest)*)
+    };
+    (@dict_member [$($acc:tt)*] $key:tt [$($entry:tt)*]) => {
+        $crate::__sfv!(@dict [$($acc)* ($key [$($entry)*])])
+    };
+    (@dict_member [$($acc:tt)*] $key:tt [$($entry:tt)*] , $($rest:tt)*) => {
+        $crate::__sfv!(@dict [$($acc)* ($key [$($entry)*])] $($rest)*)
+    };
+}
+
+#[doc(hidden)]
+pub mod __macro {
+    use crate::{BareItem, Date, Decimal, Integer, StringRef};
+
+    pub use alloc::borrow::ToOwned;
+    pub use alloc::string::String;
+    pub use alloc::vec;
+
+    pub enum Literal {
+        Integer(Integer),
+        Decimal(Decimal),
+        String(&'static StringRef),
+    }
+
+    impl Literal {
+        pub fn into_bare_item(self) -> BareItem {
+            match self {
+                Self::Integer(v) => BareItem::Integer(v),
+                Self::Decimal(v) => BareItem::Decimal(v),
+                Self::String(v) => BareItem::String(v.to_owned()),
+            }
+        }
+    }
+
+    // `source` is the literal as written, and `value` is its value as produced
+    // by `concat!`, which normalizes integer literals but not decimal ones.
+    pub const fn literal(negative: bool, source: &str, value: &'static str) -> Literal {
+        match source.as_bytes() {
+            [b'"' | b'r', ..] => {
+                if negative {
+                    panic!("strings cannot be negated");
+                }
+                Literal::String(StringRef::constant(value))
+            }
+            [b'0'..=b'9', ..] => number(negative, value.as_bytes()),
+            _ => panic!("expected a string, integer, or decimal literal"),
+        }
+    }
+
+    pub const fn date(literal: Literal) -> Date {
+        match literal {
+            Literal::Integer(v) => Date::from_unix_seconds(v),
+            _ => panic!("expected an integer literal for date"),
+        }
+    }
+
+    const fn number(negative: bool, value: &[u8]) -> Literal {
+        let mut v: i64 = 0;
+        let mut fraction_digits = None;
+        let mut i = 0;
+
+        while i < value.len() {
+            match value[i] {
+                c @ b'0'..=b'9' => {
+                    v = match v.checked_mul(10) {
+                        Some(v) => match v.checked_add((c - b'0') as i64) {
+                            Some(v) => v,
+                            None => panic!("out of range for Integer"),
+                        },
+                        None => panic!("out of range for Integer"),
+                    };
+                    if let Some(n) = fraction_digits {
+                        if n == 3 {
+                            panic!("decimal cannot have more than 3 fractional digits");
+                        }
+                        fraction_digits = Some(n + 1);
+                    }
+                }
+                b'_' => {}
+                b'.' if fraction_digits.is_none() => fraction_digits = Some(0),
+                _ => panic!("expected an integer or decimal literal"),
+            }
+            i += 1;
+        }
+
+        if negative {
+            v = -v;
+        }
+
+        match fraction_digits {
+            None => Literal::Integer(Integer::constant(v)),
+            Some(mut n) => {
+                while n < 3 {
+                    v = match v.checked_mul(10) {
+                        Some(v) => v,
+                        None => panic!("out of range for Decimal"),
+                    };
+                    n += 1;
+                }
+                Literal::Decimal(Decimal::from_integer_scaled_1000(Integer::constant(v)))
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A destination for serialized structured field values.
+///
+/// Serialization itself cannot fail, so writes to an `Output` are infallible.
+/// Destinations that can fail, such as an arbitrary [`fmt::Write`] or
+/// `std::io::Write`, can be adapted with [`FmtOutput`] or `IoOutput`
+/// respectively, which record the first error for later inspection.
+///
+/// # Examples
+/// ```
+/// use sfv::{FmtOutput, ItemSerializer};
+///
+/// struct Buffer {
+///     bytes: [u8; 16],
+///     len: usize,
+/// }
+///
+/// impl std::fmt::Write for Buffer {
+///     fn write_str(&mut self, s: &str) -> std::fmt::Result {
+///         let end = self.len + s.len();
+///         let dest = self.bytes.get_mut(self.len..end).ok_or(std::fmt::Error)?;
+///         dest.copy_from_slice(s.as_bytes());
+///         self.len = end;
+///         Ok(())
+///     }
+/// }
+///
+/// let buffer = Buffer { bytes: [0; 16], len: 0 };
+/// let output = ItemSerializer::with_output(FmtOutput::new(buffer))
+///     .bare_item(12)
+///     .finish();
+/// let buffer = output.into_inner().unwrap();
+/// assert_eq!(&buffer.bytes[..buffer.len], b"12");
+///
+/// let buffer = Buffer { bytes: [0; 16], len: 0 };
+/// let output = ItemSerializer::with_output(FmtOutput::new(buffer))
+///     .bare_item(sfv::string_ref("a string that does not fit"))
+///     .finish();
+/// assert!(output.into_inner().is_err());
+/// ```
+pub trait Output {
+    /// Appends the given string slice.
+    fn push_str(&mut self, s: &str);
+
+    /// Appends the given character.
+    fn push(&mut self, c: char) {
+        self.push_str(c.encode_utf8(&mut [0; 4]));
+    }
+}
+
+impl Output for String {
+    fn push_str(&mut self, s: &str) {
+        String::push_str(self, s);
+    }
+
+    fn push(&mut self, c: char) {
+        String::push(self, c);
+    }
+}
+
+impl Output for Vec<u8> {
+    fn push_str(&mut self, s: &str) {
+        self.extend_from_slice(s.as_bytes());
+    }
+}
+
+impl<O: ?Sized + Output> Output for &mut O {
+    fn push_str(&mut self, s: &str) {
+        (**self).push_str(s);
+    }
+
+    fn push(&mut self, c: char) {
+        (**self).push(c);
+    }
+}
+
+/// Adapts a [`fmt::Write`] into an [`Output`].
+///
+/// Once the underlying writer returns an error, subsequent writes are
+/// discarded and the error is returned by [`FmtOutput::into_inner`].
+#[derive(Debug)]
+pub struct FmtOutput<W> {
+    inner: W,
+    result: fmt::Result,
+}
+
+impl<W: fmt::Write> FmtOutput<W> {
+    /// Creates an output that writes into the given writer.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            result: Ok(()),
+        }
+    }
+
+    /// Returns the underlying writer, or the first error that it returned.
+    pub fn into_inner(self) -> Result<W, fmt::Error> {
+        self.result.map(|()| self.inner)
+    }
+}
+
+impl<W: fmt::Write> Output for FmtOutput<W> {
+    fn push_str(&mut self, s: &str) {
+        if self.result.is_ok() {
+            self.result = self.inner.write_str(s);
+        }
+    }
+
+    fn push(&mut self, c: char) {
+        if self.result.is_ok() {
+            self.result = self.inner.write_char(c);
+        }
+    }
+}
+
+/// Adapts a [`std::io::Write`] into an [`Output`].
+///
+/// Once the underlying writer returns an error, subsequent writes are
+/// discarded and the error is returned by [`IoOutput::into_inner`].
+///
+/// No buffering is performed, so wrapping an unbuffered writer in a
+/// [`std::io::BufWriter`] is recommended.
+///
+/// # Examples
+/// ```
+/// use sfv::{IoOutput, KeyRef, ListSerializer};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut ser = ListSerializer::with_output(IoOutput::new(Vec::new()));
+/// ser.bare_item(1).parameter(KeyRef::from_str("a")?, false);
+/// ser.bare_item(2);
+///
+/// assert_eq!(ser.finish()?.into_inner()?, b"1;a=?0, 2");
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct IoOutput<W> {
+    inner: W,
+    result: std::io::Result<()>,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> IoOutput<W> {
+    /// Creates an output that writes into the given writer.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            result: Ok(()),
+        }
+    }
+
+    /// Returns the underlying writer, or the first error that it returned.
+    pub fn into_inner(self) -> std::io::Result<W> {
+        self.result.map(|()| self.inner)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> Output for IoOutput<W> {
+    fn push_str(&mut self, s: &str) {
+        if self.result.is_ok() {
+            self.result = self.inner.write_all(s.as_bytes());
+        }
+    }
+}
+
+// Allows the use of `write!` with an `Output`.
+pub(crate) struct Adapter<'a, O: ?Sized>(pub(crate) &'a mut O);
+
+impl<O: ?Sized + Output> fmt::Write for Adapter<'_, O> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.push_str(s);
+        Ok(())
+    }
+}
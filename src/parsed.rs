@@ -1,7 +1,15 @@
 use crate::visitor::*;
 use crate::{BareItem, BareItemFromInput, Key, KeyRef};
 use indexmap::IndexMap;
-use std::convert::Infallible;
+
+use alloc::borrow::ToOwned;
+use alloc::vec::Vec;
+use core::convert::Infallible;
+
+#[cfg(feature = "std")]
+type RandomState = std::collections::hash_map::RandomState;
+#[cfg(not(feature = "std"))]
+type RandomState = foldhash::fast::RandomState;
 
 /// An [item]-type structured field value.
 ///
@@ -25,7 +33,7 @@ impl Item {
     pub fn new(bare_item: impl Into<BareItem>) -> Self {
         Self {
             bare_item: bare_item.into(),
-            params: Parameters::new(),
+            params: Parameters::default(),
         }
     }
 
@@ -45,7 +53,7 @@ impl Item {
 // dict-member    = member-name [ "=" member-value ]
 // member-name    = key
 // member-value   = sf-item / inner-list
-pub type Dictionary = IndexMap<Key, ListEntry>;
+pub type Dictionary = IndexMap<Key, ListEntry, RandomState>;
 
 /// A [list]-type structured field value.
 ///
@@ -64,7 +72,7 @@ pub type List = Vec<ListEntry>;
 //                 *( lcalpha / DIGIT / "_" / "-" / "." / "*" )
 // lcalpha       = %x61-7A ; a-z
 // param-value   = bare-item
-pub type Parameters = IndexMap<Key, BareItem>;
+pub type Parameters = IndexMap<Key, BareItem, RandomState>;
 
 /// A member of a [`List`] or [`Dictionary`].
 #[derive(Debug, PartialEq, Clone)]
@@ -107,7 +115,7 @@ impl InnerList {
     pub fn new(items: Vec<Item>) -> Self {
         Self {
             items,
-            params: Parameters::new(),
+            params: Parameters::default(),
         }
     }
 
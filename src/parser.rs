@@ -1,63 +1,258 @@
 use crate::utils;
 use crate::visitor::*;
 use crate::{
-    BareItemFromInput, Date, Decimal, Error, Integer, KeyRef, Num, SFVResult, String, StringRef,
-    TokenRef, Version,
+    BareItemFromInput, Date, Decimal, Diagnostic, DuplicateKeyPolicy, Error, ErrorKind, Integer,
+    KeyRef, Num, ParserLimits, SFVResult, String, StringRef, TokenRef, Version,
 };
 
 #[cfg(feature = "parsed-types")]
 use crate::{Dictionary, Item, List};
 
-use std::borrow::Cow;
-use std::string::String as StdString;
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::string::String as StdString;
+use alloc::vec::Vec;
+use core::cell::{Cell, RefCell};
 
-fn parse_item<'a>(parser: &mut Parser<'a>, visitor: impl ItemVisitor<'a>) -> SFVResult<()> {
+pub(crate) fn parse_item<'a>(
+    parser: &mut Parser<'a>,
+    visitor: impl ItemVisitor<'a>,
+) -> SFVResult<()> {
     // https://httpwg.org/specs/rfc9651.html#parse-item
-    let param_visitor = visitor
-        .bare_item(parser.parse_bare_item()?)
-        .map_err(Error::custom)?;
+    let start = parser.index;
+    let bare_item = match parser.parse_bare_item() {
+        Ok(bare_item) => bare_item,
+        Err(err) => {
+            parser.recover_value(err, start)?;
+            BareItemFromInput::Boolean(false)
+        }
+    };
+    parser.record_position();
+    let param_visitor = visitor.bare_item(bare_item).map_err(Error::custom)?;
     parser.parse_parameters(param_visitor)
 }
 
-fn parse_comma_separated<'a>(
+pub(crate) fn parse_comma_separated<'a>(
     parser: &mut Parser<'a>,
     mut parse_member: impl FnMut(&mut Parser<'a>) -> SFVResult<()>,
 ) -> SFVResult<()> {
-    while parser.peek().is_some() {
-        parse_member(parser)?;
+    let mut members = 0;
 
-        parser.consume_ows_chars();
+    while parser.peek().is_some() {
+        members += 1;
+        parser.check_limit(members, parser.limits.max_members, "too many members")?;
 
-        if parser.peek().is_none() {
-            return Ok(());
+        let start = parser.index;
+        if let Err(err) = parse_member(parser) {
+            parser.recover(err, start, |c| c == b',')?;
         }
 
-        let comma_index = parser.index;
+        parser.consume_ows_chars();
 
-        if let Some(c) = parser.peek() {
-            if c != b',' {
-                return parser.error("trailing characters after member");
+        match parser.peek() {
+            None => return Ok(()),
+            Some(b',') => {}
+            Some(_) => {
+                let index = parser.index;
+                parser.recover(
+                    Error::with_index(
+                        ErrorKind::ExpectedComma,
+                        "trailing characters after member",
+                        index,
+                    ),
+                    index,
+                    |c| c == b',',
+                )?;
+                if parser.peek().is_none() {
+                    return Ok(());
+                }
             }
-            parser.next();
         }
 
+        let comma_index = parser.index;
+        parser.next();
         parser.consume_ows_chars();
 
         if parser.peek().is_none() {
             // Report the error at the position of the comma itself, rather
             // than at the end of input.
-            return Err(Error::with_index("trailing comma", comma_index));
+            return parser.report(
+                Error::with_index(ErrorKind::TrailingComma, "trailing comma", comma_index),
+                comma_index..comma_index + 1,
+            );
         }
     }
 
     Ok(())
 }
 
+#[cfg(feature = "parsed-types")]
+fn parse_comma_separated_lenient<'a, T>(
+    parser: &mut Parser<'a>,
+    errors: &mut Vec<Error>,
+    mut parse_member: impl FnMut(&mut Parser<'a>) -> SFVResult<T>,
+    mut add_member: impl FnMut(T),
+) {
+    // Like the member limit below, this ends parsing.
+    if let Err(err) = parser.check_input_len() {
+        errors.push(err);
+        return;
+    }
+
+    parser.consume_sp_chars();
+
+    let mut members = 0;
+
+    while parser.peek().is_some() {
+        members += 1;
+        if let Err(err) = parser.check_limit(members, parser.limits.max_members, "too many members")
+        {
+            // Unlike other errors, this one ends parsing.
+            errors.push(err);
+            return;
+        }
+
+        let start = parser.index;
+
+        let member = parse_member(parser).and_then(|member| {
+            parser.consume_ows_chars();
+            match parser.peek() {
+                None | Some(b',') => Ok(member),
+                Some(_) => {
+                    parser.error(ErrorKind::ExpectedComma, "trailing characters after member")
+                }
+            }
+        });
+
+        match member {
+            Ok(member) => add_member(member),
+            Err(err) => {
+                errors.push(err);
+                parser.index = start;
+                parser.skip_until(|c| c == b',');
+            }
+        }
+
+        let comma_index = parser.index;
+
+        if parser.next().is_some() {
+            parser.consume_ows_chars();
+
+            if parser.peek().is_none() {
+                errors.push(Error::with_index(
+                    ErrorKind::TrailingComma,
+                    "trailing comma",
+                    comma_index,
+                ));
+            }
+        }
+    }
+}
+
+// The keys seen so far in a dictionary or in parameters, with the indices at
+// which they occurred. Only populated for policies other than `LastWins`.
+//
+// Keys are looked up in a map, so that checking each key of a large
+// dictionary does not take time proportional to the number of keys before
+// it, and are also kept in order of insertion, so that the most recent ones
+// can be forgotten.
+#[derive(Default)]
+pub(crate) struct SeenKeys<'a> {
+    indices: BTreeMap<&'a KeyRef, usize>,
+    order: Vec<&'a KeyRef>,
+}
+
+impl<'a> SeenKeys<'a> {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    fn get(&self, key: &KeyRef) -> Option<usize> {
+        self.indices.get(key).copied()
+    }
+
+    fn insert(&mut self, key: &'a KeyRef, index: usize) {
+        self.indices.insert(key, index);
+        self.order.push(key);
+    }
+
+    // Forgets all but the first `len` keys.
+    pub(crate) fn truncate(&mut self, len: usize) {
+        while self.order.len() > len {
+            if let Some(key) = self.order.pop() {
+                self.indices.remove(key);
+            }
+        }
+    }
+
+    // Returns the keys and their indices, in order of insertion.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&'a KeyRef, usize)> + '_ {
+        self.order.iter().map(|&key| (key, self.indices[key]))
+    }
+}
+
+impl<'a> FromIterator<(&'a KeyRef, usize)> for SeenKeys<'a> {
+    fn from_iter<I: IntoIterator<Item = (&'a KeyRef, usize)>>(iter: I) -> Self {
+        let mut seen = Self::new();
+        for (key, index) in iter {
+            seen.insert(key, index);
+        }
+        seen
+    }
+}
+
+pub(crate) fn parse_dictionary_member<'a>(
+    parser: &mut Parser<'a>,
+    visitor: &mut (impl ?Sized + DictionaryVisitor<'a>),
+    seen: &mut SeenKeys<'a>,
+) -> SFVResult<()> {
+    let index = parser.index;
+    let key = parser.parse_key()?;
+
+    if parser.check_duplicate_key(seen, key, index)? {
+        parser.record_position();
+        parse_dictionary_value(parser, visitor.entry(key).map_err(Error::custom)?)
+    } else {
+        parse_dictionary_value(parser, Ignored)
+    }
+}
+
+pub(crate) fn parse_dictionary_value<'a>(
+    parser: &mut Parser<'a>,
+    entry_visitor: impl EntryVisitor<'a>,
+) -> SFVResult<()> {
+    if let Some(b'=') = parser.peek() {
+        parser.next();
+        parser.parse_list_entry(entry_visitor)
+    } else {
+        parser.record_position();
+        let param_visitor = entry_visitor
+            .bare_item(BareItemFromInput::from(true))
+            .map_err(Error::custom)?;
+        parser.parse_parameters(param_visitor)
+    }
+}
+
 /// Exposes methods for parsing input into a structured field value.
 pub struct Parser<'a> {
-    input: &'a [u8],
-    index: usize,
+    pub(crate) input: &'a [u8],
+    pub(crate) index: usize,
     version: Version,
+    duplicate_keys: DuplicateKeyPolicy,
+    pub(crate) limits: ParserLimits,
+    // Whether byte sequences are only validated rather than decoded, and
+    // parsed as empty. Set while skipping values that are discarded.
+    pub(crate) validate_only: bool,
+    // Set to the current index before each call to a visitor, for visitors
+    // that record the spans of the values that they are passed.
+    position: Option<&'a Cell<usize>>,
+    // If set, errors are collected here and parsing resumes after them, for
+    // validation.
+    pub(crate) diagnostics: Option<&'a RefCell<Vec<Diagnostic>>>,
 }
 
 impl<'a> Parser<'a> {
@@ -67,6 +262,11 @@ impl<'a> Parser<'a> {
             input: input.as_ref(),
             index: 0,
             version: Version::Rfc9651,
+            duplicate_keys: DuplicateKeyPolicy::LastWins,
+            limits: ParserLimits::default(),
+            validate_only: false,
+            position: None,
+            diagnostics: None,
         }
     }
 
@@ -76,10 +276,26 @@ impl<'a> Parser<'a> {
         self
     }
 
+    /// Sets the parser's policy for duplicate keys and returns it.
+    ///
+    /// The default is [`DuplicateKeyPolicy::LastWins`].
+    pub fn with_duplicate_key_policy(mut self, policy: DuplicateKeyPolicy) -> Self {
+        self.duplicate_keys = policy;
+        self
+    }
+
+    /// Sets the parser's limits and returns it.
+    ///
+    /// The default is no limits.
+    pub fn with_limits(mut self, limits: ParserLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     /// Parses input into a structured field value of `Dictionary` type.
     #[cfg(feature = "parsed-types")]
     pub fn parse_dictionary(self) -> SFVResult<Dictionary> {
-        let mut dict = Dictionary::new();
+        let mut dict = Dictionary::default();
         self.parse_dictionary_with_visitor(&mut dict)?;
         Ok(dict)
     }
@@ -90,15 +306,15 @@ impl<'a> Parser<'a> {
         feature = "parsed-types",
         doc = r##"
 
-This can also be used to parse a dictionary that is split into multiple lines by merging
-them into an existing structure:
+To parse a dictionary that is split into multiple field lines, use
+[`Parser::from_field_lines`], which combines them into a single value before parsing and
+locates errors within the original lines:
 
 ```
 # use sfv::{Parser, SerializeValue};
 # fn main() -> Result<(), sfv::Error> {
-let mut dict = Parser::new("a=1").parse_dictionary()?;
-
-Parser::new("b=2").parse_dictionary_with_visitor(&mut dict)?;
+let lines = Parser::from_field_lines(["a=1", "b=2"]);
+let dict = lines.parser().parse_dictionary()?;
 
 assert_eq!(
     dict.serialize_value()?,
@@ -106,6 +322,7 @@ assert_eq!(
 );
 # Ok(())
 # }
+```
 "##
     )]
     pub fn parse_dictionary_with_visitor(
@@ -114,23 +331,56 @@ assert_eq!(
     ) -> SFVResult<()> {
         // https://httpwg.org/specs/rfc9651.html#parse-dictionary
         self.parse(|parser| {
+            let mut seen = SeenKeys::new();
             parse_comma_separated(parser, |parser| {
-                // Note: It is up to the visitor to properly handle duplicate keys.
-                let entry_visitor = visitor.entry(parser.parse_key()?).map_err(Error::custom)?;
-
-                if let Some(b'=') = parser.peek() {
-                    parser.next();
-                    parser.parse_list_entry(entry_visitor)
-                } else {
-                    let param_visitor = entry_visitor
-                        .bare_item(BareItemFromInput::from(true))
-                        .map_err(Error::custom)?;
-                    parser.parse_parameters(param_visitor)
-                }
+                parse_dictionary_member(parser, visitor, &mut seen)
             })
         })
     }
 
+    /// Parses input into a structured field value of `Dictionary` type,
+    /// skipping malformed members instead of failing.
+    ///
+    /// Each member that cannot be parsed is skipped up to the next top-level
+    /// comma, and the error that caused it to be skipped is returned
+    /// alongside the members that were parsed successfully.
+    ///
+    /// RFC 9651 requires the entire field to be ignored if any part of it is
+    /// invalid, so this should only be used where salvaging a partial value is
+    /// acceptable, such as for logging or diagnostics.
+    ///
+    /// ```
+    /// # use sfv::{ErrorKind, Parser, SerializeValue};
+    /// # fn main() -> Result<(), sfv::Error> {
+    /// let (dict, errors) = Parser::new("a=1, b=?2, c=3").parse_dictionary_lenient();
+    ///
+    /// assert_eq!(dict.serialize_value()?, "a=1, c=3");
+    /// assert_eq!(errors.len(), 1);
+    /// assert_eq!(errors[0].kind(), ErrorKind::InvalidBoolean);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "parsed-types")]
+    pub fn parse_dictionary_lenient(mut self) -> (Dictionary, Vec<Error>) {
+        let mut dict = Dictionary::default();
+        let mut errors = Vec::new();
+        let mut seen = SeenKeys::new();
+        parse_comma_separated_lenient(
+            &mut self,
+            &mut errors,
+            |parser| {
+                let mut member = Dictionary::default();
+                // Forget the key of a member that is skipped.
+                let seen_len = seen.len();
+                parse_dictionary_member(parser, &mut member, &mut seen)
+                    .inspect_err(|_| seen.truncate(seen_len))?;
+                Ok(member)
+            },
+            |member| dict.extend(member),
+        );
+        (dict, errors)
+    }
+
     /// Parses input into a structured field value of `List` type.
     #[cfg(feature = "parsed-types")]
     pub fn parse_list(self) -> SFVResult<List> {
@@ -145,14 +395,14 @@ assert_eq!(
         feature = "parsed-types",
         doc = r##"
 
-This can also be used to parse a list that is split into multiple lines by merging them
-into an existing structure:
+To parse a list that is split into multiple field lines, use [`Parser::from_field_lines`],
+which combines them into a single value before parsing and locates errors within the
+original lines:
 ```
 # use sfv::{Parser, SerializeValue};
 # fn main() -> Result<(), sfv::Error> {
-let mut list = Parser::new("11, (12 13)").parse_list()?;
-
-Parser::new(r#""foo",        "bar""#).parse_list_with_visitor(&mut list)?;
+let lines = Parser::from_field_lines(["11, (12 13)", r#""foo",        "bar""#]);
+let list = lines.parser().parse_list()?;
 
 assert_eq!(
     list.serialize_value()?,
@@ -175,6 +425,46 @@ assert_eq!(
         })
     }
 
+    /// Parses input into a structured field value of `List` type, skipping
+    /// malformed members instead of failing.
+    ///
+    /// Each member that cannot be parsed is skipped up to the next top-level
+    /// comma, and the error that caused it to be skipped is returned
+    /// alongside the members that were parsed successfully.
+    ///
+    /// RFC 9651 requires the entire field to be ignored if any part of it is
+    /// invalid, so this should only be used where salvaging a partial value is
+    /// acceptable, such as for logging or diagnostics.
+    ///
+    /// ```
+    /// # use sfv::{ErrorKind, Parser, SerializeValue};
+    /// # fn main() -> Result<(), sfv::Error> {
+    /// let (list, errors) = Parser::new(r#"1, "a, b" c, (2 3), 1234567890123456"#).parse_list_lenient();
+    ///
+    /// assert_eq!(list.serialize_value()?, "1, (2 3)");
+    /// assert_eq!(errors.len(), 2);
+    /// assert_eq!(errors[0].kind(), ErrorKind::ExpectedComma);
+    /// assert_eq!(errors[1].kind(), ErrorKind::IntegerTooLong);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "parsed-types")]
+    pub fn parse_list_lenient(mut self) -> (List, Vec<Error>) {
+        let mut list = List::new();
+        let mut errors = Vec::new();
+        parse_comma_separated_lenient(
+            &mut self,
+            &mut errors,
+            |parser| {
+                let mut member = List::new();
+                parser.parse_list_entry(&mut member)?;
+                Ok(member)
+            },
+            |member| list.extend(member),
+        );
+        (list, errors)
+    }
+
     /// Parses input into a structured field value of `Item` type.
     #[cfg(feature = "parsed-types")]
     pub fn parse_item(self) -> SFVResult<Item> {
@@ -189,42 +479,119 @@ assert_eq!(
         self.parse(|parser| parse_item(parser, visitor))
     }
 
-    fn peek(&self) -> Option<u8> {
+    // Returns a copy of the parser that only validates byte sequences, for
+    // parsing ahead without keeping the result.
+    pub(crate) fn lookahead(&self) -> Self {
+        Self {
+            input: self.input,
+            index: self.index,
+            version: self.version,
+            duplicate_keys: self.duplicate_keys,
+            limits: self.limits,
+            validate_only: true,
+            position: self.position,
+            diagnostics: self.diagnostics,
+        }
+    }
+
+    // Returns the parser, which records its index in `position` before each
+    // call to a visitor.
+    pub(crate) fn with_position<'b>(self, position: &'b Cell<usize>) -> Parser<'b>
+    where
+        'a: 'b,
+    {
+        Parser {
+            position: Some(position),
+            ..self
+        }
+    }
+
+    // Returns the parser, which collects errors in `diagnostics` and resumes
+    // parsing after them.
+    pub(crate) fn with_diagnostics<'b>(
+        self,
+        diagnostics: &'b RefCell<Vec<Diagnostic>>,
+    ) -> Parser<'b>
+    where
+        'a: 'b,
+    {
+        Parser {
+            diagnostics: Some(diagnostics),
+            ..self
+        }
+    }
+
+    fn record_position(&self) {
+        if let Some(position) = self.position {
+            position.set(self.index);
+        }
+    }
+
+    pub(crate) fn peek(&self) -> Option<u8> {
         self.input.get(self.index).copied()
     }
 
-    fn next(&mut self) -> Option<u8> {
+    pub(crate) fn next(&mut self) -> Option<u8> {
         self.peek().inspect(|_| self.index += 1)
     }
 
-    fn error<T>(&self, msg: &'static str) -> SFVResult<T> {
-        Err(Error::with_index(msg, self.index))
+    pub(crate) fn error<T>(&self, kind: ErrorKind, msg: &'static str) -> SFVResult<T> {
+        Err(Error::with_index(kind, msg, self.index))
+    }
+
+    // Returns an error at the current index if `count` exceeds `max`.
+    pub(crate) fn check_limit(&self, count: usize, max: usize, msg: &'static str) -> SFVResult<()> {
+        if count > max {
+            self.error(ErrorKind::LimitExceeded, msg)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn check_input_len(&self) -> SFVResult<()> {
+        if self.input.len() > self.limits.max_input_len {
+            Err(Error::with_index(
+                ErrorKind::LimitExceeded,
+                "input too long",
+                self.limits.max_input_len,
+            ))
+        } else {
+            Ok(())
+        }
     }
 
     // Generic parse method for checking input before parsing
     // and handling trailing text error
-    fn parse(mut self, f: impl FnOnce(&mut Self) -> SFVResult<()>) -> SFVResult<()> {
+    pub(crate) fn parse<T>(mut self, f: impl FnOnce(&mut Self) -> SFVResult<T>) -> SFVResult<T> {
         // https://httpwg.org/specs/rfc9651.html#text-parse
 
+        self.check_input_len()?;
+
         self.consume_sp_chars();
 
-        f(&mut self)?;
+        let output = f(&mut self)?;
 
         self.consume_sp_chars();
 
         if self.peek().is_some() {
-            self.error("trailing characters after parsed value")
+            self.error(
+                ErrorKind::TrailingCharacters,
+                "trailing characters after parsed value",
+            )
         } else {
-            Ok(())
+            Ok(output)
         }
     }
 
-    fn parse_list_entry(&mut self, visitor: impl EntryVisitor<'a>) -> SFVResult<()> {
+    pub(crate) fn parse_list_entry(&mut self, visitor: impl EntryVisitor<'a>) -> SFVResult<()> {
         // https://httpwg.org/specs/rfc9651.html#parse-item-or-list
         // ListEntry represents a tuple (item_or_inner_list, parameters)
 
         match self.peek() {
-            Some(b'(') => self.parse_inner_list(visitor.inner_list().map_err(Error::custom)?),
+            Some(b'(') => {
+                self.record_position();
+                self.parse_inner_list(visitor.inner_list().map_err(Error::custom)?)
+            }
             _ => parse_item(self, visitor),
         }
     }
@@ -236,30 +603,49 @@ assert_eq!(
         // https://httpwg.org/specs/rfc9651.html#parse-innerlist
 
         if Some(b'(') != self.peek() {
-            return self.error("expected start of inner list");
+            return self.error(ErrorKind::InvalidInnerList, "expected start of inner list");
         }
 
         self.next();
 
+        let mut items = 0;
+
         while self.peek().is_some() {
             self.consume_sp_chars();
 
             if Some(b')') == self.peek() {
                 self.next();
+                self.record_position();
                 let param_visitor = visitor.finish().map_err(Error::custom)?;
                 return self.parse_parameters(param_visitor);
             }
 
+            items += 1;
+            self.check_limit(
+                items,
+                self.limits.max_inner_list_len,
+                "too many inner list items",
+            )?;
+
+            self.record_position();
             parse_item(self, visitor.item().map_err(Error::custom)?)?;
 
             if let Some(c) = self.peek() {
                 if c != b' ' && c != b')' {
-                    return self.error("expected inner list delimiter (' ' or ')')");
+                    let index = self.index;
+                    self.recover_value(
+                        Error::with_index(
+                            ErrorKind::InvalidInnerList,
+                            "expected inner list delimiter (' ' or ')')",
+                            index,
+                        ),
+                        index,
+                    )?;
                 }
             }
         }
 
-        self.error("unterminated inner list")
+        self.error(ErrorKind::InvalidInnerList, "unterminated inner list")
     }
 
     pub(crate) fn parse_bare_item(&mut self) -> SFVResult<BareItemFromInput<'a>> {
@@ -280,7 +666,7 @@ assert_eq!(
                 Num::Decimal(val) => Ok(BareItemFromInput::Decimal(val)),
                 Num::Integer(val) => Ok(BareItemFromInput::Integer(val)),
             },
-            _ => self.error("expected start of bare item"),
+            _ => self.error(ErrorKind::ExpectedBareItem, "expected start of bare item"),
         }
     }
 
@@ -288,7 +674,10 @@ assert_eq!(
         // https://httpwg.org/specs/rfc9651.html#parse-boolean
 
         if self.peek() != Some(b'?') {
-            return self.error("expected start of boolean ('?')");
+            return self.error(
+                ErrorKind::ExpectedBareItem,
+                "expected start of boolean ('?')",
+            );
         }
 
         self.next();
@@ -302,7 +691,7 @@ assert_eq!(
                 self.next();
                 Ok(true)
             }
-            _ => self.error("expected boolean ('0' or '1')"),
+            _ => self.error(ErrorKind::InvalidBoolean, "expected boolean ('0' or '1')"),
         }
     }
 
@@ -310,55 +699,103 @@ assert_eq!(
         // https://httpwg.org/specs/rfc9651.html#parse-string
 
         if self.peek() != Some(b'"') {
-            return self.error(r#"expected start of string ('"')"#);
+            return self.error(
+                ErrorKind::ExpectedBareItem,
+                r#"expected start of string ('"')"#,
+            );
         }
 
+        let quote_index = self.index;
         self.next();
 
         let start = self.index;
         let mut output = Cow::Borrowed(&[] as &[u8]);
 
-        while let Some(curr_char) = self.peek() {
-            match curr_char {
-                b'"' => {
+        loop {
+            // Characters other than escape sequences are consumed in runs, which
+            // are borrowed from the input until the first escape sequence.
+            let run = self.scan_while(utils::is_unescaped_string_char);
+            if let Cow::Owned(ref output) = output {
+                self.check_string_len(output.len() + run.len(), quote_index)?;
+            } else {
+                self.check_string_len(self.index - start, quote_index)?;
+            }
+            match output {
+                Cow::Borrowed(ref mut output) => *output = &self.input[start..self.index],
+                Cow::Owned(ref mut output) => output.extend_from_slice(run),
+            }
+
+            match self.peek() {
+                Some(b'"') => {
                     self.next();
                     // TODO: The UTF-8 validation is redundant with the preceding character checks, but
                     // its removal is only possible with unsafe code.
                     return Ok(match output {
                         Cow::Borrowed(output) => {
-                            let output = std::str::from_utf8(output).unwrap();
-                            Cow::Borrowed(StringRef::from_str(output).unwrap())
+                            let output = core::str::from_utf8(output).unwrap();
+                            Cow::Borrowed(StringRef::from_validated_str(output))
                         }
                         Cow::Owned(output) => {
                             let output = StdString::from_utf8(output).unwrap();
-                            Cow::Owned(String::from_string(output).unwrap())
+                            Cow::Owned(String::from_validated_string(output))
                         }
                     });
                 }
-                0x00..=0x1f | 0x7f..=0xff => {
-                    return self.error("invalid string character");
-                }
-                b'\\' => {
+                Some(b'\\') => {
                     self.next();
                     match self.peek() {
                         Some(c @ b'\\' | c @ b'"') => {
                             self.next();
+                            self.check_string_len(output.len() + 1, quote_index)?;
                             output.to_mut().push(c);
                         }
-                        None => return self.error("unterminated escape sequence"),
-                        Some(_) => return self.error("invalid escape sequence"),
+                        None => {
+                            return self.error(
+                                ErrorKind::InvalidEscapeSequence,
+                                "unterminated escape sequence",
+                            )
+                        }
+                        Some(_) => {
+                            return self
+                                .error(ErrorKind::InvalidEscapeSequence, "invalid escape sequence")
+                        }
                     }
                 }
-                _ => {
-                    self.next();
-                    match output {
-                        Cow::Borrowed(ref mut output) => *output = &self.input[start..self.index],
-                        Cow::Owned(ref mut output) => output.push(curr_char),
-                    }
+                Some(_) => {
+                    return self.error(
+                        ErrorKind::InvalidStringCharacter,
+                        "invalid string character",
+                    );
                 }
+                None => return self.error(ErrorKind::UnterminatedString, "unterminated string"),
             }
         }
-        self.error("unterminated string")
+    }
+
+    // Returns an error at the start of a string or display string if `len`
+    // exceeds the maximum string length.
+    fn check_string_len(&self, len: usize, start: usize) -> SFVResult<()> {
+        if len > self.limits.max_string_len {
+            Err(Error::with_index(
+                ErrorKind::LimitExceeded,
+                "string too long",
+                start,
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    // Advances past the longest run of characters for which `is_allowed`
+    // returns true, and returns the run.
+    fn scan_while(&mut self, is_allowed: impl Fn(u8) -> bool) -> &'a [u8] {
+        let rest = &self.input[self.index..];
+        let len = rest
+            .iter()
+            .position(|&c| !is_allowed(c))
+            .unwrap_or(rest.len());
+        self.index += len;
+        &rest[..len]
     }
 
     fn parse_non_empty_str(
@@ -375,16 +812,10 @@ assert_eq!(
             _ => return None,
         }
 
-        loop {
-            match self.peek() {
-                Some(c) if is_allowed_inner_char(c) => {
-                    self.next();
-                }
-                // TODO: The UTF-8 validation is redundant with the preceding character checks, but
-                // its removal is only possible with unsafe code.
-                _ => return Some(std::str::from_utf8(&self.input[start..self.index]).unwrap()),
-            }
-        }
+        self.scan_while(is_allowed_inner_char);
+        // TODO: The UTF-8 validation is redundant with the preceding character checks, but
+        // its removal is only possible with unsafe code.
+        Some(core::str::from_utf8(&self.input[start..self.index]).unwrap())
     }
 
     pub(crate) fn parse_token(&mut self) -> SFVResult<&'a TokenRef> {
@@ -394,7 +825,7 @@ assert_eq!(
             utils::is_allowed_start_token_char,
             utils::is_allowed_inner_token_char,
         ) {
-            None => self.error("expected start of token"),
+            None => self.error(ErrorKind::ExpectedBareItem, "expected start of token"),
             Some(str) => Ok(TokenRef::from_validated_str(str)),
         }
     }
@@ -403,7 +834,10 @@ assert_eq!(
         // https://httpwg.org/specs/rfc9651.html#parse-binary
 
         if self.peek() != Some(b':') {
-            return self.error("expected start of byte sequence (':')");
+            return self.error(
+                ErrorKind::ExpectedBareItem,
+                "expected start of byte sequence (':')",
+            );
         }
 
         self.next();
@@ -413,13 +847,40 @@ assert_eq!(
             match self.next() {
                 Some(b':') => break,
                 Some(_) => {}
-                None => return self.error("unterminated byte sequence"),
+                None => {
+                    return self.error(
+                        ErrorKind::UnterminatedByteSequence,
+                        "unterminated byte sequence",
+                    )
+                }
             }
         }
 
         let colon_index = self.index - 1;
 
-        match base64::Engine::decode(&utils::BASE64, &self.input[start..colon_index]) {
+        let content = &self.input[start..colon_index];
+
+        // Each 4 base64 characters, excluding padding, decode to 3 bytes.
+        let unpadded_len = content
+            .iter()
+            .rposition(|&c| c != b'=')
+            .map_or(0, |i| i + 1);
+        let decoded_len = unpadded_len / 4 * 3 + unpadded_len % 4 * 3 / 4;
+        if decoded_len > self.limits.max_byte_sequence_len {
+            return Err(Error::with_index(
+                ErrorKind::LimitExceeded,
+                "byte sequence too long",
+                start - 1,
+            ));
+        }
+
+        if self.validate_only && utils::is_valid_base64(content) {
+            return Ok(Vec::new());
+        }
+
+        // Invalid input is decoded even when only validating, in order to
+        // report the same error.
+        match base64::Engine::decode(&utils::BASE64, content) {
             Ok(content) => Ok(content),
             Err(err) => {
                 let index = match err {
@@ -433,7 +894,11 @@ assert_eq!(
                     }
                 };
 
-                Err(Error::with_index("invalid byte sequence", index))
+                Err(Error::with_index(
+                    ErrorKind::InvalidBase64,
+                    "invalid byte sequence",
+                    index,
+                ))
             }
         }
     }
@@ -457,7 +922,7 @@ assert_eq!(
                 self.next();
                 char_to_i64(c)
             }
-            _ => return self.error("expected digit"),
+            _ => return self.error(ErrorKind::ExpectedDigit, "expected digit"),
         };
 
         let mut digits = 1;
@@ -466,7 +931,10 @@ assert_eq!(
             match self.peek() {
                 Some(b'.') => {
                     if digits > 12 {
-                        return self.error("too many digits before decimal point");
+                        return self.error(
+                            ErrorKind::InvalidDecimal,
+                            "too many digits before decimal point",
+                        );
                     }
                     self.next();
                     break;
@@ -474,7 +942,7 @@ assert_eq!(
                 Some(c @ b'0'..=b'9') => {
                     digits += 1;
                     if digits > 15 {
-                        return self.error("too many digits");
+                        return self.error(ErrorKind::IntegerTooLong, "too many digits");
                     }
                     self.next();
                     magnitude = magnitude * 10 + char_to_i64(c);
@@ -488,7 +956,10 @@ assert_eq!(
 
         while let Some(c @ b'0'..=b'9') = self.peek() {
             if scale == 0 {
-                return self.error("too many digits after decimal point");
+                return self.error(
+                    ErrorKind::InvalidDecimal,
+                    "too many digits after decimal point",
+                );
             }
 
             self.next();
@@ -499,7 +970,11 @@ assert_eq!(
         if scale == 100 {
             // Report the error at the position of the decimal itself, rather
             // than the next position.
-            Err(Error::with_index("trailing decimal point", self.index - 1))
+            Err(Error::with_index(
+                ErrorKind::InvalidDecimal,
+                "trailing decimal point",
+                self.index - 1,
+            ))
         } else {
             Ok(Num::Decimal(Decimal::from_integer_scaled_1000(
                 Integer::try_from(sign * magnitude).unwrap(),
@@ -511,11 +986,16 @@ assert_eq!(
         // https://httpwg.org/specs/rfc9651.html#parse-date
 
         if self.peek() != Some(b'@') {
-            return self.error("expected start of date ('@')");
+            return self.error(ErrorKind::ExpectedBareItem, "expected start of date ('@')");
         }
 
         match self.version {
-            Version::Rfc8941 => return self.error("RFC 8941 does not support dates"),
+            Version::Rfc8941 => {
+                return self.error(
+                    ErrorKind::UnsupportedByVersion,
+                    "RFC 8941 does not support dates",
+                )
+            }
             Version::Rfc9651 => {}
         }
 
@@ -525,6 +1005,7 @@ assert_eq!(
         match self.parse_number()? {
             Num::Integer(seconds) => Ok(Date::from_unix_seconds(seconds)),
             Num::Decimal(_) => Err(Error::with_index(
+                ErrorKind::InvalidDate,
                 "date must be an integer number of seconds",
                 start,
             )),
@@ -535,18 +1016,27 @@ assert_eq!(
         // https://httpwg.org/specs/rfc9651.html#parse-display
 
         if self.peek() != Some(b'%') {
-            return self.error("expected start of display string ('%')");
+            return self.error(
+                ErrorKind::ExpectedBareItem,
+                "expected start of display string ('%')",
+            );
         }
 
         match self.version {
-            Version::Rfc8941 => return self.error("RFC 8941 does not support display strings"),
+            Version::Rfc8941 => {
+                return self.error(
+                    ErrorKind::UnsupportedByVersion,
+                    "RFC 8941 does not support display strings",
+                )
+            }
             Version::Rfc9651 => {}
         }
 
+        let percent_index = self.index;
         self.next();
 
         if self.peek() != Some(b'"') {
-            return self.error(r#"expected '"'"#);
+            return self.error(ErrorKind::InvalidDisplayString, r#"expected '"'"#);
         }
 
         self.next();
@@ -559,9 +1049,10 @@ assert_eq!(
                 b'"' => {
                     self.next();
                     return match output {
-                        Cow::Borrowed(output) => match std::str::from_utf8(output) {
+                        Cow::Borrowed(output) => match core::str::from_utf8(output) {
                             Ok(output) => Ok(Cow::Borrowed(output)),
                             Err(err) => Err(Error::with_index(
+                                ErrorKind::InvalidDisplayString,
                                 "invalid UTF-8 in display string",
                                 start + err.valid_up_to(),
                             )),
@@ -569,6 +1060,7 @@ assert_eq!(
                         Cow::Owned(output) => match StdString::from_utf8(output) {
                             Ok(output) => Ok(Cow::Owned(output)),
                             Err(err) => Err(Error::with_index(
+                                ErrorKind::InvalidDisplayString,
                                 "invalid UTF-8 in display string",
                                 start + err.utf8_error().valid_up_to(),
                             )),
@@ -576,7 +1068,10 @@ assert_eq!(
                     };
                 }
                 0x00..=0x1f | 0x7f..=0xff => {
-                    return self.error("invalid display string character");
+                    return self.error(
+                        ErrorKind::InvalidDisplayString,
+                        "invalid display string character",
+                    );
                 }
                 b'%' => {
                     self.next();
@@ -594,14 +1089,26 @@ assert_eq!(
                                     self.next();
                                     c - b'a' + 10
                                 }
-                                None => return self.error("unterminated escape sequence"),
-                                Some(_) => return self.error("invalid escape sequence"),
+                                None => {
+                                    return self.error(
+                                        ErrorKind::InvalidEscapeSequence,
+                                        "unterminated escape sequence",
+                                    )
+                                }
+                                Some(_) => {
+                                    return self.error(
+                                        ErrorKind::InvalidEscapeSequence,
+                                        "invalid escape sequence",
+                                    )
+                                }
                             };
                     }
 
+                    self.check_string_len(output.len() + 1, percent_index)?;
                     output.to_mut().push(octet);
                 }
                 _ => {
+                    self.check_string_len(output.len() + 1, percent_index)?;
                     self.next();
                     match output {
                         Cow::Borrowed(ref mut output) => *output = &self.input[start..self.index],
@@ -610,7 +1117,10 @@ assert_eq!(
                 }
             }
         }
-        self.error("unterminated display string")
+        self.error(
+            ErrorKind::InvalidDisplayString,
+            "unterminated display string",
+        )
     }
 
     pub(crate) fn parse_parameters(
@@ -619,27 +1129,77 @@ assert_eq!(
     ) -> SFVResult<()> {
         // https://httpwg.org/specs/rfc9651.html#parse-param
 
+        let mut seen = SeenKeys::new();
+        let mut params = 0;
+
         while let Some(b';') = self.peek() {
+            params += 1;
+            self.check_limit(params, self.limits.max_params, "too many parameters")?;
+
             self.next();
             self.consume_sp_chars();
 
-            let param_name = self.parse_key()?;
+            let index = self.index;
+            let param_name = match self.parse_key() {
+                Ok(param_name) => param_name,
+                Err(err) => {
+                    self.recover_value(err, index)?;
+                    continue;
+                }
+            };
             let param_value = match self.peek() {
                 Some(b'=') => {
                     self.next();
-                    self.parse_bare_item()?
+                    let start = self.index;
+                    match self.parse_bare_item() {
+                        Ok(param_value) => param_value,
+                        Err(err) => {
+                            self.recover_value(err, start)?;
+                            continue;
+                        }
+                    }
                 }
                 _ => BareItemFromInput::Boolean(true),
             };
-            // Note: It is up to the visitor to properly handle duplicate keys.
-            visitor
-                .parameter(param_name, param_value)
-                .map_err(Error::custom)?;
+            if self.check_duplicate_key(&mut seen, param_name, index)? {
+                self.record_position();
+                visitor
+                    .parameter(param_name, param_value)
+                    .map_err(Error::custom)?;
+            }
         }
 
+        self.record_position();
         visitor.finish().map_err(Error::custom)
     }
 
+    // Returns whether the dictionary member or parameter with the given key,
+    // which occurred at the given index, should be passed to the visitor.
+    //
+    // Under `LastWins`, it is up to the visitor to properly handle duplicate
+    // keys.
+    pub(crate) fn check_duplicate_key(
+        &self,
+        seen: &mut SeenKeys<'a>,
+        key: &'a KeyRef,
+        index: usize,
+    ) -> SFVResult<bool> {
+        if self.duplicate_keys == DuplicateKeyPolicy::LastWins {
+            return Ok(true);
+        }
+
+        match seen.get(key) {
+            None => {
+                seen.insert(key, index);
+                Ok(true)
+            }
+            Some(previous_index) => match self.duplicate_keys {
+                DuplicateKeyPolicy::Reject => Err(Error::duplicate_key(index, previous_index)),
+                _ => Ok(false),
+            },
+        }
+    }
+
     pub(crate) fn parse_key(&mut self) -> SFVResult<&'a KeyRef> {
         // https://httpwg.org/specs/rfc9651.html#parse-key
 
@@ -647,18 +1207,46 @@ assert_eq!(
             utils::is_allowed_start_key_char,
             utils::is_allowed_inner_key_char,
         ) {
-            None => self.error("expected start of key ('a'-'z' or '*')"),
+            None => self.error(
+                ErrorKind::InvalidKeyCharacter,
+                "expected start of key ('a'-'z' or '*')",
+            ),
             Some(str) => Ok(KeyRef::from_validated_str(str)),
         }
     }
 
-    fn consume_ows_chars(&mut self) {
+    // Advances to the next byte matching `is_delimiter`, or to the end of
+    // input, without validating anything but the boundaries of strings and
+    // display strings, which may themselves contain delimiters.
+    pub(crate) fn skip_until(&mut self, is_delimiter: impl Fn(u8) -> bool) {
+        let mut in_string = false;
+        let mut in_display_string = false;
+        let mut prev = None;
+
+        while let Some(c) = self.peek() {
+            match c {
+                b'"' if !in_string => {
+                    in_string = true;
+                    in_display_string = prev == Some(b'%');
+                }
+                b'"' => in_string = false,
+                b'\\' if in_string && !in_display_string => {
+                    self.next();
+                }
+                _ if !in_string && is_delimiter(c) => break,
+                _ => {}
+            }
+            prev = self.next();
+        }
+    }
+
+    pub(crate) fn consume_ows_chars(&mut self) {
         while let Some(b' ' | b'\t') = self.peek() {
             self.next();
         }
     }
 
-    fn consume_sp_chars(&mut self) {
+    pub(crate) fn consume_sp_chars(&mut self) {
         while let Some(b' ') = self.peek() {
             self.next();
         }
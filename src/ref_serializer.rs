@@ -1,10 +1,10 @@
 use crate::serializer::Serializer;
-use crate::{Error, KeyRef, RefBareItem, SFVResult};
+use crate::{Error, ErrorKind, KeyRef, Output, RefBareItem, SFVResult};
 
 #[cfg(feature = "parsed-types")]
 use crate::{Item, ListEntry};
 
-use std::borrow::BorrowMut;
+use alloc::string::String;
 
 /// Serializes `Item` field value components incrementally.
 ///
@@ -53,11 +53,16 @@ impl ItemSerializer<String> {
 impl<'a> ItemSerializer<&'a mut String> {
     /// Creates a serializer that writes into the given string.
     pub fn with_buffer(buffer: &'a mut String) -> Self {
-        Self { buffer }
+        Self::with_output(buffer)
     }
 }
 
-impl<W: BorrowMut<String>> ItemSerializer<W> {
+impl<W: Output> ItemSerializer<W> {
+    /// Creates a serializer that writes into the given output.
+    pub fn with_output(buffer: W) -> Self {
+        Self { buffer }
+    }
+
     /// Serializes the given bare item.
     ///
     /// Returns a serializer for the item's parameters.
@@ -65,7 +70,7 @@ impl<W: BorrowMut<String>> ItemSerializer<W> {
         mut self,
         bare_item: impl Into<RefBareItem<'b>>,
     ) -> ParameterSerializer<W> {
-        Serializer::serialize_bare_item(bare_item, self.buffer.borrow_mut());
+        Serializer::serialize_bare_item(bare_item, &mut self.buffer);
         ParameterSerializer {
             buffer: self.buffer,
         }
@@ -78,12 +83,12 @@ pub struct ParameterSerializer<W> {
     buffer: W,
 }
 
-impl<W: BorrowMut<String>> ParameterSerializer<W> {
+impl<W: Output> ParameterSerializer<W> {
     /// Serializes a parameter with the given name and value.
     ///
     /// Returns the serializer.
     pub fn parameter<'b>(mut self, name: &KeyRef, value: impl Into<RefBareItem<'b>>) -> Self {
-        Serializer::serialize_parameter(name, value, self.buffer.borrow_mut());
+        Serializer::serialize_parameter(name, value, &mut self.buffer);
         self
     }
 
@@ -95,7 +100,7 @@ impl<W: BorrowMut<String>> ParameterSerializer<W> {
         params: impl IntoIterator<Item = (impl AsRef<KeyRef>, impl Into<RefBareItem<'b>>)>,
     ) -> Self {
         for (name, value) in params {
-            Serializer::serialize_parameter(name.as_ref(), value, self.buffer.borrow_mut());
+            Serializer::serialize_parameter(name.as_ref(), value, &mut self.buffer);
         }
         self
     }
@@ -106,7 +111,7 @@ impl<W: BorrowMut<String>> ParameterSerializer<W> {
     }
 }
 
-fn maybe_write_separator(buffer: &mut String, first: &mut bool) {
+fn maybe_write_separator(buffer: &mut impl Output, first: &mut bool) {
     if *first {
         *first = false;
     } else {
@@ -178,22 +183,27 @@ impl ListSerializer<String> {
 impl<'a> ListSerializer<&'a mut String> {
     /// Creates a serializer that writes into the given string.
     pub fn with_buffer(buffer: &'a mut String) -> Self {
+        Self::with_output(buffer)
+    }
+}
+
+impl<W: Output> ListSerializer<W> {
+    /// Creates a serializer that writes into the given output.
+    pub fn with_output(buffer: W) -> Self {
         Self {
             buffer,
             first: true,
         }
     }
-}
 
-impl<W: BorrowMut<String>> ListSerializer<W> {
     /// Serializes the given bare item as a member of the list.
     ///
     /// Returns a serializer for the item's parameters.
     pub fn bare_item<'b>(
         &mut self,
         bare_item: impl Into<RefBareItem<'b>>,
-    ) -> ParameterSerializer<&mut String> {
-        let buffer = self.buffer.borrow_mut();
+    ) -> ParameterSerializer<&mut W> {
+        let buffer = &mut self.buffer;
         maybe_write_separator(buffer, &mut self.first);
         Serializer::serialize_bare_item(bare_item, buffer);
         ParameterSerializer { buffer }
@@ -201,12 +211,13 @@ impl<W: BorrowMut<String>> ListSerializer<W> {
 
     /// Opens an inner list, returning a serializer to be used for its items and
     /// parameters.
-    pub fn inner_list(&mut self) -> InnerListSerializer {
-        let buffer = self.buffer.borrow_mut();
+    pub fn inner_list(&mut self) -> InnerListSerializer<'_, W> {
+        let buffer = &mut self.buffer;
         maybe_write_separator(buffer, &mut self.first);
         buffer.push('(');
         InnerListSerializer {
             buffer: Some(buffer),
+            first: true,
         }
     }
 
@@ -234,7 +245,10 @@ impl<W: BorrowMut<String>> ListSerializer<W> {
     /// all](https://httpwg.org/specs/rfc9651.html#text-serialize).
     pub fn finish(self) -> SFVResult<W> {
         if self.first {
-            return Err(Error::new("serializing empty list is not allowed"));
+            return Err(Error::new(
+                ErrorKind::EmptyList,
+                "serializing empty list is not allowed",
+            ));
         }
         Ok(self.buffer)
     }
@@ -307,14 +321,19 @@ impl DictSerializer<String> {
 impl<'a> DictSerializer<&'a mut String> {
     /// Creates a serializer that writes into the given string.
     pub fn with_buffer(buffer: &'a mut String) -> Self {
+        Self::with_output(buffer)
+    }
+}
+
+impl<W: Output> DictSerializer<W> {
+    /// Creates a serializer that writes into the given output.
+    pub fn with_output(buffer: W) -> Self {
         Self {
             buffer,
             first: true,
         }
     }
-}
 
-impl<W: BorrowMut<String>> DictSerializer<W> {
     /// Serializes the given bare item as a member of the dictionary with the
     /// given key.
     ///
@@ -323,8 +342,8 @@ impl<W: BorrowMut<String>> DictSerializer<W> {
         &mut self,
         name: &KeyRef,
         value: impl Into<RefBareItem<'b>>,
-    ) -> ParameterSerializer<&mut String> {
-        let buffer = self.buffer.borrow_mut();
+    ) -> ParameterSerializer<&mut W> {
+        let buffer = &mut self.buffer;
         maybe_write_separator(buffer, &mut self.first);
         Serializer::serialize_key(name, buffer);
         let value = value.into();
@@ -337,13 +356,14 @@ impl<W: BorrowMut<String>> DictSerializer<W> {
 
     /// Opens an inner list with the given key, returning a serializer to be
     /// used for its items and parameters.
-    pub fn inner_list(&mut self, name: &KeyRef) -> InnerListSerializer {
-        let buffer = self.buffer.borrow_mut();
+    pub fn inner_list(&mut self, name: &KeyRef) -> InnerListSerializer<'_, W> {
+        let buffer = &mut self.buffer;
         maybe_write_separator(buffer, &mut self.first);
         Serializer::serialize_key(name, buffer);
         buffer.push_str("=(");
         InnerListSerializer {
             buffer: Some(buffer),
+            first: true,
         }
     }
 
@@ -375,7 +395,10 @@ impl<W: BorrowMut<String>> DictSerializer<W> {
     /// all](https://httpwg.org/specs/rfc9651.html#text-serialize).
     pub fn finish(self) -> SFVResult<W> {
         if self.first {
-            return Err(Error::new("serializing empty dictionary is not allowed"));
+            return Err(Error::new(
+                ErrorKind::EmptyDictionary,
+                "serializing empty dictionary is not allowed",
+            ));
         }
         Ok(self.buffer)
     }
@@ -390,11 +413,12 @@ impl<W: BorrowMut<String>> DictSerializer<W> {
 /// an invalid serialization that lacks a closing `)` character.
 // https://httpwg.org/specs/rfc9651.html#ser-innerlist
 #[derive(Debug)]
-pub struct InnerListSerializer<'a> {
-    buffer: Option<&'a mut String>,
+pub struct InnerListSerializer<'a, W: Output = String> {
+    buffer: Option<&'a mut W>,
+    first: bool,
 }
 
-impl Drop for InnerListSerializer<'_> {
+impl<W: Output> Drop for InnerListSerializer<'_, W> {
     fn drop(&mut self) {
         if let Some(ref mut buffer) = self.buffer {
             buffer.push(')');
@@ -402,18 +426,19 @@ impl Drop for InnerListSerializer<'_> {
     }
 }
 
-impl<'a> InnerListSerializer<'a> {
+impl<'a, W: Output> InnerListSerializer<'a, W> {
     /// Serializes the given bare item as a member of the inner list.
     ///
     /// Returns a serializer for the item's parameters.
     pub fn bare_item<'b>(
         &mut self,
         bare_item: impl Into<RefBareItem<'b>>,
-    ) -> ParameterSerializer<&mut String> {
+    ) -> ParameterSerializer<&mut W> {
         let buffer = self.buffer.as_mut().unwrap();
-        if !buffer.is_empty() && !buffer.ends_with('(') {
+        if !self.first {
             buffer.push(' ');
         }
+        self.first = false;
         Serializer::serialize_bare_item(bare_item, buffer);
         ParameterSerializer { buffer }
     }
@@ -427,7 +452,7 @@ impl<'a> InnerListSerializer<'a> {
     }
 
     /// Closes the inner list and returns a serializer for its parameters.
-    pub fn finish(mut self) -> ParameterSerializer<&'a mut String> {
+    pub fn finish(mut self) -> ParameterSerializer<&'a mut W> {
         let buffer = self.buffer.take().unwrap();
         buffer.push(')');
         ParameterSerializer { buffer }
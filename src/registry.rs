@@ -0,0 +1,270 @@
+/*!
+Maps HTTP field names to their structured types.
+
+[`parse_field`] parses a field value according to the type registered for its
+name in the [IANA HTTP Field Name Registry], or defined for a pre-existing
+field by [Retrofit Structured Fields for HTTP]:
+
+```
+# use sfv::registry::{self, FieldType, FieldValue};
+# fn main() -> Result<(), sfv::Error> {
+assert_eq!(registry::field_type("Cache-Control"), Some(FieldType::Dictionary));
+
+match registry::parse_field("accept-encoding", "gzip, br;q=0.9")? {
+    FieldValue::List(list) => assert_eq!(list.len(), 2),
+    _ => unreachable!(),
+}
+# Ok(())
+# }
+```
+
+Retrofitted fields predate structured fields, so some valid values, such as
+an HTTP date in `Retry-After`, cannot be parsed as structured fields.
+
+Additional names, such as those of proprietary fields, can be added to a
+[`Registry`]:
+
+```
+# use sfv::registry::{FieldType, Registry};
+# fn main() -> Result<(), sfv::Error> {
+let mut registry = Registry::new();
+registry.insert("X-Example", FieldType::Item);
+
+assert_eq!(registry.parse_field("x-example", "?1")?.field_type(), FieldType::Item);
+assert_eq!(registry.field_type("Cache-Control"), Some(FieldType::Dictionary));
+# Ok(())
+# }
+```
+
+[IANA HTTP Field Name Registry]: <https://www.iana.org/assignments/http-fields/http-fields.xhtml>
+[Retrofit Structured Fields for HTTP]: <https://datatracker.ietf.org/doc/draft-ietf-httpbis-retrofit/>
+*/
+
+use crate::{Dictionary, Error, ErrorKind, Item, List, Parser, SFVResult, SerializeValue};
+
+use alloc::string::String as StdString;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// The top-level type of a structured field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FieldType {
+    /// An [item](https://httpwg.org/specs/rfc9651.html#item).
+    Item,
+    /// A [list](https://httpwg.org/specs/rfc9651.html#list).
+    List,
+    /// A [dictionary](https://httpwg.org/specs/rfc9651.html#dictionary).
+    Dictionary,
+}
+
+impl FieldType {
+    /// Parses the parser's input as a structured field of this type.
+    pub fn parse(self, parser: Parser<'_>) -> SFVResult<FieldValue> {
+        match self {
+            Self::Item => parser.parse_item().map(FieldValue::Item),
+            Self::List => parser.parse_list().map(FieldValue::List),
+            Self::Dictionary => parser.parse_dictionary().map(FieldValue::Dictionary),
+        }
+    }
+}
+
+/// A parsed structured field of any type.
+#[derive(Debug, PartialEq, Clone)]
+pub enum FieldValue {
+    /// An item.
+    Item(Item),
+    /// A list.
+    List(List),
+    /// A dictionary.
+    Dictionary(Dictionary),
+}
+
+impl FieldValue {
+    /// Returns the type of the field.
+    pub fn field_type(&self) -> FieldType {
+        match self {
+            Self::Item(_) => FieldType::Item,
+            Self::List(_) => FieldType::List,
+            Self::Dictionary(_) => FieldType::Dictionary,
+        }
+    }
+}
+
+impl SerializeValue for FieldValue {
+    fn serialize_value(&self) -> SFVResult<StdString> {
+        match self {
+            Self::Item(item) => item.serialize_value(),
+            Self::List(list) => list.serialize_value(),
+            Self::Dictionary(dict) => dict.serialize_value(),
+        }
+    }
+}
+
+// Sorted by lowercase name.
+pub(crate) static FIELDS: &[(&str, FieldType)] = &[
+    ("accept", FieldType::List),
+    ("accept-ch", FieldType::List),
+    ("accept-encoding", FieldType::List),
+    ("accept-language", FieldType::List),
+    ("accept-patch", FieldType::List),
+    ("accept-post", FieldType::List),
+    ("accept-ranges", FieldType::List),
+    ("accept-signature", FieldType::Dictionary),
+    ("access-control-allow-credentials", FieldType::Item),
+    ("access-control-allow-headers", FieldType::List),
+    ("access-control-allow-methods", FieldType::List),
+    ("access-control-allow-origin", FieldType::Item),
+    ("access-control-expose-headers", FieldType::List),
+    ("access-control-max-age", FieldType::Item),
+    ("access-control-request-headers", FieldType::List),
+    ("access-control-request-method", FieldType::Item),
+    ("age", FieldType::Item),
+    ("allow", FieldType::List),
+    ("alpn", FieldType::List),
+    ("alt-svc", FieldType::Dictionary),
+    ("alt-used", FieldType::Item),
+    ("available-dictionary", FieldType::Item),
+    ("cache-control", FieldType::Dictionary),
+    ("cache-status", FieldType::List),
+    ("cdn-cache-control", FieldType::Dictionary),
+    ("cdn-loop", FieldType::List),
+    ("clear-site-data", FieldType::List),
+    ("client-cert", FieldType::Item),
+    ("client-cert-chain", FieldType::List),
+    ("connection", FieldType::List),
+    ("content-digest", FieldType::Dictionary),
+    ("content-encoding", FieldType::List),
+    ("content-language", FieldType::List),
+    ("content-length", FieldType::List),
+    ("content-type", FieldType::Item),
+    ("cross-origin-embedder-policy", FieldType::Item),
+    ("cross-origin-embedder-policy-report-only", FieldType::Item),
+    ("cross-origin-opener-policy", FieldType::Item),
+    ("cross-origin-opener-policy-report-only", FieldType::Item),
+    ("cross-origin-resource-policy", FieldType::Item),
+    ("deprecation", FieldType::Item),
+    ("dictionary-id", FieldType::Item),
+    ("dnt", FieldType::Item),
+    ("expect", FieldType::Dictionary),
+    ("expect-ct", FieldType::Dictionary),
+    ("host", FieldType::Item),
+    ("keep-alive", FieldType::Dictionary),
+    ("max-forwards", FieldType::Item),
+    ("origin", FieldType::Item),
+    ("origin-agent-cluster", FieldType::Item),
+    ("pragma", FieldType::Dictionary),
+    ("prefer", FieldType::Dictionary),
+    ("preference-applied", FieldType::Dictionary),
+    ("priority", FieldType::Dictionary),
+    ("proxy-status", FieldType::List),
+    ("repr-digest", FieldType::Dictionary),
+    ("retry-after", FieldType::Item),
+    ("sec-websocket-extensions", FieldType::List),
+    ("sec-websocket-protocol", FieldType::List),
+    ("sec-websocket-version", FieldType::Item),
+    ("server-timing", FieldType::List),
+    ("signature", FieldType::Dictionary),
+    ("signature-input", FieldType::Dictionary),
+    ("surrogate-control", FieldType::Dictionary),
+    ("te", FieldType::List),
+    ("timing-allow-origin", FieldType::List),
+    ("trailer", FieldType::List),
+    ("transfer-encoding", FieldType::List),
+    ("upgrade-insecure-requests", FieldType::Item),
+    ("use-as-dictionary", FieldType::Dictionary),
+    ("vary", FieldType::List),
+    ("want-content-digest", FieldType::Dictionary),
+    ("want-repr-digest", FieldType::Dictionary),
+    ("x-content-type-options", FieldType::Item),
+    ("x-frame-options", FieldType::Item),
+    ("x-xss-protection", FieldType::List),
+];
+
+fn cmp_ignore_ascii_case(a: &str, b: &str) -> Ordering {
+    let a = a.bytes().map(|c| c.to_ascii_lowercase());
+    let b = b.bytes().map(|c| c.to_ascii_lowercase());
+    a.cmp(b)
+}
+
+fn lookup(fields: &[(impl AsRef<str>, FieldType)], name: &str) -> Option<FieldType> {
+    fields
+        .binary_search_by(|(field, _)| cmp_ignore_ascii_case(field.as_ref(), name))
+        .ok()
+        .map(|i| fields[i].1)
+}
+
+fn unknown_field() -> Error {
+    Error::new(ErrorKind::TypeMismatch, "unknown structured field name")
+}
+
+/// Returns the structured type of the field with the given name, which is
+/// matched case-insensitively.
+pub fn field_type(name: &str) -> Option<FieldType> {
+    lookup(FIELDS, name)
+}
+
+/// Parses the given value of the field with the given name according to its
+/// structured type.
+///
+/// Fails with [`ErrorKind::TypeMismatch`] if the field does not have a
+/// structured type.
+pub fn parse_field(name: &str, value: &(impl ?Sized + AsRef<[u8]>)) -> SFVResult<FieldValue> {
+    field_type(name)
+        .ok_or_else(unknown_field)?
+        .parse(Parser::new(value))
+}
+
+/// A set of field names with their structured types, which extends the
+/// fields known to [`field_type`].
+#[derive(Debug, Default, Clone)]
+pub struct Registry {
+    // Sorted case-insensitively.
+    fields: Vec<(StdString, FieldType)>,
+}
+
+impl Registry {
+    /// Creates a registry that contains only the fields known to
+    /// [`field_type`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the structured type of the field with the given name, which is
+    /// matched case-insensitively.
+    ///
+    /// This overrides the type of a field known to [`field_type`]. Returns the
+    /// type previously set for the name in this registry, if any.
+    pub fn insert(&mut self, name: &str, field_type: FieldType) -> Option<FieldType> {
+        match self
+            .fields
+            .binary_search_by(|(field, _)| cmp_ignore_ascii_case(field, name))
+        {
+            Ok(i) => Some(core::mem::replace(&mut self.fields[i].1, field_type)),
+            Err(i) => {
+                self.fields.insert(i, (name.into(), field_type));
+                None
+            }
+        }
+    }
+
+    /// Returns the structured type of the field with the given name, which is
+    /// matched case-insensitively.
+    pub fn field_type(&self, name: &str) -> Option<FieldType> {
+        lookup(&self.fields, name).or_else(|| field_type(name))
+    }
+
+    /// Parses the given value of the field with the given name according to
+    /// its structured type.
+    ///
+    /// Fails with [`ErrorKind::TypeMismatch`] if the field does not have a
+    /// structured type.
+    pub fn parse_field(
+        &self,
+        name: &str,
+        value: &(impl ?Sized + AsRef<[u8]>),
+    ) -> SFVResult<FieldValue> {
+        self.field_type(name)
+            .ok_or_else(unknown_field)?
+            .parse(Parser::new(value))
+    }
+}
@@ -0,0 +1,885 @@
+use crate::{
+    Decimal, DictSerializer, Error, ErrorKind, InnerListSerializer, Integer, ItemSerializer, Key,
+    KeyRef, ListSerializer, Output, ParameterSerializer, RefBareItem, SFVResult, StringRef,
+    TokenRef,
+};
+
+use serde::ser::{self, Impossible, Serialize};
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use core::convert::Infallible;
+use core::fmt;
+use core::marker::PhantomData;
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::custom(msg)
+    }
+}
+
+/// Serializes the given value as a structured field value of `Dictionary`
+/// type.
+///
+/// `T` must serialize as a map or struct, whose keys become the dictionary's
+/// keys. See the [crate-level documentation](crate#serde) for how the other
+/// parts of the serde data model are mapped to structured field values.
+///
+/// ```
+/// # use serde::Serialize;
+/// # fn main() -> Result<(), sfv::Error> {
+/// #[derive(Serialize)]
+/// struct Priority {
+///     u: u8,
+///     i: bool,
+/// }
+///
+/// assert_eq!(
+///     sfv::to_string_dictionary(&Priority { u: 5, i: true })?,
+///     "u=5, i",
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_string_dictionary<T: ?Sized + Serialize>(value: &T) -> SFVResult<String> {
+    let mut ser = DictSerializer::new();
+    value.serialize(DictionaryAdapter(&mut ser))?;
+    ser.finish()
+}
+
+/// Serializes the given value as a structured field value of `List` type.
+///
+/// `T` must serialize as a sequence or tuple, whose elements become the list's
+/// members. See the [crate-level documentation](crate#serde) for how the other
+/// parts of the serde data model are mapped to structured field values.
+///
+/// ```
+/// # use std::collections::BTreeMap;
+/// # fn main() -> Result<(), sfv::Error> {
+/// assert_eq!(
+///     sfv::to_string_list(&[(1, BTreeMap::from([("a", true)])), (2, BTreeMap::new())])?,
+///     "1;a, 2",
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_string_list<T: ?Sized + Serialize>(value: &T) -> SFVResult<String> {
+    let mut ser = ListSerializer::new();
+    value.serialize(ListAdapter(&mut ser))?;
+    ser.finish()
+}
+
+/// Serializes the given value as a structured field value of `Item` type.
+///
+/// See the [crate-level documentation](crate#serde) for how the serde data
+/// model is mapped to structured field values.
+///
+/// ```
+/// # use std::collections::BTreeMap;
+/// # fn main() -> Result<(), sfv::Error> {
+/// assert_eq!(sfv::to_string_item(&12.5)?, "12.5");
+/// assert_eq!(
+///     sfv::to_string_item(&("abc", BTreeMap::from([("a", 1)])))?,
+///     r#""abc";a=1"#,
+/// );
+/// assert!(sfv::to_string_item(&u64::MAX).is_err());
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_string_item<T: ?Sized + Serialize>(value: &T) -> SFVResult<String> {
+    match value.serialize(ValueAdapter(ItemSerializer::new()))? {
+        Written::Params(ser) => Ok(ser.finish()),
+        Written::Skipped(_) => Err(Error::new(
+            ErrorKind::TypeMismatch,
+            "serializing None as an item is not allowed",
+        )),
+    }
+}
+
+// A destination for a single bare item or inner list, which is written only
+// once its contents are known. This allows e.g. `None` dictionary members to
+// be omitted entirely.
+trait Sink: Sized {
+    type Buffer: Output;
+    type InnerList: InnerListSink<Buffer = Self::Buffer>;
+
+    fn bare_item(self, value: RefBareItem<'_>) -> ParameterSerializer<Self::Buffer>;
+
+    fn inner_list(self) -> SFVResult<Self::InnerList>;
+}
+
+trait InnerListSink {
+    type Buffer: Output;
+    type Output: Output;
+
+    fn bare_item(&mut self, value: RefBareItem<'_>) -> ParameterSerializer<&mut Self::Output>;
+
+    fn finish(self) -> ParameterSerializer<Self::Buffer>;
+}
+
+impl<'a, W: Output> InnerListSink for InnerListSerializer<'a, W> {
+    type Buffer = &'a mut W;
+    type Output = W;
+
+    fn bare_item(&mut self, value: RefBareItem<'_>) -> ParameterSerializer<&mut W> {
+        InnerListSerializer::bare_item(self, value)
+    }
+
+    fn finish(self) -> ParameterSerializer<&'a mut W> {
+        InnerListSerializer::finish(self)
+    }
+}
+
+// The inner-list type for sinks that do not support inner lists.
+struct NoInnerList<W>(Infallible, PhantomData<W>);
+
+impl<W: Output> InnerListSink for NoInnerList<W> {
+    type Buffer = W;
+    type Output = W;
+
+    fn bare_item(&mut self, _value: RefBareItem<'_>) -> ParameterSerializer<&mut W> {
+        match self.0 {}
+    }
+
+    fn finish(self) -> ParameterSerializer<W> {
+        match self.0 {}
+    }
+}
+
+impl<W: Output> Sink for ItemSerializer<W> {
+    type Buffer = W;
+    type InnerList = NoInnerList<W>;
+
+    fn bare_item(self, value: RefBareItem<'_>) -> ParameterSerializer<W> {
+        ItemSerializer::bare_item(self, value)
+    }
+
+    fn inner_list(self) -> SFVResult<Self::InnerList> {
+        Err(Error::new(
+            ErrorKind::TypeMismatch,
+            "cannot serialize inner list as item",
+        ))
+    }
+}
+
+impl<'a, W: Output> Sink for &'a mut ListSerializer<W> {
+    type Buffer = &'a mut W;
+    type InnerList = InnerListSerializer<'a, W>;
+
+    fn bare_item(self, value: RefBareItem<'_>) -> ParameterSerializer<&'a mut W> {
+        ListSerializer::bare_item(self, value)
+    }
+
+    fn inner_list(self) -> SFVResult<InnerListSerializer<'a, W>> {
+        Ok(ListSerializer::inner_list(self))
+    }
+}
+
+struct DictMember<'a, W> {
+    ser: &'a mut DictSerializer<W>,
+    key: &'a KeyRef,
+}
+
+impl<'a, W: Output> Sink for DictMember<'a, W> {
+    type Buffer = &'a mut W;
+    type InnerList = InnerListSerializer<'a, W>;
+
+    fn bare_item(self, value: RefBareItem<'_>) -> ParameterSerializer<&'a mut W> {
+        self.ser.bare_item(self.key, value)
+    }
+
+    fn inner_list(self) -> SFVResult<InnerListSerializer<'a, W>> {
+        Ok(self.ser.inner_list(self.key))
+    }
+}
+
+struct InnerListMember<'a, L>(&'a mut L);
+
+impl<'a, L: InnerListSink> Sink for InnerListMember<'a, L> {
+    type Buffer = &'a mut L::Output;
+    type InnerList = NoInnerList<&'a mut L::Output>;
+
+    fn bare_item(self, value: RefBareItem<'_>) -> ParameterSerializer<&'a mut L::Output> {
+        self.0.bare_item(value)
+    }
+
+    fn inner_list(self) -> SFVResult<Self::InnerList> {
+        Err(Error::new(
+            ErrorKind::TypeMismatch,
+            "inner lists cannot be nested",
+        ))
+    }
+}
+
+struct Parameter<'a, W> {
+    ser: ParameterSerializer<W>,
+    key: &'a KeyRef,
+}
+
+impl<W: Output> Sink for Parameter<'_, W> {
+    type Buffer = W;
+    type InnerList = NoInnerList<W>;
+
+    fn bare_item(self, value: RefBareItem<'_>) -> ParameterSerializer<W> {
+        self.ser.parameter(self.key, value)
+    }
+
+    fn inner_list(self) -> SFVResult<Self::InnerList> {
+        Err(Error::new(
+            ErrorKind::TypeMismatch,
+            "parameter value must be a bare item",
+        ))
+    }
+}
+
+fn serialize_parameter<W: Output>(
+    ser: ParameterSerializer<W>,
+    key: &KeyRef,
+    value: &(impl ?Sized + Serialize),
+) -> SFVResult<ParameterSerializer<W>> {
+    match value.serialize(ValueAdapter(Parameter { ser, key }))? {
+        Written::Params(ser) => Ok(ser),
+        Written::Skipped(param) => Ok(param.ser),
+    }
+}
+
+// The result of serializing a value into a sink: either a serializer for the
+// value's parameters, or the unused sink if the value was `None`.
+enum Written<S: Sink> {
+    Params(ParameterSerializer<S::Buffer>),
+    Skipped(S),
+}
+
+// Implements the given `Serializer` methods, named without their `serialize_`
+// prefix, by returning a type mismatch error with the given message.
+macro_rules! reject {
+    ($msg:expr; $($method:ident)*) => {
+        $(reject_method!($msg; $method);)*
+    };
+}
+
+macro_rules! reject_method {
+    ($msg:expr; $method:ident($($arg:ident: $ty:ty),*) -> $ret:ty) => {
+        fn $method(self, $($arg: $ty),*) -> SFVResult<$ret> {
+            Err(Error::new(ErrorKind::TypeMismatch, $msg))
+        }
+    };
+    ($msg:expr; bool) => { reject_method!($msg; serialize_bool(_v: bool) -> Self::Ok); };
+    ($msg:expr; i8) => { reject_method!($msg; serialize_i8(_v: i8) -> Self::Ok); };
+    ($msg:expr; i16) => { reject_method!($msg; serialize_i16(_v: i16) -> Self::Ok); };
+    ($msg:expr; i32) => { reject_method!($msg; serialize_i32(_v: i32) -> Self::Ok); };
+    ($msg:expr; i64) => { reject_method!($msg; serialize_i64(_v: i64) -> Self::Ok); };
+    ($msg:expr; u8) => { reject_method!($msg; serialize_u8(_v: u8) -> Self::Ok); };
+    ($msg:expr; u16) => { reject_method!($msg; serialize_u16(_v: u16) -> Self::Ok); };
+    ($msg:expr; u32) => { reject_method!($msg; serialize_u32(_v: u32) -> Self::Ok); };
+    ($msg:expr; u64) => { reject_method!($msg; serialize_u64(_v: u64) -> Self::Ok); };
+    ($msg:expr; f32) => { reject_method!($msg; serialize_f32(_v: f32) -> Self::Ok); };
+    ($msg:expr; f64) => { reject_method!($msg; serialize_f64(_v: f64) -> Self::Ok); };
+    ($msg:expr; char) => { reject_method!($msg; serialize_char(_v: char) -> Self::Ok); };
+    ($msg:expr; str) => { reject_method!($msg; serialize_str(_v: &str) -> Self::Ok); };
+    ($msg:expr; bytes) => { reject_method!($msg; serialize_bytes(_v: &[u8]) -> Self::Ok); };
+    ($msg:expr; none) => { reject_method!($msg; serialize_none() -> Self::Ok); };
+    ($msg:expr; unit) => { reject_method!($msg; serialize_unit() -> Self::Ok); };
+    ($msg:expr; unit_struct) => {
+        reject_method!($msg; serialize_unit_struct(_name: &'static str) -> Self::Ok);
+    };
+    ($msg:expr; unit_variant) => {
+        reject_method!($msg; serialize_unit_variant(
+            _name: &'static str,
+            _variant_index: u32,
+            _variant: &'static str
+        ) -> Self::Ok);
+    };
+    ($msg:expr; seq) => {
+        reject_method!($msg; serialize_seq(_len: Option<usize>) -> Self::SerializeSeq);
+    };
+    ($msg:expr; tuple) => {
+        reject_method!($msg; serialize_tuple(_len: usize) -> Self::SerializeTuple);
+    };
+    ($msg:expr; tuple_struct) => {
+        reject_method!($msg; serialize_tuple_struct(
+            _name: &'static str,
+            _len: usize
+        ) -> Self::SerializeTupleStruct);
+    };
+    ($msg:expr; tuple_variant) => {
+        reject_method!($msg; serialize_tuple_variant(
+            _name: &'static str,
+            _variant_index: u32,
+            _variant: &'static str,
+            _len: usize
+        ) -> Self::SerializeTupleVariant);
+    };
+    ($msg:expr; map) => {
+        reject_method!($msg; serialize_map(_len: Option<usize>) -> Self::SerializeMap);
+    };
+    ($msg:expr; struct) => {
+        reject_method!($msg; serialize_struct(
+            _name: &'static str,
+            _len: usize
+        ) -> Self::SerializeStruct);
+    };
+    ($msg:expr; struct_variant) => {
+        reject_method!($msg; serialize_struct_variant(
+            _name: &'static str,
+            _variant_index: u32,
+            _variant: &'static str,
+            _len: usize
+        ) -> Self::SerializeStructVariant);
+    };
+    ($msg:expr; some) => {
+        fn serialize_some<T: ?Sized + Serialize>(self, _value: &T) -> SFVResult<Self::Ok> {
+            Err(Error::new(ErrorKind::TypeMismatch, $msg))
+        }
+    };
+    ($msg:expr; newtype_variant) => {
+        fn serialize_newtype_variant<T: ?Sized + Serialize>(
+            self,
+            _name: &'static str,
+            _variant_index: u32,
+            _variant: &'static str,
+            _value: &T,
+        ) -> SFVResult<Self::Ok> {
+            Err(Error::new(ErrorKind::TypeMismatch, $msg))
+        }
+    };
+}
+
+// Serializes a bare item or inner list, optionally with parameters.
+struct ValueAdapter<S>(S);
+
+impl<S: Sink> ValueAdapter<S> {
+    fn bare_item<'b>(self, value: impl Into<RefBareItem<'b>>) -> SFVResult<Written<S>> {
+        Ok(Written::Params(self.0.bare_item(value.into())))
+    }
+}
+
+impl<S: Sink> ser::Serializer for ValueAdapter<S> {
+    type Ok = Written<S>;
+    type Error = Error;
+    type SerializeSeq = InnerListAdapter<S>;
+    type SerializeTuple = TupleAdapter<S>;
+    type SerializeTupleStruct = Impossible<Self::Ok, Error>;
+    type SerializeTupleVariant = Impossible<Self::Ok, Error>;
+    type SerializeMap = Impossible<Self::Ok, Error>;
+    type SerializeStruct = Impossible<Self::Ok, Error>;
+    type SerializeStructVariant = Impossible<Self::Ok, Error>;
+
+    reject! {
+        "cannot serialize unit as bare item";
+        unit
+    }
+
+    reject! {
+        "cannot serialize tuple struct";
+        tuple_struct
+    }
+
+    reject! {
+        "cannot serialize map as bare item";
+        map
+    }
+
+    reject! {
+        "cannot serialize struct as bare item";
+        struct
+    }
+
+    reject! {
+        "cannot serialize enum variant with data";
+        newtype_variant tuple_variant struct_variant
+    }
+
+    fn serialize_bool(self, v: bool) -> SFVResult<Self::Ok> {
+        self.bare_item(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> SFVResult<Self::Ok> {
+        self.bare_item(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> SFVResult<Self::Ok> {
+        self.bare_item(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> SFVResult<Self::Ok> {
+        self.bare_item(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> SFVResult<Self::Ok> {
+        self.bare_item(Integer::try_from(v)?)
+    }
+
+    fn serialize_i128(self, v: i128) -> SFVResult<Self::Ok> {
+        self.bare_item(Integer::try_from(v)?)
+    }
+
+    fn serialize_u8(self, v: u8) -> SFVResult<Self::Ok> {
+        self.bare_item(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> SFVResult<Self::Ok> {
+        self.bare_item(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> SFVResult<Self::Ok> {
+        self.bare_item(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> SFVResult<Self::Ok> {
+        self.bare_item(Integer::try_from(v)?)
+    }
+
+    fn serialize_u128(self, v: u128) -> SFVResult<Self::Ok> {
+        self.bare_item(Integer::try_from(v)?)
+    }
+
+    fn serialize_f32(self, v: f32) -> SFVResult<Self::Ok> {
+        self.bare_item(Decimal::try_from(v)?)
+    }
+
+    fn serialize_f64(self, v: f64) -> SFVResult<Self::Ok> {
+        self.bare_item(Decimal::try_from(v)?)
+    }
+
+    fn serialize_char(self, v: char) -> SFVResult<Self::Ok> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> SFVResult<Self::Ok> {
+        self.bare_item(StringRef::from_str(v)?)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> SFVResult<Self::Ok> {
+        self.bare_item(RefBareItem::ByteSequence(v))
+    }
+
+    fn serialize_none(self) -> SFVResult<Self::Ok> {
+        Ok(Written::Skipped(self.0))
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> SFVResult<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> SFVResult<Self::Ok> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> SFVResult<Self::Ok> {
+        self.bare_item(TokenRef::from_str(variant)?)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> SFVResult<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> SFVResult<Self::SerializeSeq> {
+        Ok(InnerListAdapter(self.0.inner_list()?))
+    }
+
+    fn serialize_tuple(self, len: usize) -> SFVResult<Self::SerializeTuple> {
+        if len == 2 {
+            Ok(TupleAdapter::WithParams {
+                sink: Some(self.0),
+                written: None,
+            })
+        } else {
+            self.serialize_seq(Some(len)).map(TupleAdapter::InnerList)
+        }
+    }
+}
+
+struct InnerListAdapter<S: Sink>(S::InnerList);
+
+impl<S: Sink> ser::SerializeSeq for InnerListAdapter<S> {
+    type Ok = Written<S>;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> SFVResult<()> {
+        value.serialize(ValueAdapter(InnerListMember(&mut self.0)))?;
+        Ok(())
+    }
+
+    fn end(self) -> SFVResult<Self::Ok> {
+        Ok(Written::Params(self.0.finish()))
+    }
+}
+
+enum TupleAdapter<S: Sink> {
+    // A `(value, parameters)` pair.
+    WithParams {
+        sink: Option<S>,
+        written: Option<Written<S>>,
+    },
+    InnerList(InnerListAdapter<S>),
+}
+
+impl<S: Sink> ser::SerializeTuple for TupleAdapter<S> {
+    type Ok = Written<S>;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> SFVResult<()> {
+        match self {
+            Self::WithParams { sink, written } => {
+                *written = match (sink.take(), written.take()) {
+                    (Some(sink), _) => Some(value.serialize(ValueAdapter(sink))?),
+                    (None, Some(Written::Params(ser))) => {
+                        Some(Written::Params(value.serialize(ParametersAdapter(ser))?))
+                    }
+                    // The value was `None`, so its parameters are omitted too.
+                    (None, written) => written,
+                };
+                Ok(())
+            }
+            Self::InnerList(inner_list) => ser::SerializeSeq::serialize_element(inner_list, value),
+        }
+    }
+
+    fn end(self) -> SFVResult<Self::Ok> {
+        match self {
+            Self::WithParams {
+                written: Some(written),
+                ..
+            } => Ok(written),
+            Self::WithParams { .. } => Err(Error::custom("expected (value, parameters)")),
+            Self::InnerList(inner_list) => ser::SerializeSeq::end(inner_list),
+        }
+    }
+}
+
+// Serializes a map or struct as parameters.
+struct ParametersAdapter<W>(ParameterSerializer<W>);
+
+impl<W: Output> ser::Serializer for ParametersAdapter<W> {
+    type Ok = ParameterSerializer<W>;
+    type Error = Error;
+    type SerializeSeq = Impossible<Self::Ok, Error>;
+    type SerializeTuple = Impossible<Self::Ok, Error>;
+    type SerializeTupleStruct = Impossible<Self::Ok, Error>;
+    type SerializeTupleVariant = Impossible<Self::Ok, Error>;
+    type SerializeMap = ParametersMapAdapter<W>;
+    type SerializeStruct = ParametersMapAdapter<W>;
+    type SerializeStructVariant = Impossible<Self::Ok, Error>;
+
+    reject! {
+        "parameters must be a map or struct";
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str bytes unit_struct unit_variant
+        newtype_variant seq tuple tuple_struct tuple_variant struct_variant
+    }
+
+    fn serialize_none(self) -> SFVResult<Self::Ok> {
+        Ok(self.0)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> SFVResult<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> SFVResult<Self::Ok> {
+        Ok(self.0)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> SFVResult<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> SFVResult<Self::SerializeMap> {
+        Ok(ParametersMapAdapter {
+            ser: Some(self.0),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> SFVResult<Self::SerializeStruct> {
+        self.serialize_map(None)
+    }
+}
+
+struct ParametersMapAdapter<W> {
+    // `None` once serializing a parameter has failed.
+    ser: Option<ParameterSerializer<W>>,
+    key: Option<Key>,
+}
+
+impl<W: Output> ParametersMapAdapter<W> {
+    fn parameter(&mut self, key: &KeyRef, value: &(impl ?Sized + Serialize)) -> SFVResult<()> {
+        let ser = self.ser.take().ok_or_else(failed_parameter)?;
+        self.ser = Some(serialize_parameter(ser, key, value)?);
+        Ok(())
+    }
+}
+
+fn failed_parameter() -> Error {
+    Error::custom("cannot serialize parameters after an error")
+}
+
+fn missing_key() -> Error {
+    Error::custom("expected key before value")
+}
+
+impl<W: Output> ser::SerializeMap for ParametersMapAdapter<W> {
+    type Ok = ParameterSerializer<W>;
+    type Error = Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> SFVResult<()> {
+        self.key = Some(key.serialize(KeyAdapter)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> SFVResult<()> {
+        let key = self.key.take().ok_or_else(missing_key)?;
+        self.parameter(&key, value)
+    }
+
+    fn end(self) -> SFVResult<Self::Ok> {
+        self.ser.ok_or_else(failed_parameter)
+    }
+}
+
+impl<W: Output> ser::SerializeStruct for ParametersMapAdapter<W> {
+    type Ok = ParameterSerializer<W>;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> SFVResult<()> {
+        self.parameter(KeyRef::from_str(key)?, value)
+    }
+
+    fn end(self) -> SFVResult<Self::Ok> {
+        self.ser.ok_or_else(failed_parameter)
+    }
+}
+
+// Serializes a map or struct as a dictionary.
+struct DictionaryAdapter<'a, W>(&'a mut DictSerializer<W>);
+
+impl<'a, W: Output> ser::Serializer for DictionaryAdapter<'a, W> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = DictionaryMapAdapter<'a, W>;
+    type SerializeStruct = DictionaryMapAdapter<'a, W>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    reject! {
+        "dictionary must be a map or struct";
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str bytes unit_struct none unit unit_variant
+        newtype_variant seq tuple tuple_struct tuple_variant struct_variant
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> SFVResult<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> SFVResult<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> SFVResult<Self::SerializeMap> {
+        Ok(DictionaryMapAdapter {
+            ser: self.0,
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> SFVResult<Self::SerializeStruct> {
+        self.serialize_map(None)
+    }
+}
+
+struct DictionaryMapAdapter<'a, W> {
+    ser: &'a mut DictSerializer<W>,
+    key: Option<Key>,
+}
+
+impl<W: Output> DictionaryMapAdapter<'_, W> {
+    fn member(&mut self, key: &KeyRef, value: &(impl ?Sized + Serialize)) -> SFVResult<()> {
+        value.serialize(ValueAdapter(DictMember {
+            ser: &mut *self.ser,
+            key,
+        }))?;
+        Ok(())
+    }
+}
+
+impl<W: Output> ser::SerializeMap for DictionaryMapAdapter<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> SFVResult<()> {
+        self.key = Some(key.serialize(KeyAdapter)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> SFVResult<()> {
+        let key = self.key.take().ok_or_else(missing_key)?;
+        self.member(&key, value)
+    }
+
+    fn end(self) -> SFVResult<()> {
+        Ok(())
+    }
+}
+
+impl<W: Output> ser::SerializeStruct for DictionaryMapAdapter<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> SFVResult<()> {
+        self.member(KeyRef::from_str(key)?, value)
+    }
+
+    fn end(self) -> SFVResult<()> {
+        Ok(())
+    }
+}
+
+// Serializes a sequence or tuple as a list.
+struct ListAdapter<'a, W>(&'a mut ListSerializer<W>);
+
+impl<'a, W: Output> ser::Serializer for ListAdapter<'a, W> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = Impossible<(), Error>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    reject! {
+        "list must be a sequence or tuple";
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str bytes unit_struct none unit unit_variant
+        newtype_variant tuple_struct tuple_variant map struct struct_variant
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> SFVResult<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> SFVResult<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> SFVResult<Self> {
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> SFVResult<Self> {
+        Ok(self)
+    }
+}
+
+impl<W: Output> ser::SerializeSeq for ListAdapter<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> SFVResult<()> {
+        value.serialize(ValueAdapter(&mut *self.0))?;
+        Ok(())
+    }
+
+    fn end(self) -> SFVResult<()> {
+        Ok(())
+    }
+}
+
+impl<W: Output> ser::SerializeTuple for ListAdapter<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> SFVResult<()> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> SFVResult<()> {
+        Ok(())
+    }
+}
+
+// Serializes a string as a dictionary or parameter key.
+struct KeyAdapter;
+
+impl ser::Serializer for KeyAdapter {
+    type Ok = Key;
+    type Error = Error;
+    type SerializeSeq = Impossible<Key, Error>;
+    type SerializeTuple = Impossible<Key, Error>;
+    type SerializeTupleStruct = Impossible<Key, Error>;
+    type SerializeTupleVariant = Impossible<Key, Error>;
+    type SerializeMap = Impossible<Key, Error>;
+    type SerializeStruct = Impossible<Key, Error>;
+    type SerializeStructVariant = Impossible<Key, Error>;
+
+    reject! {
+        "key must be a string";
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 bytes unit_struct none some unit newtype_variant
+        seq tuple tuple_struct tuple_variant map struct struct_variant
+    }
+
+    fn serialize_char(self, v: char) -> SFVResult<Key> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> SFVResult<Key> {
+        KeyRef::from_str(v).map(ToOwned::to_owned)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> SFVResult<Key> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> SFVResult<Key> {
+        value.serialize(self)
+    }
+}
@@ -1,10 +1,14 @@
+use crate::output::{Adapter, Output};
 use crate::utils;
 use crate::{Date, Decimal, Integer, KeyRef, RefBareItem, StringRef, TokenRef};
-use std::fmt::Write as _;
 
 #[cfg(feature = "parsed-types")]
 use crate::{Dictionary, Item, List, SFVResult};
 
+#[cfg(feature = "parsed-types")]
+use alloc::string::String;
+use core::fmt::Write as _;
+
 /// Serializes a structured field value into a string.
 ///
 /// Note: The serialization conforms to [RFC 9651], meaning that
@@ -33,6 +37,28 @@ pub trait SerializeValue {
     /// # }
     /// ```
     fn serialize_value(&self) -> SFVResult<String>;
+
+    /// Serializes a structured field value into an [`http::HeaderValue`].
+    ///
+    /// The serialized string is not copied, but it is still checked by
+    /// [`http::HeaderValue`]. This check always succeeds, since serialized
+    /// values only contain visible ASCII characters and spaces, so this only
+    /// fails for the same reasons as [`SerializeValue::serialize_value`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use sfv::{Parser, SerializeValue};
+    /// # fn main() -> Result<(), sfv::Error> {
+    /// let dict = Parser::new("a=1,  b").parse_dictionary()?;
+    ///
+    /// assert_eq!(dict.serialize_header_value()?, "a=1, b");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "http")]
+    fn serialize_header_value(&self) -> SFVResult<http::HeaderValue> {
+        self.serialize_value().map(crate::headers::to_header_value)
+    }
 }
 
 #[cfg(feature = "parsed-types")]
@@ -66,7 +92,10 @@ impl SerializeValue for Item {
 pub(crate) struct Serializer;
 
 impl Serializer {
-    pub(crate) fn serialize_bare_item<'b>(value: impl Into<RefBareItem<'b>>, output: &mut String) {
+    pub(crate) fn serialize_bare_item<'b>(
+        value: impl Into<RefBareItem<'b>>,
+        output: &mut impl Output,
+    ) {
         // https://httpwg.org/specs/rfc9651.html#ser-bare-item
 
         match value.into() {
@@ -84,7 +113,7 @@ impl Serializer {
     pub(crate) fn serialize_parameter<'b>(
         name: &KeyRef,
         value: impl Into<RefBareItem<'b>>,
-        output: &mut String,
+        output: &mut impl Output,
     ) {
         // https://httpwg.org/specs/rfc9651.html#ser-params
         output.push(';');
@@ -97,25 +126,25 @@ impl Serializer {
         }
     }
 
-    pub(crate) fn serialize_key(input_key: &KeyRef, output: &mut String) {
+    pub(crate) fn serialize_key(input_key: &KeyRef, output: &mut impl Output) {
         // https://httpwg.org/specs/rfc9651.html#ser-key
 
         output.push_str(input_key.as_str());
     }
 
-    pub(crate) fn serialize_integer(value: Integer, output: &mut String) {
+    pub(crate) fn serialize_integer(value: Integer, output: &mut impl Output) {
         //https://httpwg.org/specs/rfc9651.html#ser-integer
 
-        write!(output, "{}", value).unwrap();
+        write!(Adapter(output), "{}", value).unwrap();
     }
 
-    pub(crate) fn serialize_decimal(value: Decimal, output: &mut String) {
+    pub(crate) fn serialize_decimal(value: Decimal, output: &mut impl Output) {
         // https://httpwg.org/specs/rfc9651.html#ser-decimal
 
-        write!(output, "{}", value).unwrap();
+        write!(Adapter(output), "{}", value).unwrap();
     }
 
-    pub(crate) fn serialize_string(value: &StringRef, output: &mut String) {
+    pub(crate) fn serialize_string(value: &StringRef, output: &mut impl Output) {
         // https://httpwg.org/specs/rfc9651.html#ser-string
 
         output.push('"');
@@ -128,33 +157,39 @@ impl Serializer {
         output.push('"');
     }
 
-    pub(crate) fn serialize_token(value: &TokenRef, output: &mut String) {
+    pub(crate) fn serialize_token(value: &TokenRef, output: &mut impl Output) {
         // https://httpwg.org/specs/rfc9651.html#ser-token
 
         output.push_str(value.as_str());
     }
 
-    pub(crate) fn serialize_byte_sequence(value: &[u8], output: &mut String) {
+    pub(crate) fn serialize_byte_sequence(value: &[u8], output: &mut impl Output) {
         // https://httpwg.org/specs/rfc9651.html#ser-binary
 
         output.push(':');
-        base64::Engine::encode_string(&utils::BASE64, value, output);
+        // Encode in chunks whose length is a multiple of 3, so that padding
+        // can only occur at the end of the last one.
+        let mut buf = [0; 64];
+        for chunk in value.chunks(48) {
+            let len = base64::Engine::encode_slice(&utils::BASE64, chunk, &mut buf).unwrap();
+            output.push_str(core::str::from_utf8(&buf[..len]).unwrap());
+        }
         output.push(':');
     }
 
-    pub(crate) fn serialize_bool(value: bool, output: &mut String) {
+    pub(crate) fn serialize_bool(value: bool, output: &mut impl Output) {
         // https://httpwg.org/specs/rfc9651.html#ser-boolean
 
         output.push_str(if value { "?1" } else { "?0" });
     }
 
-    pub(crate) fn serialize_date(value: Date, output: &mut String) {
+    pub(crate) fn serialize_date(value: Date, output: &mut impl Output) {
         // https://httpwg.org/specs/rfc9651.html#ser-date
 
-        write!(output, "{}", value).unwrap();
+        write!(Adapter(output), "{}", value).unwrap();
     }
 
-    pub(crate) fn serialize_display_string(value: &str, output: &mut String) {
+    pub(crate) fn serialize_display_string(value: &str, output: &mut impl Output) {
         // https://httpwg.org/specs/rfc9651.html#ser-display
 
         output.push_str(r#"%""#);
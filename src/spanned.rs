@@ -0,0 +1,425 @@
+/*!
+Contains types for structured field values annotated with the ranges of bytes
+in the input that they were parsed from.
+
+These are produced by [`Parser::parse_item_spanned`],
+[`Parser::parse_list_spanned`], and [`Parser::parse_dictionary_spanned`], and
+are useful for highlighting parts of a field value or for rewriting them in
+place:
+
+```
+# use sfv::Parser;
+# fn main() -> Result<(), sfv::Error> {
+let input = "a=1;x=2, b=(c d)";
+let dict = Parser::new(input).parse_dictionary_spanned()?;
+
+assert_eq!(&input[dict[1].span.clone()], "b=(c d)");
+
+let mut output = input.to_owned();
+output.replace_range(dict[0].key.span.clone(), "z");
+assert_eq!(output, "z=1;x=2, b=(c d)");
+# Ok(())
+# }
+```
+*/
+
+use alloc::borrow::ToOwned;
+use alloc::vec::Vec;
+use core::cell::Cell;
+use core::convert::Infallible;
+use core::ops::Range;
+
+use crate::parser::{parse_comma_separated, parse_dictionary_member, parse_item, SeenKeys};
+use crate::utils;
+use crate::visitor::*;
+use crate::{BareItem, BareItemFromInput, Key, KeyRef, Parser, SFVResult};
+
+/// A value together with the range of bytes in the input that it was parsed
+/// from.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Spanned<T> {
+    /// The value.
+    pub value: T,
+    /// The range of bytes in the input that the value was parsed from.
+    ///
+    /// This is empty for a boolean `true` value that is implied by the absence
+    /// of an explicit one, and is located at the end of the associated key.
+    pub span: Range<usize>,
+}
+
+/// An item with spans.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Item {
+    /// The item's value.
+    pub bare_item: Spanned<BareItem>,
+    /// The item's parameters, which can be empty.
+    pub params: Parameters,
+    /// The range of bytes in the input that the item, including its
+    /// parameters, was parsed from.
+    pub span: Range<usize>,
+}
+
+/// An inner list with spans.
+#[derive(Debug, PartialEq, Clone)]
+pub struct InnerList {
+    /// The inner list's items, which can be empty.
+    pub items: Vec<Item>,
+    /// The inner list's parameters, which can be empty.
+    pub params: Parameters,
+    /// The range of bytes in the input that the inner list, including its
+    /// parameters, was parsed from.
+    pub span: Range<usize>,
+}
+
+/// A member of a list or dictionary with spans.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ListEntry {
+    /// An item.
+    Item(Item),
+    /// An inner list.
+    InnerList(InnerList),
+}
+
+impl ListEntry {
+    /// Returns the range of bytes in the input that the entry, including its
+    /// parameters, was parsed from.
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            Self::Item(item) => item.span.clone(),
+            Self::InnerList(inner_list) => inner_list.span.clone(),
+        }
+    }
+}
+
+/// A parameter with spans.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Parameter {
+    /// The parameter's key.
+    pub key: Spanned<Key>,
+    /// The parameter's value.
+    pub value: Spanned<BareItem>,
+    /// The range of bytes in the input that the parameter was parsed from,
+    /// starting with its leading `;`.
+    pub span: Range<usize>,
+}
+
+/// The parameters of an [`Item`] or [`InnerList`], in input order.
+///
+/// Unlike `sfv::Parameters`, this retains parameters whose keys are
+/// duplicated, unless the parser's
+/// [`DuplicateKeyPolicy`][crate::DuplicateKeyPolicy] discards them.
+pub type Parameters = Vec<Parameter>;
+
+/// A list with spans.
+pub type List = Vec<ListEntry>;
+
+/// A dictionary member with spans.
+#[derive(Debug, PartialEq, Clone)]
+pub struct DictionaryMember {
+    /// The member's key.
+    pub key: Spanned<Key>,
+    /// The member's value.
+    pub value: ListEntry,
+    /// The range of bytes in the input that the member, including its key,
+    /// was parsed from.
+    pub span: Range<usize>,
+}
+
+/// The members of a dictionary, in input order.
+///
+/// Unlike `sfv::Dictionary`, this retains members whose keys are
+/// duplicated, unless the parser's
+/// [`DuplicateKeyPolicy`][crate::DuplicateKeyPolicy] discards them.
+pub type Dictionary = Vec<DictionaryMember>;
+
+// Collects spanned values from the calls made to visitors by the parser, which
+// records its index in `position` before each call.
+#[derive(Clone, Copy)]
+struct Context<'c> {
+    input: &'c [u8],
+    position: &'c Cell<usize>,
+}
+
+impl Context<'_> {
+    fn index(&self) -> usize {
+        self.position.get()
+    }
+
+    // Returns the span of a key, which is borrowed from the input.
+    fn key_span(&self, key: &KeyRef) -> Range<usize> {
+        utils::span_of(self.input, key.as_str())
+    }
+}
+
+fn empty_item(start: usize) -> Item {
+    Item {
+        bare_item: Spanned {
+            value: BareItem::Boolean(false),
+            span: start..start,
+        },
+        params: Parameters::new(),
+        span: start..start,
+    }
+}
+
+struct ParametersCollector<'c, 'v> {
+    cx: Context<'c>,
+    params: &'v mut Parameters,
+    // The end of the preceding bare item, inner list, or parameter, which is
+    // where the next parameter's `;` is.
+    prev_end: usize,
+    // The end of the span of the item or inner list being parameterized.
+    end: &'v mut usize,
+}
+
+impl<'a> ParameterVisitor<'a> for ParametersCollector<'_, '_> {
+    type Error = Infallible;
+
+    fn parameter(
+        &mut self,
+        key: &'a KeyRef,
+        value: BareItemFromInput<'a>,
+    ) -> Result<(), Infallible> {
+        let end = self.cx.index();
+        let key_span = self.cx.key_span(key);
+        // An implied `true` is empty, and otherwise the value follows an `=`.
+        let value_span = if key_span.end == end {
+            end..end
+        } else {
+            key_span.end + 1..end
+        };
+        self.params.push(Parameter {
+            key: Spanned {
+                value: key.to_owned(),
+                span: key_span,
+            },
+            value: Spanned {
+                value: value.into(),
+                span: value_span,
+            },
+            span: self.prev_end..end,
+        });
+        self.prev_end = end;
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), Infallible> {
+        *self.end = self.cx.index();
+        Ok(())
+    }
+}
+
+struct ItemCollector<'c, 'v> {
+    cx: Context<'c>,
+    item: &'v mut Item,
+}
+
+impl<'a> ItemVisitor<'a> for ItemCollector<'_, '_> {
+    type Error = Infallible;
+
+    fn bare_item(
+        self,
+        bare_item: BareItemFromInput<'a>,
+    ) -> Result<impl ParameterVisitor<'a>, Infallible> {
+        let end = self.cx.index();
+        self.item.bare_item = Spanned {
+            value: bare_item.into(),
+            span: self.item.span.start..end,
+        };
+        Ok(ParametersCollector {
+            cx: self.cx,
+            params: &mut self.item.params,
+            prev_end: end,
+            end: &mut self.item.span.end,
+        })
+    }
+}
+
+struct InnerListCollector<'c, 'v> {
+    cx: Context<'c>,
+    inner_list: &'v mut InnerList,
+}
+
+impl<'a> InnerListVisitor<'a> for InnerListCollector<'_, '_> {
+    type Error = Infallible;
+
+    fn item(&mut self) -> Result<impl ItemVisitor<'a>, Infallible> {
+        self.inner_list.items.push(empty_item(self.cx.index()));
+        match self.inner_list.items.last_mut() {
+            Some(item) => Ok(ItemCollector { cx: self.cx, item }),
+            None => unreachable!(),
+        }
+    }
+
+    fn finish(self) -> Result<impl ParameterVisitor<'a>, Infallible> {
+        Ok(ParametersCollector {
+            cx: self.cx,
+            params: &mut self.inner_list.params,
+            prev_end: self.cx.index(),
+            end: &mut self.inner_list.span.end,
+        })
+    }
+}
+
+struct EntryCollector<'c, 'v> {
+    cx: Context<'c>,
+    entry: &'v mut ListEntry,
+    // The index at which the entry starts, or, for a dictionary member, the
+    // end of its key.
+    start: usize,
+    is_member: bool,
+}
+
+impl<'a> ItemVisitor<'a> for EntryCollector<'_, '_> {
+    type Error = Infallible;
+
+    fn bare_item(
+        self,
+        bare_item: BareItemFromInput<'a>,
+    ) -> Result<impl ParameterVisitor<'a>, Infallible> {
+        // A dictionary member's value follows an `=`, unless it is an implied
+        // `true`, which is empty.
+        let start = if self.is_member && self.cx.index() != self.start {
+            self.start + 1
+        } else {
+            self.start
+        };
+        *self.entry = ListEntry::Item(empty_item(start));
+        match self.entry {
+            ListEntry::Item(item) => ItemCollector { cx: self.cx, item }.bare_item(bare_item),
+            ListEntry::InnerList(_) => unreachable!(),
+        }
+    }
+}
+
+impl<'a> EntryVisitor<'a> for EntryCollector<'_, '_> {
+    fn inner_list(self) -> Result<impl InnerListVisitor<'a>, Infallible> {
+        let start = self.cx.index();
+        *self.entry = ListEntry::InnerList(InnerList {
+            items: Vec::new(),
+            params: Parameters::new(),
+            span: start..start,
+        });
+        match self.entry {
+            ListEntry::InnerList(inner_list) => Ok(InnerListCollector {
+                cx: self.cx,
+                inner_list,
+            }),
+            ListEntry::Item(_) => unreachable!(),
+        }
+    }
+}
+
+struct DictionaryCollector<'c, 'v> {
+    cx: Context<'c>,
+    dict: &'v mut Dictionary,
+}
+
+impl<'a> DictionaryVisitor<'a> for DictionaryCollector<'_, '_> {
+    type Error = Infallible;
+
+    fn entry(&mut self, key: &'a KeyRef) -> Result<impl EntryVisitor<'a>, Infallible> {
+        let key_span = self.cx.key_span(key);
+        self.dict.push(DictionaryMember {
+            key: Spanned {
+                value: key.to_owned(),
+                span: key_span.clone(),
+            },
+            value: ListEntry::Item(empty_item(key_span.end)),
+            span: key_span.clone(),
+        });
+        match self.dict.last_mut() {
+            Some(member) => Ok(EntryCollector {
+                cx: self.cx,
+                entry: &mut member.value,
+                start: key_span.end,
+                is_member: true,
+            }),
+            None => unreachable!(),
+        }
+    }
+}
+
+impl Parser<'_> {
+    /// Parses input into a structured field value of `Item` type, recording
+    /// the span of each node.
+    pub fn parse_item_spanned(self) -> SFVResult<Item> {
+        let position = Cell::new(0);
+        let cx = Context {
+            input: self.input,
+            position: &position,
+        };
+        self.with_position(&position).parse(|parser| {
+            let mut item = empty_item(parser.index);
+            parse_item(
+                parser,
+                ItemCollector {
+                    cx,
+                    item: &mut item,
+                },
+            )?;
+            Ok(item)
+        })
+    }
+
+    /// Parses input into a structured field value of `List` type, recording
+    /// the span of each node.
+    pub fn parse_list_spanned(self) -> SFVResult<List> {
+        let position = Cell::new(0);
+        let cx = Context {
+            input: self.input,
+            position: &position,
+        };
+        self.with_position(&position).parse(|parser| {
+            let mut list = List::new();
+            parse_comma_separated(parser, |parser| {
+                let start = parser.index;
+                list.push(ListEntry::Item(empty_item(start)));
+                match list.last_mut() {
+                    Some(entry) => parser.parse_list_entry(EntryCollector {
+                        cx,
+                        entry,
+                        start,
+                        is_member: false,
+                    }),
+                    None => unreachable!(),
+                }
+            })?;
+            Ok(list)
+        })
+    }
+
+    /// Parses input into a structured field value of `Dictionary` type,
+    /// recording the span of each node.
+    pub fn parse_dictionary_spanned(self) -> SFVResult<Dictionary> {
+        let position = Cell::new(0);
+        let cx = Context {
+            input: self.input,
+            position: &position,
+        };
+        self.with_position(&position).parse(|parser| {
+            let mut dict = Dictionary::new();
+            let mut seen = SeenKeys::new();
+            parse_comma_separated(parser, |parser| {
+                let len = dict.len();
+                parse_dictionary_member(
+                    parser,
+                    &mut DictionaryCollector {
+                        cx,
+                        dict: &mut dict,
+                    },
+                    &mut seen,
+                )?;
+                // The member is not collected if its key is a duplicate that
+                // the parser's policy discards.
+                if dict.len() > len {
+                    if let Some(member) = dict.last_mut() {
+                        member.span.end = parser.index;
+                    }
+                }
+                Ok(())
+            })?;
+            Ok(dict)
+        })
+    }
+}
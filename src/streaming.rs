@@ -0,0 +1,340 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::parser::{parse_dictionary_member, parse_item, SeenKeys};
+use crate::visitor::*;
+use crate::{
+    DuplicateKeyPolicy, Error, ErrorKind, KeyRef, Parser, ParserLimits, SFVResult, Version,
+};
+
+/// Exposes methods for parsing a structured field value whose input arrives
+/// in chunks.
+///
+/// Chunks are passed to [`StreamingParser::feed`] as they arrive. After each
+/// one, calling a parse method emits visitor callbacks for the list or
+/// dictionary members that are complete so far, and returns an error of kind
+/// [`ErrorKind::NeedMoreInput`] if the input is a valid prefix of a structured
+/// field value, or any other error if it is not. A member is complete once a
+/// character that cannot continue it has been received, or once the input has
+/// been marked as complete with [`StreamingParser::finish`]. After that, the
+/// parse methods behave like those of [`Parser`] on the whole input.
+///
+/// Each member is emitted exactly once, so the same visitor should be passed
+/// to every call of the same parse method. Items have no members, and are only
+/// emitted once the input is complete.
+///
+/// The input is buffered in full, so that errors report byte indices in the
+/// whole input, and so that visitors can borrow from it during each call.
+#[cfg_attr(
+    feature = "parsed-types",
+    doc = r##"
+
+```
+# use sfv::{Dictionary, ErrorKind, StreamingParser};
+# fn main() -> Result<(), sfv::Error> {
+let mut parser = StreamingParser::new();
+let mut dict = Dictionary::default();
+
+parser.feed("a=1, b=(1 ");
+let err = parser.parse_dictionary_with_visitor(&mut dict).unwrap_err();
+assert_eq!(err.kind(), ErrorKind::NeedMoreInput);
+assert_eq!(dict.len(), 1);
+
+parser.feed("2), c");
+let err = parser.parse_dictionary_with_visitor(&mut dict).unwrap_err();
+assert_eq!(err.kind(), ErrorKind::NeedMoreInput);
+assert_eq!(dict.len(), 2);
+
+parser.finish();
+parser.parse_dictionary_with_visitor(&mut dict)?;
+assert_eq!(dict.len(), 3);
+
+let mut parser = StreamingParser::new();
+parser.feed("a=1, b=?2");
+let err = parser.parse_dictionary_with_visitor(&mut Dictionary::default()).unwrap_err();
+assert_eq!(err.kind(), ErrorKind::InvalidBoolean);
+# Ok(())
+# }
+```
+"##
+)]
+pub struct StreamingParser {
+    buffer: Vec<u8>,
+    cursor: Cursor,
+    finished: bool,
+    version: Version,
+    duplicate_keys: DuplicateKeyPolicy,
+    limits: ParserLimits,
+    // The ranges of the dictionary keys seen so far. Only populated for
+    // policies other than `DuplicateKeyPolicy::LastWins`.
+    seen: Vec<Range<usize>>,
+}
+
+// The position at which parsing of a list or dictionary resumes.
+#[derive(Default)]
+struct Cursor {
+    index: usize,
+    // The index of the last comma, if any.
+    comma_index: Option<usize>,
+    // Whether a member has been parsed since the last comma.
+    after_member: bool,
+    // The number of members parsed so far.
+    members: usize,
+}
+
+impl Default for StreamingParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StreamingParser {
+    /// Creates a streaming parser without input, with [`Version::Rfc9651`].
+    pub fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            cursor: Cursor::default(),
+            finished: false,
+            version: Version::Rfc9651,
+            duplicate_keys: DuplicateKeyPolicy::LastWins,
+            limits: ParserLimits::default(),
+            seen: Vec::new(),
+        }
+    }
+
+    /// Sets the parser's version and returns it.
+    pub fn with_version(mut self, version: Version) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Sets the parser's policy for duplicate keys and returns it.
+    ///
+    /// The default is [`DuplicateKeyPolicy::LastWins`].
+    pub fn with_duplicate_key_policy(mut self, policy: DuplicateKeyPolicy) -> Self {
+        self.duplicate_keys = policy;
+        self
+    }
+
+    /// Sets the parser's limits and returns it.
+    ///
+    /// The maximum input length applies to the input received so far, so
+    /// input that is too long is rejected as soon as it has been received.
+    ///
+    /// The default is no limits.
+    pub fn with_limits(mut self, limits: ParserLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Appends a chunk to the input.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`StreamingParser::finish`] has been called.
+    pub fn feed(&mut self, chunk: impl AsRef<[u8]>) {
+        assert!(!self.finished, "input fed to a finished StreamingParser");
+        self.buffer.extend_from_slice(chunk.as_ref());
+    }
+
+    /// Marks the input as complete.
+    pub fn finish(&mut self) {
+        self.finished = true;
+    }
+
+    /// Returns whether [`StreamingParser::finish`] has been called.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn parser<'a>(
+        input: &'a [u8],
+        version: Version,
+        duplicate_keys: DuplicateKeyPolicy,
+        limits: ParserLimits,
+    ) -> Parser<'a> {
+        Parser::new(input)
+            .with_version(version)
+            .with_duplicate_key_policy(duplicate_keys)
+            .with_limits(limits)
+    }
+
+    /// Parses the input received so far as a structured field value of `Item`
+    /// type, using the given visitor.
+    ///
+    /// Until the input is complete, this only validates the input and returns
+    /// an error.
+    pub fn parse_item_with_visitor<'a>(
+        &'a mut self,
+        visitor: impl ItemVisitor<'a>,
+    ) -> SFVResult<()> {
+        let parser = Self::parser(&self.buffer, self.version, self.duplicate_keys, self.limits);
+        if self.finished {
+            return parser.parse_item_with_visitor(visitor);
+        }
+
+        parser.check_input_len()?;
+        let mut parser = parser.lookahead();
+
+        // https://httpwg.org/specs/rfc9651.html#text-parse
+        parser.consume_sp_chars();
+        let result = parse_item(&mut parser, Ignored);
+        if parser.peek().is_some() {
+            result?;
+            parser.consume_sp_chars();
+            if parser.peek().is_some() {
+                return parser.error(
+                    ErrorKind::TrailingCharacters,
+                    "trailing characters after parsed value",
+                );
+            }
+        }
+        Err(need_more_input(&parser))
+    }
+
+    /// Parses the input received so far as a structured field value of `List`
+    /// type, using the given visitor for the members that are complete.
+    pub fn parse_list_with_visitor<'a>(
+        &'a mut self,
+        visitor: &mut (impl ?Sized + ListVisitor<'a>),
+    ) -> SFVResult<()> {
+        let parser = Self::parser(&self.buffer, self.version, self.duplicate_keys, self.limits);
+        parse_members(parser, &mut self.cursor, self.finished, |parser, emit| {
+            if emit {
+                parser.parse_list_entry(visitor.entry().map_err(Error::custom)?)
+            } else {
+                parser.parse_list_entry(Ignored)
+            }
+        })
+    }
+
+    /// Parses the input received so far as a structured field value of
+    /// `Dictionary` type, using the given visitor for the members that are
+    /// complete.
+    pub fn parse_dictionary_with_visitor<'a>(
+        &'a mut self,
+        visitor: &mut (impl ?Sized + DictionaryVisitor<'a>),
+    ) -> SFVResult<()> {
+        let input = &self.buffer[..];
+        let parser = Self::parser(input, self.version, self.duplicate_keys, self.limits);
+
+        let mut seen: SeenKeys<'a> = self
+            .seen
+            .iter()
+            .map(|range| {
+                // TODO: The UTF-8 validation is redundant with the key's earlier validation, but
+                // its removal is only possible with unsafe code.
+                let key = core::str::from_utf8(&input[range.clone()]).unwrap();
+                (KeyRef::from_validated_str(key), range.start)
+            })
+            .collect();
+
+        let result = parse_members(parser, &mut self.cursor, self.finished, |parser, emit| {
+            if emit {
+                parse_dictionary_member(parser, visitor, &mut seen)
+            } else {
+                // Forget the key once the member has been checked, since it
+                // is parsed again when emitted.
+                let seen_len = seen.len();
+                let result = parse_dictionary_member(parser, &mut Ignored, &mut seen);
+                seen.truncate(seen_len);
+                result
+            }
+        });
+
+        self.seen = seen
+            .iter()
+            .map(|(key, index)| index..index + key.as_str().len())
+            .collect();
+        result
+    }
+}
+
+fn need_more_input(parser: &Parser<'_>) -> Error {
+    Error::with_index(
+        ErrorKind::NeedMoreInput,
+        "incomplete input",
+        parser.input.len(),
+    )
+}
+
+// Parses the members of a list or dictionary that are complete, starting at
+// the cursor and advancing it past them.
+//
+// Unless the input is complete, each member is first parsed without emitting
+// it. A member is complete, and parse errors in it are final, if parsing
+// stopped before the end of the input, since the parser then never depended
+// on what follows.
+fn parse_members<'a>(
+    mut parser: Parser<'a>,
+    cursor: &mut Cursor,
+    finished: bool,
+    mut parse_member: impl FnMut(&mut Parser<'a>, bool) -> SFVResult<()>,
+) -> SFVResult<()> {
+    parser.check_input_len()?;
+
+    // https://httpwg.org/specs/rfc9651.html#parse-list
+    parser.index = cursor.index;
+
+    loop {
+        if cursor.after_member {
+            parser.consume_ows_chars();
+            cursor.index = parser.index;
+
+            match parser.peek() {
+                None if finished => return Ok(()),
+                None => return Err(need_more_input(&parser)),
+                Some(b',') => {}
+                Some(_) => {
+                    return parser
+                        .error(ErrorKind::ExpectedComma, "trailing characters after member")
+                }
+            }
+
+            cursor.comma_index = Some(parser.index);
+            cursor.after_member = false;
+            parser.next();
+        }
+
+        match cursor.comma_index {
+            // https://httpwg.org/specs/rfc9651.html#text-parse
+            None => parser.consume_sp_chars(),
+            Some(_) => parser.consume_ows_chars(),
+        }
+        cursor.index = parser.index;
+
+        if parser.peek().is_none() {
+            return match cursor.comma_index {
+                _ if !finished => Err(need_more_input(&parser)),
+                None => Ok(()),
+                // Report the error at the position of the comma itself, rather
+                // than at the end of input.
+                Some(comma_index) => Err(Error::with_index(
+                    ErrorKind::TrailingComma,
+                    "trailing comma",
+                    comma_index,
+                )),
+            };
+        }
+
+        parser.check_limit(
+            cursor.members + 1,
+            parser.limits.max_members,
+            "too many members",
+        )?;
+
+        if !finished {
+            let mut lookahead = parser.lookahead();
+            let result = parse_member(&mut lookahead, false);
+            if lookahead.peek().is_none() {
+                return Err(need_more_input(&parser));
+            }
+            result?;
+        }
+
+        parse_member(&mut parser, true)?;
+        cursor.after_member = true;
+        cursor.members += 1;
+        cursor.index = parser.index;
+    }
+}
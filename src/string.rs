@@ -1,8 +1,9 @@
-use crate::Error;
+use crate::{Error, ErrorKind};
 
-use std::borrow::{Borrow, Cow};
-use std::fmt;
-use std::string::String as StdString;
+use alloc::borrow::{Cow, ToOwned};
+use alloc::string::String as StdString;
+use core::borrow::Borrow;
+use core::fmt;
 
 /// An owned structured field value [string].
 ///
@@ -31,7 +32,11 @@ struct StringError {
 
 impl From<StringError> for Error {
     fn from(err: StringError) -> Error {
-        Error::with_index("invalid character", err.byte_index)
+        Error::with_index(
+            ErrorKind::InvalidStringCharacter,
+            "invalid character",
+            err.byte_index,
+        )
     }
 }
 
@@ -64,6 +69,13 @@ impl StringRef {
         Ok(Self::cast(v))
     }
 
+    // Like `from_str`, but assumes that the contents of the string have already
+    // been validated as a string.
+    pub(crate) fn from_validated_str(v: &str) -> &Self {
+        debug_assert!(validate(v.as_bytes()).is_ok());
+        Self::cast(v)
+    }
+
     /// Creates a `&StringRef`, panicking if the value is invalid.
     ///
     /// This method is intended to be called from `const` contexts in which the
@@ -100,7 +112,7 @@ impl Borrow<StringRef> for String {
     }
 }
 
-impl std::ops::Deref for String {
+impl core::ops::Deref for String {
     type Target = StringRef;
 
     fn deref(&self) -> &StringRef {
@@ -133,6 +145,13 @@ impl String {
             Err(err) => Err((err.into(), v)),
         }
     }
+
+    // Like `from_string`, but assumes that the contents of the string have
+    // already been validated as a string.
+    pub(crate) fn from_validated_string(v: StdString) -> Self {
+        debug_assert!(validate(v.as_bytes()).is_ok());
+        Self(v)
+    }
 }
 
 /// Creates a `&StringRef`, panicking if the value is invalid.
@@ -0,0 +1,71 @@
+use crate::{
+    integer, string_ref, token_ref, Date, Decimal, DictionaryExt, Error, ErrorKind, Parser,
+    TypeMismatch, ValueKind,
+};
+
+#[test]
+fn dictionary_accessors() -> Result<(), Error> {
+    let dict =
+        Parser::new(r#"i=1;p, d=1.5, s="x", t=y, b=:AQI=:, f, g=?0, at=@5, ds=%"%c3%bc", l=(1 2)"#)
+            .parse_dictionary()?;
+
+    assert_eq!(dict.get_integer("i")?, Some(integer(1)));
+    assert_eq!(dict.get_decimal("d")?, Some(Decimal::try_from(1.5)?));
+    assert_eq!(dict.get_string("s")?, Some(string_ref("x")));
+    assert_eq!(dict.get_token("t")?, Some(token_ref("y")));
+    assert_eq!(dict.get_byte_sequence("b")?, Some(&[1, 2][..]));
+    assert_eq!(dict.get_bool_flag("f")?, Some(true));
+    assert_eq!(dict.get_bool_flag("g")?, Some(false));
+    assert_eq!(
+        dict.get_date("at")?,
+        Some(Date::from_unix_seconds(integer(5)))
+    );
+    assert_eq!(dict.get_display_string("ds")?, Some("ü"));
+    assert_eq!(dict.get_inner_list("l")?.map(|l| l.items.len()), Some(2));
+    assert_eq!(dict.get_item("i")?.map(|i| i.params.len()), Some(1));
+    assert_eq!(dict.get_as::<bool>("g")?, Some(false));
+
+    assert_eq!(dict.get_integer("missing")?, None);
+    assert_eq!(dict.get_inner_list("missing")?, None);
+    Ok(())
+}
+
+#[test]
+fn dictionary_accessor_errors() -> Result<(), Error> {
+    let dict = Parser::new("i=1, l=(1 2)").parse_dictionary()?;
+
+    let err = dict.get_string("i").unwrap_err();
+    assert_eq!(err.expected(), ValueKind::String);
+    assert_eq!(err.found(), ValueKind::Integer);
+    assert_eq!(err.to_string(), "expected string, found integer");
+
+    let err = dict.get_integer("l").unwrap_err();
+    assert_eq!(err.expected(), ValueKind::Item);
+    assert_eq!(err.found(), ValueKind::InnerList);
+    assert_eq!(err.to_string(), "expected item, found inner list");
+
+    let err = dict.get_inner_list("i").unwrap_err();
+    assert_eq!(err.expected(), ValueKind::InnerList);
+    assert_eq!(err.found(), ValueKind::Integer);
+
+    let err = Error::from(dict.get_bool_flag("i").unwrap_err());
+    assert_eq!(err.kind(), ErrorKind::TypeMismatch);
+    assert_eq!(err.to_string(), "expected boolean, found integer");
+
+    let dict = Parser::new(r#"s="x", t=y"#).parse_dictionary()?;
+    for key in ["s", "t"] {
+        let err = dict.get_display_string(key).unwrap_err();
+        assert_eq!(err.expected(), ValueKind::DisplayString);
+    }
+    Ok(())
+}
+
+#[test]
+fn bare_item_try_from() {
+    let bare_item = crate::BareItem::Integer(integer(3));
+    assert_eq!(crate::Integer::try_from(&bare_item), Ok(integer(3)));
+    assert!(matches!(
+        <&crate::StringRef>::try_from(&bare_item),
+        Err(TypeMismatch { .. })
+    ));
+}
@@ -0,0 +1,284 @@
+use std::borrow::Cow;
+
+use crate::borrowed::{InnerList, Item, ListEntry};
+use crate::{
+    key_ref, string_ref, token_ref, BareItemFromInput, DuplicateKeyPolicy, Error, ErrorKind, Parser,
+};
+
+#[test]
+fn parse_item_borrowed() -> Result<(), Error> {
+    let input = r#""a\"b";c="d";e=tok;f=:AQI=:"#;
+    let item = Parser::new(input).parse_item_borrowed()?;
+    assert_eq!(
+        item.bare_item,
+        BareItemFromInput::String(Cow::Owned(string_ref(r#"a"b"#).to_owned()))
+    );
+    assert_eq!(item.params.len(), 3);
+
+    let (key, value) = &item.params[0];
+    assert_eq!(*key, key_ref("c"));
+    assert!(matches!(value, BareItemFromInput::String(Cow::Borrowed(s)) if s.as_str() == "d"));
+    assert_eq!(
+        item.params.get("e"),
+        Some(&BareItemFromInput::Token(token_ref("tok")))
+    );
+    assert_eq!(
+        item.params.get("f"),
+        Some(&BareItemFromInput::ByteSequence(vec![1, 2]))
+    );
+    assert_eq!(item.params.get("g"), None);
+
+    // Keys and tokens point into the input.
+    assert!(input
+        .as_bytes()
+        .as_ptr_range()
+        .contains(&key.as_str().as_ptr()));
+    Ok(())
+}
+
+#[test]
+fn parse_list_borrowed() -> Result<(), Error> {
+    let list = Parser::new("1;a, (b c);d, ()").parse_list_borrowed()?;
+    assert_eq!(list.len(), 3);
+
+    let item = list[0].as_item().unwrap();
+    assert_eq!(item.bare_item, BareItemFromInput::Integer(1.into()));
+    assert_eq!(
+        item.params.get("a"),
+        Some(&BareItemFromInput::Boolean(true))
+    );
+    assert_eq!(list[0].as_inner_list(), None);
+
+    let inner_list = list[1].as_inner_list().unwrap();
+    assert_eq!(
+        inner_list
+            .items
+            .iter()
+            .map(|item| item.bare_item.as_token().unwrap().as_str())
+            .collect::<Vec<_>>(),
+        ["b", "c"]
+    );
+    assert_eq!(inner_list.params.len(), 1);
+
+    assert_eq!(list[2], ListEntry::InnerList(InnerList::default()));
+    Ok(())
+}
+
+#[test]
+fn parse_dictionary_borrowed() -> Result<(), Error> {
+    let dict = Parser::new("a=1;x=1;y;x=2, b, c=(1), a=2;x").parse_dictionary_borrowed()?;
+    assert_eq!(
+        dict.iter().map(|(key, _)| key.as_str()).collect::<Vec<_>>(),
+        ["a", "b", "c"]
+    );
+
+    let a = dict.get("a").unwrap().as_item().unwrap();
+    assert_eq!(a.bare_item, BareItemFromInput::Integer(2.into()));
+    assert_eq!(a.params.len(), 1);
+    assert_eq!(
+        dict.get("b"),
+        Some(&ListEntry::Item(Item {
+            bare_item: BareItemFromInput::Boolean(true),
+            params: Default::default(),
+        }))
+    );
+    assert!(dict.get("c").unwrap().as_inner_list().is_some());
+    assert_eq!(dict.get("d"), None);
+
+    let dict = Parser::new("a=1;x=1;x=2, a=2")
+        .with_duplicate_key_policy(DuplicateKeyPolicy::FirstWins)
+        .parse_dictionary_borrowed()?;
+    let a = dict.get("a").unwrap().as_item().unwrap();
+    assert_eq!(a.bare_item, BareItemFromInput::Integer(1.into()));
+    assert_eq!(
+        a.params.get("x"),
+        Some(&BareItemFromInput::Integer(1.into()))
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_borrowed_errors() {
+    assert_eq!(
+        Parser::new("a=1,")
+            .parse_dictionary_borrowed()
+            .unwrap_err()
+            .kind(),
+        ErrorKind::TrailingComma
+    );
+    assert_eq!(
+        Parser::new("(1").parse_list_borrowed().unwrap_err().kind(),
+        ErrorKind::InvalidInnerList
+    );
+    assert_eq!(
+        Parser::new("1 2").parse_item_borrowed().unwrap_err().kind(),
+        ErrorKind::TrailingCharacters
+    );
+}
+
+#[test]
+fn parse_borrowed_many_keys() -> Result<(), Error> {
+    let keys: Vec<_> = (0..10_000).map(|i| format!("k{i}")).collect();
+
+    let input = format!("{}, k5000=1", keys.join(", "));
+    let dict = Parser::new(&input).parse_dictionary_borrowed()?;
+    assert_eq!(dict.len(), 10_000);
+    assert_eq!(dict[5000].0, key_ref("k5000"));
+    assert_eq!(
+        dict.get("k5000")
+            .and_then(ListEntry::as_item)
+            .map(|item| &item.bare_item),
+        Some(&BareItemFromInput::Integer(crate::integer(1)))
+    );
+
+    let input = format!("x;{};k5000=1", keys.join(";"));
+    let item = Parser::new(&input).parse_item_borrowed()?;
+    assert_eq!(item.params.len(), 10_000);
+    assert_eq!(
+        item.params.get("k5000"),
+        Some(&BareItemFromInput::Integer(crate::integer(1)))
+    );
+    Ok(())
+}
+
+#[test]
+fn parameters_take() -> Result<(), Error> {
+    use crate::visitor::ParameterVisitor;
+
+    let mut params = Parser::new("x;a=1;b=2;c=3").parse_item_borrowed()?.params;
+    assert_eq!(
+        params.take("a"),
+        Some(BareItemFromInput::Integer(crate::integer(1)))
+    );
+    assert_eq!(params.take("a"), None);
+
+    let mut visitor = &mut params;
+    visitor
+        .parameter(key_ref("c"), BareItemFromInput::Boolean(true))
+        .unwrap();
+    visitor
+        .parameter(key_ref("d"), BareItemFromInput::Boolean(false))
+        .unwrap();
+    assert_eq!(
+        params
+            .iter()
+            .map(|(key, _)| key.as_str())
+            .collect::<Vec<_>>(),
+        ["b", "c", "d"]
+    );
+    assert_eq!(params.get("c"), Some(&BareItemFromInput::Boolean(true)));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "parsed-types")]
+fn into_owned() -> Result<(), Error> {
+    for input in [
+        r#"a=1;x="y\\z", b, c=(tok :AQI=: ?0;p);q=1.5, d=%"%c3%a9", e=@1"#,
+        "a=1;x=1;x=2, b=2, a=3",
+        "a=()",
+    ] {
+        assert_eq!(
+            Parser::new(input).parse_dictionary_borrowed()?.into_owned(),
+            Parser::new(input).parse_dictionary()?,
+            "{input}"
+        );
+        assert_eq!(
+            Parser::new(input)
+                .parse_list_borrowed()
+                .map(|list| list.into_owned()),
+            Parser::new(input).parse_list(),
+            "{input}"
+        );
+    }
+
+    for input in [r#""a";b=tok"#, "1;a;a=?0", ":AQI=:"] {
+        assert_eq!(
+            Parser::new(input).parse_item_borrowed()?.into_owned(),
+            Parser::new(input).parse_item()?,
+            "{input}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn find_dictionary_member() -> Result<(), Error> {
+    let input = "a=1;x, b=:AQI=:, c=(tok \"s\");y=?0, a=2;z, d";
+
+    let a = Parser::new(input).find_dictionary_member("a")?.unwrap();
+    assert_eq!(
+        a,
+        Parser::new(input)
+            .parse_dictionary_borrowed()?
+            .get("a")
+            .unwrap()
+            .clone()
+    );
+    assert_eq!(
+        a.as_item().unwrap().bare_item,
+        BareItemFromInput::Integer(2.into())
+    );
+
+    let b = Parser::new(input).find_dictionary_member("b")?.unwrap();
+    assert_eq!(
+        b.as_item().unwrap().bare_item,
+        BareItemFromInput::ByteSequence(vec![1, 2])
+    );
+
+    let c = Parser::new(input).find_dictionary_member("c")?.unwrap();
+    assert_eq!(c.as_inner_list().unwrap().items.len(), 2);
+
+    let d = Parser::new(input).find_dictionary_member("d")?.unwrap();
+    assert_eq!(
+        d.as_item().unwrap().bare_item,
+        BareItemFromInput::Boolean(true)
+    );
+
+    assert_eq!(Parser::new(input).find_dictionary_member("e")?, None);
+
+    let a = Parser::new(input)
+        .with_duplicate_key_policy(DuplicateKeyPolicy::FirstWins)
+        .find_dictionary_member("a")?
+        .unwrap();
+    assert_eq!(
+        a.as_item().unwrap().bare_item,
+        BareItemFromInput::Integer(1.into())
+    );
+
+    let err = Parser::new(input)
+        .with_duplicate_key_policy(DuplicateKeyPolicy::Reject)
+        .find_dictionary_member("d")
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::DuplicateKey);
+    Ok(())
+}
+
+#[test]
+fn find_dictionary_member_validates_input() {
+    let long = "A".repeat(2048);
+    for input in [
+        "a=1, b=:AQI:",
+        "a=1, b=:AQI=:, c=:AQ=I:",
+        "a=1, b=:A:",
+        "a=1, b=:A===:",
+        "a=1, b=:AQ#=:",
+        "a=1, b=:AQI=",
+        "a=1, b=?2",
+        "a=1, b=(1,",
+        "a=1, b=1,",
+        "a=1 b=1",
+        &format!("a=1, b=:{long}:"),
+        &format!("a=1, b=:{}A=:", "A".repeat(1022)),
+        &format!("a=1, b=:{}AA==:", "A".repeat(2044)),
+        &format!("a=1, b=:{}AA==AAAA:", "A".repeat(1020)),
+        &format!("a=1, b=:{}AA==:", "A".repeat(1021)),
+        &format!("a=1, b=:{}A#AA:", "A".repeat(1500)),
+    ] {
+        assert_eq!(
+            Parser::new(input).find_dictionary_member("a").map(drop),
+            Parser::new(input).parse_dictionary_borrowed().map(drop),
+            "{input}"
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{from_str_dictionary, from_str_item, from_str_list, Error, ErrorKind};
+
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+#[test]
+fn deserialize_item() -> Result<(), Error> {
+    assert_eq!(from_str_item::<i64>("12")?, 12);
+    assert_eq!(from_str_item::<u8>("12;a=1")?, 12);
+    assert_eq!(from_str_item::<f64>("12.5")?, 12.5);
+    assert!(from_str_item::<bool>("?1")?);
+    assert_eq!(from_str_item::<&str>("tok")?, "tok");
+    assert_eq!(from_str_item::<&str>(r#""foo""#)?, "foo");
+    assert_eq!(from_str_item::<&str>(r#"%"f%c3%bc""#).ok(), None);
+    assert_eq!(from_str_item::<String>(r#"%"f%c3%bc""#)?, "fü");
+    assert_eq!(from_str_item::<i64>("@1659578233")?, 1659578233);
+    assert_eq!(from_str_item::<Vec<u8>>(":aGVsbG8=:")?, b"hello");
+    assert_eq!(from_str_item::<Option<i64>>("1")?, Some(1));
+    Ok(())
+}
+
+#[test]
+fn deserialize_item_borrows_from_input() -> Result<(), Error> {
+    #[derive(Deserialize)]
+    struct Value<'a>(#[serde(borrow)] Cow<'a, str>);
+
+    let Value(value) = from_str_item(r#""foo""#)?;
+    assert!(matches!(value, Cow::Borrowed("foo")));
+
+    let Value(value) = from_str_item(r#""f\"oo""#)?;
+    assert!(matches!(value, Cow::Owned(ref v) if v == "f\"oo"));
+    Ok(())
+}
+
+#[test]
+fn deserialize_item_with_params() -> Result<(), Error> {
+    let (value, params): (&str, BTreeMap<&str, i64>) = from_str_item("tok;a=1;b=2;a=3")?;
+    assert_eq!(value, "tok");
+    assert_eq!(params, BTreeMap::from([("a", 3), ("b", 2)]));
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Params {
+        q: f64,
+        #[serde(default)]
+        x: bool,
+    }
+
+    let (value, params): (i64, Params) = from_str_item("1;q=0.5")?;
+    assert_eq!(value, 1);
+    assert_eq!(params, Params { q: 0.5, x: false });
+    Ok(())
+}
+
+#[test]
+fn deserialize_enum() -> Result<(), Error> {
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    enum Policy {
+        RequireCorp,
+        Credentialless,
+    }
+
+    assert_eq!(
+        from_str_item::<Policy>("require-corp")?,
+        Policy::RequireCorp
+    );
+    assert_eq!(
+        from_str_item::<Policy>(r#""credentialless""#)?,
+        Policy::Credentialless
+    );
+    assert!(from_str_item::<Policy>("unsafe-none").is_err());
+    assert!(from_str_item::<Policy>("1").is_err());
+    Ok(())
+}
+
+#[test]
+fn deserialize_list() -> Result<(), Error> {
+    assert_eq!(from_str_list::<Vec<i64>>("1, 2;a, 3")?, [1, 2, 3]);
+    assert_eq!(
+        from_str_list::<Vec<Vec<&str>>>("(a b), (), (c)")?,
+        [vec!["a", "b"], vec![], vec!["c"]]
+    );
+
+    let list: Vec<(Vec<i64>, BTreeMap<&str, &str>)> = from_str_list("(1 2);a=x, ()")?;
+    assert_eq!(
+        list,
+        [
+            (vec![1, 2], BTreeMap::from([("a", "x")])),
+            (vec![], BTreeMap::new())
+        ]
+    );
+
+    assert!(from_str_list::<(i64, i64)>("1, 2, 3").is_err());
+    Ok(())
+}
+
+#[test]
+fn deserialize_dictionary() -> Result<(), Error> {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Dict<'a> {
+        a: i64,
+        #[serde(default)]
+        b: bool,
+        c: Option<&'a str>,
+        #[serde(rename = "d-e")]
+        d_e: Vec<f64>,
+        #[serde(default)]
+        missing: Option<i64>,
+    }
+
+    assert_eq!(
+        from_str_dictionary::<Dict>(r#"a=1, b, c="x", d-e=(1.5 2.0), a=2"#)?,
+        Dict {
+            a: 2,
+            b: true,
+            c: Some("x"),
+            d_e: vec![1.5, 2.0],
+            missing: None,
+        }
+    );
+
+    let dict: BTreeMap<&str, (bool, BTreeMap<&str, i64>)> = from_str_dictionary("a;x=1, b=?0")?;
+    assert_eq!(
+        dict,
+        BTreeMap::from([
+            ("a", (true, BTreeMap::from([("x", 1)]))),
+            ("b", (false, BTreeMap::new())),
+        ])
+    );
+    Ok(())
+}
+
+#[test]
+fn deserialize_errors() {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Dict {
+        a: i64,
+    }
+
+    assert_eq!(
+        from_str_item::<i64>("1,"),
+        Err(Error::with_index(
+            ErrorKind::TrailingCharacters,
+            "trailing characters after parsed value",
+            1
+        ))
+    );
+    assert_eq!(
+        from_str_dictionary::<Dict>("b=1").unwrap_err(),
+        Error::custom("missing field `a`")
+    );
+    assert_eq!(
+        from_str_dictionary::<Dict>("a=tok").unwrap_err(),
+        Error::custom(r#"invalid type: string "tok", expected i64"#)
+    );
+    assert_eq!(
+        from_str_item::<u8>("256").unwrap_err(),
+        Error::custom("invalid value: integer `256`, expected u8")
+    );
+}
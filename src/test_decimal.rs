@@ -1,4 +1,4 @@
-use crate::{Decimal, Error, Integer};
+use crate::{Decimal, Error, ErrorKind, Integer};
 
 #[test]
 fn test_display() {
@@ -58,7 +58,7 @@ fn test_into_f64() {
 #[test]
 fn test_try_from_f64() {
     for (expected, input) in [
-        (Err(Error::new("NaN")), f64::NAN),
+        (Err(Error::new(ErrorKind::NaN, "NaN")), f64::NAN),
         (Err(Error::out_of_range()), f64::INFINITY),
         (Err(Error::out_of_range()), f64::NEG_INFINITY),
         (Err(Error::out_of_range()), -1_000_000_000_000.0),
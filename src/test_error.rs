@@ -0,0 +1,71 @@
+use crate::visitor::Ignored;
+use crate::{ErrorKind, Parser};
+
+#[test]
+fn render() {
+    let input = "a=1, b";
+    let err = Parser::new(input)
+        .parse_item_with_visitor(Ignored)
+        .unwrap_err();
+    assert_eq!(
+        err.render(input),
+        "\
+error: trailing characters after parsed value at index 1
+  | a=1, b
+  |  ^ expected end of input
+"
+    );
+
+    let input = r#""abc"#;
+    let err = Parser::new(input)
+        .parse_item_with_visitor(Ignored)
+        .unwrap_err();
+    assert_eq!(
+        err.render(input),
+        "\
+error: unterminated string at index 4
+  | \"abc
+  |     ^ expected closing '\"'
+"
+    );
+}
+
+#[test]
+fn render_escapes_non_printable_bytes() {
+    let input = b"\"a\tb\"";
+    let err = Parser::new(input)
+        .parse_item_with_visitor(Ignored)
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidStringCharacter);
+    assert_eq!(
+        err.render(input),
+        "\
+error: invalid string character at index 2
+  | \"a\\x09b\"
+  |   ^ expected printable ASCII
+"
+    );
+}
+
+#[test]
+fn render_clips_long_input() {
+    let input = format!("{}1.2345, {}", "1, ".repeat(20), "2, ".repeat(20));
+    let err = Parser::new(&input)
+        .parse_list_with_visitor(&mut Ignored)
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidDecimal);
+    assert_eq!(
+        err.render(&input),
+        "\
+error: too many digits after decimal point at index 65
+  | ...1, 1, 1, 1, 1, 1, 1, 1, 1, 1.2345, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2,...
+  |                                    ^ expected at most 12 digits before the decimal point and 1 to 3 after it
+"
+    );
+}
+
+#[test]
+fn render_without_index() {
+    let err = crate::Integer::try_from(i64::MAX).unwrap_err();
+    assert_eq!(err.render("x"), "error: out of range\n  | x\n");
+}
@@ -0,0 +1,79 @@
+use crate::{Error, ErrorKind, LineLocation, Parser};
+
+fn location(line: usize, offset: usize) -> Option<LineLocation> {
+    Some(LineLocation { line, offset })
+}
+
+#[test]
+fn test_from_field_lines() {
+    let lines = Parser::from_field_lines(["a, b", "", "c"]);
+    assert_eq!(lines.as_bytes(), b"a, b, , c");
+    assert_eq!(lines.line_count(), 3);
+
+    let lines = Parser::from_field_lines([b"a".as_slice()]);
+    assert_eq!(lines.as_bytes(), b"a");
+    assert_eq!(lines.line_count(), 1);
+
+    let lines = Parser::from_field_lines(Vec::<String>::new());
+    assert_eq!(lines.as_bytes(), b"");
+    assert_eq!(lines.line_count(), 0);
+    assert_eq!(lines.location(0), None);
+}
+
+#[test]
+fn test_location() {
+    let lines = Parser::from_field_lines(["ab", "cde", "f"]);
+
+    assert_eq!(lines.location(0), location(0, 0));
+    assert_eq!(lines.location(1), location(0, 1));
+    assert_eq!(lines.location(2), location(0, 2));
+    assert_eq!(lines.location(3), location(0, 2));
+    assert_eq!(lines.location(4), location(1, 0));
+    assert_eq!(lines.location(6), location(1, 2));
+    assert_eq!(lines.location(7), location(1, 3));
+    assert_eq!(lines.location(9), location(2, 0));
+    assert_eq!(lines.location(10), location(2, 1));
+    assert_eq!(lines.location(100), location(2, 1));
+}
+
+#[test]
+fn test_error_location() {
+    let lines = Parser::from_field_lines(["a, b", "c d"]);
+    let err = lines.parser().parse_list_spanned().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::ExpectedComma);
+    assert_eq!(err.index(), Some(8));
+    assert_eq!(lines.error_location(&err), location(1, 2));
+    assert_eq!(
+        lines.error_location(&err).unwrap().to_string(),
+        "line 1, offset 2"
+    );
+
+    let lines = Parser::from_field_lines(["a", ""]);
+    let err = lines.parser().parse_list_spanned().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TrailingComma);
+    assert_eq!(lines.error_location(&err), location(0, 1));
+
+    let err = Error::new(ErrorKind::EmptyList, "no index");
+    assert_eq!(lines.error_location(&err), None);
+}
+
+#[test]
+#[cfg(feature = "parsed-types")]
+fn test_parse_field_lines() -> Result<(), Error> {
+    let lines = Parser::from_field_lines(["a=1;x, b=2", "c, a=3"]);
+    assert_eq!(
+        lines.parser().parse_dictionary()?,
+        Parser::new("a=3, b=2, c").parse_dictionary()?
+    );
+
+    let lines = Parser::from_field_lines([r#""foo"#, r#"bar""#]);
+    assert_eq!(
+        lines.parser().parse_item()?,
+        Parser::new(r#""foo, bar""#).parse_item()?
+    );
+
+    let lines = Parser::from_field_lines(["1", "2"]);
+    let err = lines.parser().parse_item().unwrap_err();
+    assert_eq!(lines.error_location(&err), location(0, 1));
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use crate::combine_field_lines;
+#[cfg(feature = "parsed-types")]
+use crate::{key_ref, HeaderMapExt, Parser, SFVResult, SerializeValue};
+
+#[cfg(feature = "parsed-types")]
+use http::header::HeaderMap;
+use http::header::HeaderValue;
+
+#[test]
+fn test_combine_field_lines() {
+    assert_eq!(&*combine_field_lines([]), b"");
+
+    let a = HeaderValue::from_static("a;x=1");
+    let b = HeaderValue::from_static("b, c");
+    let c = HeaderValue::from_static("");
+
+    let combined = combine_field_lines([&a]);
+    assert!(matches!(combined, std::borrow::Cow::Borrowed(_)));
+    assert_eq!(&*combined, b"a;x=1");
+
+    assert_eq!(&*combine_field_lines([&a, &b]), b"a;x=1, b, c");
+    assert_eq!(&*combine_field_lines([&a, &b, &c]), b"a;x=1, b, c, ");
+}
+
+#[test]
+#[cfg(feature = "parsed-types")]
+fn test_header_map_ext() -> SFVResult<()> {
+    let mut headers = HeaderMap::new();
+    headers.append("item", HeaderValue::from_static("?1"));
+    headers.append("list", HeaderValue::from_static("a, b"));
+    headers.append("list", HeaderValue::from_static("c"));
+    headers.append("dict", HeaderValue::from_static("a=1"));
+    headers.append("dict", HeaderValue::from_static("b=2, a=3"));
+    headers.append("two-items", HeaderValue::from_static("1"));
+    headers.append("two-items", HeaderValue::from_static("2"));
+
+    assert_eq!(
+        headers.parse_sfv_item("item")?,
+        Some(Parser::new("?1").parse_item()?)
+    );
+    assert_eq!(
+        headers.parse_sfv_list("list")?,
+        Some(Parser::new("a, b, c").parse_list()?)
+    );
+
+    let dict = headers.parse_sfv_dictionary("dict")?.unwrap();
+    assert_eq!(dict.len(), 2);
+    assert_eq!(dict.get_index(0).unwrap().0, key_ref("a"));
+    assert_eq!(dict, Parser::new("a=3, b=2").parse_dictionary()?);
+
+    assert!(headers.parse_sfv_item("two-items").is_err());
+    assert_eq!(headers.parse_sfv_list("missing")?, None);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "parsed-types")]
+fn test_parse_header_value() -> SFVResult<()> {
+    let value = HeaderValue::from_static("a=1, b");
+    assert_eq!(
+        Parser::new(&value).parse_dictionary()?,
+        Parser::new("a=1, b").parse_dictionary()?
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "parsed-types")]
+fn test_serialize_header_value() -> SFVResult<()> {
+    let list = Parser::new(r#"%"caf%c3%a9", :AQID:;a="b", (1 2)"#).parse_list()?;
+    let value = list.serialize_header_value()?;
+    assert_eq!(value, list.serialize_value()?.as_str());
+    assert_eq!(Parser::new(&value).parse_list()?, list);
+
+    assert!(crate::List::new().serialize_header_value().is_err());
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use crate::{
+    integer, key_ref, string_ref, token_ref, BareItem, Date, Decimal, Dictionary, Error, InnerList,
+    Item, List, ListEntry, Parameters, Parser,
+};
+
+#[test]
+fn sfv_item() -> Result<(), Error> {
+    assert_eq!(crate::sfv_item!(1), Item::new(1));
+    assert_eq!(crate::sfv_item!(-1_000), Item::new(-1000));
+    assert_eq!(crate::sfv_item!(0x10), Item::new(16));
+    assert_eq!(crate::sfv_item!(1.5), Item::new(Decimal::try_from(1.5)?));
+    assert_eq!(
+        crate::sfv_item!(-0.125),
+        Item::new(Decimal::try_from(-0.125)?)
+    );
+    assert_eq!(crate::sfv_item!("a\"b"), Item::new(string_ref("a\"b")));
+    assert_eq!(crate::sfv_item!(r"a\b"), Item::new(string_ref("a\\b")));
+    assert_eq!(crate::sfv_item!(abc), Item::new(token_ref("abc")));
+    assert_eq!(crate::sfv_item!(?0), Item::new(false));
+    assert_eq!(crate::sfv_item!(?1), Item::new(true));
+    assert_eq!(
+        crate::sfv_item!(@-5),
+        Item::new(Date::from_unix_seconds(integer(-5)))
+    );
+    assert_eq!(
+        crate::sfv_item!(%"ü"),
+        Item::new(BareItem::DisplayString("ü".to_owned()))
+    );
+    assert_eq!(
+        crate::sfv_item!({ vec![1, 2] }),
+        Item::new(BareItem::ByteSequence(vec![1, 2]))
+    );
+    assert_eq!(
+        crate::sfv_item!({ token_ref("*/x") }),
+        Item::new(token_ref("*/x"))
+    );
+
+    assert_eq!(
+        crate::sfv_item!(1; a; b = -2.5; "c-d" = x),
+        Item::with_params(
+            1,
+            Parameters::from_iter(vec![
+                (key_ref("a").to_owned(), BareItem::Boolean(true)),
+                (key_ref("b").to_owned(), Decimal::try_from(-2.5)?.into()),
+                (key_ref("c-d").to_owned(), token_ref("x").into()),
+            ])
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn sfv_list() -> Result<(), Error> {
+    assert_eq!(crate::sfv_list![], List::new());
+
+    let list = crate::sfv_list![1;a, (), (x "y";b ?1);c=@0, %"z",];
+    assert_eq!(
+        list,
+        Parser::new(r#"1;a, (), (x "y";b ?1);c=@0, %"z""#).parse_list()?
+    );
+
+    assert_eq!(
+        crate::sfv_list![(1 2)],
+        vec![ListEntry::InnerList(InnerList::new(vec![
+            Item::new(1),
+            Item::new(2)
+        ]))]
+    );
+    Ok(())
+}
+
+#[test]
+fn sfv_dict() -> Result<(), Error> {
+    assert_eq!(crate::sfv_dict! {}, Dictionary::new());
+
+    let dict = crate::sfv_dict! {
+        a = 1,
+        b = (tok "s"); p = ?1,
+        c; q = 1.5,
+        "max-age" = 60,
+        d,
+    };
+    assert_eq!(
+        dict,
+        Parser::new(r#"a=1, b=(tok "s");p, c;q=1.5, max-age=60, d"#).parse_dictionary()?
+    );
+    Ok(())
+}
@@ -1,8 +1,15 @@
+use std::borrow::Cow;
+
 use crate::visitor::Ignored;
-use crate::{integer, key_ref, string_ref, token_ref, Decimal, Error, Num, Parser, RefBareItem};
+use crate::{
+    integer, key_ref, string_ref, token_ref, Decimal, DuplicateKeyPolicy, Error, ErrorKind,
+    Integer, KeyRef, Num, Parser, ParserLimits, RefBareItem,
+};
 
 #[cfg(feature = "parsed-types")]
-use crate::{BareItem, Date, Dictionary, InnerList, Item, List, Parameters, Version};
+use crate::{
+    BareItem, Date, Dictionary, InnerList, Item, List, Parameters, SerializeValue, Version,
+};
 
 #[test]
 #[cfg(feature = "parsed-types")]
@@ -24,19 +31,28 @@ fn parse() -> Result<(), Error> {
 fn parse_errors() {
     let input = r#""some_value¢""#;
     assert_eq!(
-        Err(Error::with_index("invalid string character", 11)),
+        Err(Error::with_index(
+            ErrorKind::InvalidStringCharacter,
+            "invalid string character",
+            11
+        )),
         Parser::new(input).parse_item_with_visitor(Ignored)
     );
     let input = r#""some_value" trailing_text""#;
     assert_eq!(
         Err(Error::with_index(
+            ErrorKind::TrailingCharacters,
             "trailing characters after parsed value",
             13
         )),
         Parser::new(input).parse_item_with_visitor(Ignored)
     );
     assert_eq!(
-        Err(Error::with_index("expected start of bare item", 0)),
+        Err(Error::with_index(
+            ErrorKind::ExpectedBareItem,
+            "expected start of bare item",
+            0
+        )),
         Parser::new("").parse_item_with_visitor(Ignored)
     );
 }
@@ -141,49 +157,78 @@ fn parse_list_of_items_and_lists_with_param() -> Result<(), Error> {
 fn parse_list_errors() {
     let input = ",";
     assert_eq!(
-        Err(Error::with_index("expected start of bare item", 0)),
+        Err(Error::with_index(
+            ErrorKind::ExpectedBareItem,
+            "expected start of bare item",
+            0
+        )),
         Parser::new(input).parse_list_with_visitor(&mut Ignored)
     );
 
     let input = "a, b c";
     assert_eq!(
-        Err(Error::with_index("trailing characters after member", 5)),
+        Err(Error::with_index(
+            ErrorKind::ExpectedComma,
+            "trailing characters after member",
+            5
+        )),
         Parser::new(input).parse_list_with_visitor(&mut Ignored)
     );
 
     let input = "a,";
     assert_eq!(
-        Err(Error::with_index("trailing comma", 1)),
+        Err(Error::with_index(
+            ErrorKind::TrailingComma,
+            "trailing comma",
+            1
+        )),
         Parser::new(input).parse_list_with_visitor(&mut Ignored)
     );
 
     let input = "a     ,    ";
     assert_eq!(
-        Err(Error::with_index("trailing comma", 6)),
+        Err(Error::with_index(
+            ErrorKind::TrailingComma,
+            "trailing comma",
+            6
+        )),
         Parser::new(input).parse_list_with_visitor(&mut Ignored)
     );
 
     let input = "a\t \t ,\t ";
     assert_eq!(
-        Err(Error::with_index("trailing comma", 5)),
+        Err(Error::with_index(
+            ErrorKind::TrailingComma,
+            "trailing comma",
+            5
+        )),
         Parser::new(input).parse_list_with_visitor(&mut Ignored)
     );
 
     let input = "a\t\t,\t\t\t";
     assert_eq!(
-        Err(Error::with_index("trailing comma", 3)),
+        Err(Error::with_index(
+            ErrorKind::TrailingComma,
+            "trailing comma",
+            3
+        )),
         Parser::new(input).parse_list_with_visitor(&mut Ignored)
     );
 
     let input = "(a b),";
     assert_eq!(
-        Err(Error::with_index("trailing comma", 5)),
+        Err(Error::with_index(
+            ErrorKind::TrailingComma,
+            "trailing comma",
+            5
+        )),
         Parser::new(input).parse_list_with_visitor(&mut Ignored)
     );
 
     let input = "(1, 2, (a b)";
     assert_eq!(
         Err(Error::with_index(
+            ErrorKind::InvalidInnerList,
             "expected inner list delimiter (' ' or ')')",
             2
         )),
@@ -195,13 +240,21 @@ fn parse_list_errors() {
 fn parse_inner_list_errors() {
     let input = "c b); a=1";
     assert_eq!(
-        Err(Error::with_index("expected start of inner list", 0)),
+        Err(Error::with_index(
+            ErrorKind::InvalidInnerList,
+            "expected start of inner list",
+            0
+        )),
         Parser::new(input).parse_inner_list(Ignored)
     );
 
     let input = "(";
     assert_eq!(
-        Err(Error::with_index("unterminated inner list", 1)),
+        Err(Error::with_index(
+            ErrorKind::InvalidInnerList,
+            "unterminated inner list",
+            1
+        )),
         Parser::new(input).parse_inner_list(Ignored)
     );
 }
@@ -266,12 +319,20 @@ fn parse_dict_empty() -> Result<(), Error> {
 fn parse_dict_errors() {
     let input = "abc=123;a=1;b=2 def";
     assert_eq!(
-        Err(Error::with_index("trailing characters after member", 16)),
+        Err(Error::with_index(
+            ErrorKind::ExpectedComma,
+            "trailing characters after member",
+            16
+        )),
         Parser::new(input).parse_dictionary_with_visitor(&mut Ignored)
     );
     let input = "abc=123;a=1,";
     assert_eq!(
-        Err(Error::with_index("trailing comma", 11)),
+        Err(Error::with_index(
+            ErrorKind::TrailingComma,
+            "trailing comma",
+            11
+        )),
         Parser::new(input).parse_dictionary_with_visitor(&mut Ignored)
     );
 }
@@ -385,15 +446,27 @@ fn parse_bare_item() -> Result<(), Error> {
 #[test]
 fn parse_bare_item_errors() {
     assert_eq!(
-        Err(Error::with_index("expected start of bare item", 0)),
+        Err(Error::with_index(
+            ErrorKind::ExpectedBareItem,
+            "expected start of bare item",
+            0
+        )),
         Parser::new("!?0").parse_bare_item()
     );
     assert_eq!(
-        Err(Error::with_index("expected start of bare item", 0)),
+        Err(Error::with_index(
+            ErrorKind::ExpectedBareItem,
+            "expected start of bare item",
+            0
+        )),
         Parser::new("_11abc").parse_bare_item()
     );
     assert_eq!(
-        Err(Error::with_index("expected start of bare item", 0)),
+        Err(Error::with_index(
+            ErrorKind::ExpectedBareItem,
+            "expected start of bare item",
+            0
+        )),
         Parser::new("   ").parse_bare_item()
     );
 }
@@ -413,11 +486,19 @@ fn parse_bool() -> Result<(), Error> {
 #[test]
 fn parse_bool_errors() {
     assert_eq!(
-        Err(Error::with_index("expected start of boolean ('?')", 0)),
+        Err(Error::with_index(
+            ErrorKind::ExpectedBareItem,
+            "expected start of boolean ('?')",
+            0
+        )),
         Parser::new("").parse_bool()
     );
     assert_eq!(
-        Err(Error::with_index("expected boolean ('0' or '1')", 1)),
+        Err(Error::with_index(
+            ErrorKind::InvalidBoolean,
+            "expected boolean ('0' or '1')",
+            1
+        )),
         Parser::new("?").parse_bool()
     );
 }
@@ -438,29 +519,80 @@ fn parse_string() -> Result<(), Error> {
         string_ref("some string"),
         Parser::new(r#""some string""#).parse_string()?
     );
+
+    // Strings without escape sequences are borrowed from the input.
+    assert!(matches!(
+        Parser::new(r#""a b c""#).parse_string()?,
+        Cow::Borrowed(s) if s.as_str() == "a b c"
+    ));
+    assert_eq!(
+        string_ref(r#""a\b" c\"#),
+        Parser::new(r#""\"a\\b\" c\\""#).parse_string()?
+    );
     Ok(())
 }
 
+#[test]
+fn parse_string_character_classes() {
+    for c in 0..=u8::MAX {
+        let input = [b'"', c, b'"'];
+        let expected = match c {
+            b'"' => Err(ErrorKind::TrailingCharacters),
+            // The closing quote is escaped.
+            b'\\' => Err(ErrorKind::UnterminatedString),
+            0x20..=0x7e => Ok(()),
+            _ => Err(ErrorKind::InvalidStringCharacter),
+        };
+        assert_eq!(
+            Parser::new(&input)
+                .parse_item_with_visitor(Ignored)
+                .map_err(|err| err.kind()),
+            expected,
+            "{c:#04x}"
+        );
+    }
+}
+
 #[test]
 fn parse_string_errors() {
     assert_eq!(
-        Err(Error::with_index(r#"expected start of string ('"')"#, 0)),
+        Err(Error::with_index(
+            ErrorKind::ExpectedBareItem,
+            r#"expected start of string ('"')"#,
+            0
+        )),
         Parser::new("test").parse_string()
     );
     assert_eq!(
-        Err(Error::with_index("unterminated escape sequence", 2)),
+        Err(Error::with_index(
+            ErrorKind::InvalidEscapeSequence,
+            "unterminated escape sequence",
+            2
+        )),
         Parser::new(r#""\"#).parse_string()
     );
     assert_eq!(
-        Err(Error::with_index("invalid escape sequence", 2)),
+        Err(Error::with_index(
+            ErrorKind::InvalidEscapeSequence,
+            "invalid escape sequence",
+            2
+        )),
         Parser::new(r#""\l""#).parse_string()
     );
     assert_eq!(
-        Err(Error::with_index("invalid string character", 1)),
+        Err(Error::with_index(
+            ErrorKind::InvalidStringCharacter,
+            "invalid string character",
+            1
+        )),
         Parser::new("\"\u{1f}\"").parse_string()
     );
     assert_eq!(
-        Err(Error::with_index("unterminated string", 5)),
+        Err(Error::with_index(
+            ErrorKind::UnterminatedString,
+            "unterminated string",
+            5
+        )),
         Parser::new(r#""smth"#).parse_string()
     );
 }
@@ -495,17 +627,29 @@ fn parse_token() -> Result<(), Error> {
 fn parse_token_errors() {
     let mut parser = Parser::new("765token");
     assert_eq!(
-        Err(Error::with_index("expected start of token", 0)),
+        Err(Error::with_index(
+            ErrorKind::ExpectedBareItem,
+            "expected start of token",
+            0
+        )),
         parser.parse_token()
     );
     assert_eq!(parser.remaining(), b"765token");
 
     assert_eq!(
-        Err(Error::with_index("expected start of token", 0)),
+        Err(Error::with_index(
+            ErrorKind::ExpectedBareItem,
+            "expected start of token",
+            0
+        )),
         Parser::new("7token").parse_token()
     );
     assert_eq!(
-        Err(Error::with_index("expected start of token", 0)),
+        Err(Error::with_index(
+            ErrorKind::ExpectedBareItem,
+            "expected start of token",
+            0
+        )),
         Parser::new("").parse_token()
     );
 }
@@ -536,17 +680,26 @@ fn parse_byte_sequence() -> Result<(), Error> {
 fn parse_byte_sequence_errors() {
     assert_eq!(
         Err(Error::with_index(
+            ErrorKind::ExpectedBareItem,
             "expected start of byte sequence (':')",
             0
         )),
         Parser::new("aGVsbG8").parse_byte_sequence()
     );
     assert_eq!(
-        Err(Error::with_index("invalid byte sequence", 6)),
+        Err(Error::with_index(
+            ErrorKind::InvalidBase64,
+            "invalid byte sequence",
+            6
+        )),
         Parser::new(":aGVsb G8=:").parse_byte_sequence()
     );
     assert_eq!(
-        Err(Error::with_index("unterminated byte sequence", 9)),
+        Err(Error::with_index(
+            ErrorKind::UnterminatedByteSequence,
+            "unterminated byte sequence",
+            9
+        )),
         Parser::new(":aGVsbG8=").parse_byte_sequence()
     );
 }
@@ -635,67 +788,117 @@ fn parse_number_decimal() -> Result<(), Error> {
 fn parse_number_errors() {
     let mut parser = Parser::new(":aGVsbG8:rest");
     assert_eq!(
-        Err(Error::with_index("expected digit", 0)),
+        Err(Error::with_index(
+            ErrorKind::ExpectedDigit,
+            "expected digit",
+            0
+        )),
         parser.parse_number()
     );
     assert_eq!(parser.remaining(), b":aGVsbG8:rest");
 
     let mut parser = Parser::new("-11.5555 test string");
     assert_eq!(
-        Err(Error::with_index("too many digits after decimal point", 7)),
+        Err(Error::with_index(
+            ErrorKind::InvalidDecimal,
+            "too many digits after decimal point",
+            7
+        )),
         parser.parse_number()
     );
     assert_eq!(parser.remaining(), b"5 test string");
 
     assert_eq!(
-        Err(Error::with_index("expected digit", 1)),
+        Err(Error::with_index(
+            ErrorKind::ExpectedDigit,
+            "expected digit",
+            1
+        )),
         Parser::new("--0").parse_number()
     );
     assert_eq!(
         Err(Error::with_index(
+            ErrorKind::InvalidDecimal,
             "too many digits before decimal point",
             13
         )),
         Parser::new("1999999999999.1").parse_number()
     );
     assert_eq!(
-        Err(Error::with_index("trailing decimal point", 11)),
+        Err(Error::with_index(
+            ErrorKind::InvalidDecimal,
+            "trailing decimal point",
+            11
+        )),
         Parser::new("19888899999.").parse_number()
     );
     assert_eq!(
-        Err(Error::with_index("too many digits", 15)),
+        Err(Error::with_index(
+            ErrorKind::IntegerTooLong,
+            "too many digits",
+            15
+        )),
         Parser::new("1999999999999999").parse_number()
     );
     assert_eq!(
-        Err(Error::with_index("too many digits after decimal point", 15)),
+        Err(Error::with_index(
+            ErrorKind::InvalidDecimal,
+            "too many digits after decimal point",
+            15
+        )),
         Parser::new("19999999999.99991").parse_number()
     );
     assert_eq!(
-        Err(Error::with_index("expected digit", 1)),
+        Err(Error::with_index(
+            ErrorKind::ExpectedDigit,
+            "expected digit",
+            1
+        )),
         Parser::new("- 42").parse_number()
     );
     assert_eq!(
-        Err(Error::with_index("trailing decimal point", 1)),
+        Err(Error::with_index(
+            ErrorKind::InvalidDecimal,
+            "trailing decimal point",
+            1
+        )),
         Parser::new("1..4").parse_number()
     );
     assert_eq!(
-        Err(Error::with_index("expected digit", 1)),
+        Err(Error::with_index(
+            ErrorKind::ExpectedDigit,
+            "expected digit",
+            1
+        )),
         Parser::new("-").parse_number()
     );
     assert_eq!(
-        Err(Error::with_index("trailing decimal point", 2)),
+        Err(Error::with_index(
+            ErrorKind::InvalidDecimal,
+            "trailing decimal point",
+            2
+        )),
         Parser::new("-5. 14").parse_number()
     );
     assert_eq!(
-        Err(Error::with_index("trailing decimal point", 1)),
+        Err(Error::with_index(
+            ErrorKind::InvalidDecimal,
+            "trailing decimal point",
+            1
+        )),
         Parser::new("7. 1").parse_number()
     );
     assert_eq!(
-        Err(Error::with_index("too many digits after decimal point", 6)),
+        Err(Error::with_index(
+            ErrorKind::InvalidDecimal,
+            "too many digits after decimal point",
+            6
+        )),
         Parser::new("-7.3333333333").parse_number()
     );
     assert_eq!(
         Err(Error::with_index(
+            ErrorKind::InvalidDecimal,
             "too many digits before decimal point",
             14
         )),
@@ -790,6 +993,7 @@ fn parse_key() -> Result<(), Error> {
 fn parse_key_errors() {
     assert_eq!(
         Err(Error::with_index(
+            ErrorKind::InvalidKeyCharacter,
             "expected start of key ('a'-'z' or '*')",
             0
         )),
@@ -892,36 +1096,408 @@ fn parse_display_string() -> Result<(), Error> {
 fn parse_display_string_errors() {
     assert_eq!(
         Parser::new(" %").parse_item(),
-        Err(Error::with_index(r#"expected '"'"#, 2))
+        Err(Error::with_index(
+            ErrorKind::InvalidDisplayString,
+            r#"expected '"'"#,
+            2
+        ))
     );
 
     assert_eq!(
         Parser::new(r#" %""#).parse_item(),
-        Err(Error::with_index("unterminated display string", 3))
+        Err(Error::with_index(
+            ErrorKind::InvalidDisplayString,
+            "unterminated display string",
+            3
+        ))
     );
 
     assert_eq!(
         Parser::new(r#" %"%"#).parse_item(),
-        Err(Error::with_index("unterminated escape sequence", 4))
+        Err(Error::with_index(
+            ErrorKind::InvalidEscapeSequence,
+            "unterminated escape sequence",
+            4
+        ))
     );
 
     assert_eq!(
         Parser::new(r#" %"%a"#).parse_item(),
-        Err(Error::with_index("unterminated escape sequence", 5))
+        Err(Error::with_index(
+            ErrorKind::InvalidEscapeSequence,
+            "unterminated escape sequence",
+            5
+        ))
     );
 
     assert_eq!(
         Parser::new(r#" %"%A"#).parse_item(),
-        Err(Error::with_index("invalid escape sequence", 4))
+        Err(Error::with_index(
+            ErrorKind::InvalidEscapeSequence,
+            "invalid escape sequence",
+            4
+        ))
     );
 
     assert_eq!(
         Parser::new(r#" %"%aA"#).parse_item(),
-        Err(Error::with_index("invalid escape sequence", 5))
+        Err(Error::with_index(
+            ErrorKind::InvalidEscapeSequence,
+            "invalid escape sequence",
+            5
+        ))
     );
 
     assert_eq!(
         Parser::new(r#" %"x%aa""#).parse_item(),
-        Err(Error::with_index("invalid UTF-8 in display string", 4))
+        Err(Error::with_index(
+            ErrorKind::InvalidDisplayString,
+            "invalid UTF-8 in display string",
+            4
+        ))
     );
 }
+
+#[test]
+fn error_kind_and_index() {
+    let err = Parser::new("a=1, b=2,")
+        .parse_dictionary_with_visitor(&mut Ignored)
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TrailingComma);
+    assert_eq!(err.index(), Some(8));
+
+    let err = Parser::new("1234567890123456")
+        .parse_item_with_visitor(Ignored)
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::IntegerTooLong);
+    assert_eq!(err.index(), Some(15));
+
+    let err = Parser::new(":a:")
+        .parse_item_with_visitor(Ignored)
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidBase64);
+
+    let err = KeyRef::from_str("aB").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidKeyCharacter);
+    assert_eq!(err.index(), Some(1));
+
+    let err = Integer::try_from(i64::MAX).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::OutOfRange);
+    assert_eq!(err.index(), None);
+}
+
+#[test]
+#[cfg(feature = "parsed-types")]
+fn parse_list_lenient() -> Result<(), Error> {
+    let (list, errors) =
+        Parser::new(r#" 1, "a, b" c, , (2 3;a=?x), %"%zz, x", ?1 "#).parse_list_lenient();
+    assert_eq!(
+        list,
+        vec![Item::new(1).into(), Item::new(true).into()] as List
+    );
+    assert_eq!(
+        errors,
+        vec![
+            Error::with_index(
+                ErrorKind::ExpectedComma,
+                "trailing characters after member",
+                11
+            ),
+            Error::with_index(
+                ErrorKind::ExpectedBareItem,
+                "expected start of bare item",
+                14
+            ),
+            Error::with_index(
+                ErrorKind::InvalidBoolean,
+                "expected boolean ('0' or '1')",
+                24
+            ),
+            Error::with_index(
+                ErrorKind::InvalidEscapeSequence,
+                "invalid escape sequence",
+                31
+            ),
+        ]
+    );
+
+    let (list, errors) = Parser::new("1, 2,").parse_list_lenient();
+    assert_eq!(list.len(), 2);
+    assert_eq!(
+        errors,
+        vec![Error::with_index(
+            ErrorKind::TrailingComma,
+            "trailing comma",
+            4
+        )]
+    );
+
+    let (list, errors) = Parser::new("").parse_list_lenient();
+    assert!(list.is_empty());
+    assert!(errors.is_empty());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "parsed-types")]
+fn parse_dictionary_lenient() -> Result<(), Error> {
+    let (dict, errors) =
+        Parser::new(r#"a=1, B=2, b="x, y" z, c=?, a=(4), d;e"#).parse_dictionary_lenient();
+    let mut expected = Dictionary::new();
+    expected.insert(
+        key_ref("a").to_owned(),
+        InnerList::new(vec![Item::new(4)]).into(),
+    );
+    expected.insert(
+        key_ref("d").to_owned(),
+        Item::with_params(
+            true,
+            Parameters::from_iter(vec![(key_ref("e").to_owned(), BareItem::Boolean(true))]),
+        )
+        .into(),
+    );
+    assert_eq!(dict, expected);
+    assert_eq!(
+        errors
+            .iter()
+            .map(|err| (err.kind(), err.index()))
+            .collect::<Vec<_>>(),
+        vec![
+            (ErrorKind::InvalidKeyCharacter, Some(5)),
+            (ErrorKind::ExpectedComma, Some(19)),
+            (ErrorKind::InvalidBoolean, Some(25)),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "parsed-types")]
+fn parse_duplicate_key_policy() -> Result<(), Error> {
+    let input = "a=1;x=1;y;x=2, b, a=(1 2);z;z=3";
+
+    let dict = Parser::new(input)
+        .with_duplicate_key_policy(DuplicateKeyPolicy::LastWins)
+        .parse_dictionary()?;
+    assert_eq!(dict, Parser::new(input).parse_dictionary()?);
+    assert_eq!(dict.serialize_value()?, "a=(1 2);z=3, b");
+
+    let dict = Parser::new(input)
+        .with_duplicate_key_policy(DuplicateKeyPolicy::FirstWins)
+        .parse_dictionary()?;
+    assert_eq!(dict.serialize_value()?, "a=1;x=1;y, b");
+
+    let list = Parser::new("1;a=1;a=2, (2;b;b=?0);c=1;c=2")
+        .with_duplicate_key_policy(DuplicateKeyPolicy::FirstWins)
+        .parse_list()?;
+    assert_eq!(list.serialize_value()?, "1;a=1, (2;b);c=1");
+
+    let item = Parser::new("1;a=1;a=2")
+        .with_duplicate_key_policy(DuplicateKeyPolicy::FirstWins)
+        .parse_item()?;
+    assert_eq!(item.serialize_value()?, "1;a=1");
+
+    // Ignored values must still be valid.
+    let err = Parser::new("a=1, a=?2")
+        .with_duplicate_key_policy(DuplicateKeyPolicy::FirstWins)
+        .parse_dictionary()
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidBoolean);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "parsed-types")]
+fn parse_duplicate_key_policy_reject() -> Result<(), Error> {
+    for (input, index, previous_index) in [
+        ("a=1, b, a=2", 8, 0),
+        ("a=1;x;y;x=2", 8, 4),
+        ("a=(1;x; x)", 8, 5),
+        ("a=();x;x", 7, 5),
+    ] {
+        let err = Parser::new(input)
+            .with_duplicate_key_policy(DuplicateKeyPolicy::Reject)
+            .parse_dictionary()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DuplicateKey, "{input}");
+        assert_eq!(err.index(), Some(index), "{input}");
+        assert_eq!(err.previous_index(), Some(previous_index), "{input}");
+    }
+
+    let err = Parser::new("1;a;a")
+        .with_duplicate_key_policy(DuplicateKeyPolicy::Reject)
+        .parse_list()
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::DuplicateKey);
+    assert_eq!(err.render("1;a;a"), "error: duplicate key at index 4\n  | 1;a;a\n  |     ^ expected a key that has not appeared in this scope\n");
+
+    // Keys are only compared within the same scope.
+    let input = "a=1;x, b=1;x;a, c=(1;x 2;x);x";
+    let dict = Parser::new(input)
+        .with_duplicate_key_policy(DuplicateKeyPolicy::Reject)
+        .parse_dictionary()?;
+    assert_eq!(dict.serialize_value()?, "a=1;x, b=1;x;a, c=(1;x 2;x);x");
+
+    let err = Parser::new("a=1, a=2")
+        .parse_dictionary_with_visitor(&mut Ignored)
+        .and_then(|()| {
+            Parser::new("a=1, a=2")
+                .with_duplicate_key_policy(DuplicateKeyPolicy::Reject)
+                .parse_dictionary_with_visitor(&mut Ignored)
+        })
+        .unwrap_err();
+    assert_eq!(err.previous_index(), Some(0));
+    assert_eq!(Error::out_of_range().previous_index(), None);
+    Ok(())
+}
+
+#[test]
+fn parse_duplicate_key_policy_many_keys() {
+    let mut input = (0..10_000)
+        .map(|i| format!("k{i}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    assert_eq!(
+        Parser::new(&input)
+            .with_duplicate_key_policy(DuplicateKeyPolicy::Reject)
+            .parse_dictionary_with_visitor(&mut Ignored),
+        Ok(())
+    );
+
+    input.push_str(", k5000");
+    let err = Parser::new(&input)
+        .with_duplicate_key_policy(DuplicateKeyPolicy::Reject)
+        .parse_dictionary_with_visitor(&mut Ignored)
+        .unwrap_err();
+    assert_eq!(err.index(), Some(input.len() - 5));
+    assert_eq!(err.previous_index(), input.find("k5000"));
+}
+
+#[test]
+#[cfg(feature = "parsed-types")]
+fn parse_dictionary_lenient_duplicate_key_policy() {
+    let (dict, errors) = Parser::new("a=?2, a=1, b=1, b=2")
+        .with_duplicate_key_policy(DuplicateKeyPolicy::Reject)
+        .parse_dictionary_lenient();
+    assert_eq!(dict.serialize_value().unwrap(), "a=1, b=1");
+    assert_eq!(
+        errors
+            .iter()
+            .map(|err| (err.kind(), err.index(), err.previous_index()))
+            .collect::<Vec<_>>(),
+        vec![
+            (ErrorKind::InvalidBoolean, Some(3), None),
+            (ErrorKind::DuplicateKey, Some(16), Some(11)),
+        ]
+    );
+}
+
+#[test]
+fn parse_limits() {
+    let limits = ParserLimits {
+        max_input_len: 32,
+        max_members: 2,
+        max_inner_list_len: 2,
+        max_params: 2,
+        max_byte_sequence_len: 4,
+        max_string_len: 3,
+    };
+
+    for (input, index) in [
+        ("a=1, b=2, c=3", 10),
+        ("a, b=(1 2 3)", 10),
+        ("a;x;y;z", 5),
+        ("a=(1;x;y;z)", 8),
+        ("a=();x;y;z", 8),
+        ("a=:AQIDBAU=:", 2),
+        ("a=:AQIDBAU:", 2),
+        (r#"a="abcd""#, 2),
+        (r#"a="ab\"d""#, 2),
+        (r#"a=%"%c3%a9%c3%a9""#, 2),
+        ("a=%\"abcd\"", 2),
+        ("a=tok, b=tok, c=tok, d=tok, e=tok", 32),
+    ] {
+        let err = Parser::new(input)
+            .with_limits(limits)
+            .parse_dictionary_with_visitor(&mut Ignored)
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::LimitExceeded, "{input}");
+        assert_eq!(err.index(), Some(index), "{input}");
+    }
+
+    // Values at the limits are accepted.
+    for input in [
+        "a=1, b=2",
+        "a=(1 2);x;y",
+        "a=(1;x;y 2)",
+        "a=:AQIDBA==:",
+        "a=:AQIDBA:",
+        r#"a="ab\"""#,
+        r#"a=%"%c3%a9a""#,
+        "a=:AQIDBA==:, b=\"abc\"",
+    ] {
+        assert_eq!(
+            Parser::new(input)
+                .with_limits(limits)
+                .parse_dictionary_with_visitor(&mut Ignored),
+            Ok(()),
+            "{input}"
+        );
+    }
+
+    assert_eq!(
+        Parser::new("(1 2 3)")
+            .with_limits(limits)
+            .parse_list_with_visitor(&mut Ignored)
+            .map_err(|err| err.kind()),
+        Err(ErrorKind::LimitExceeded)
+    );
+}
+
+#[test]
+#[cfg(feature = "parsed-types")]
+fn parse_dictionary_lenient_limits() {
+    let limits = ParserLimits {
+        max_members: 2,
+        max_string_len: 1,
+        ..ParserLimits::default()
+    };
+    let (dict, errors) = Parser::new(r#"a="xy", b=1, c=2, d=3"#)
+        .with_limits(limits)
+        .parse_dictionary_lenient();
+    assert_eq!(dict.serialize_value().unwrap(), "b=1");
+    assert_eq!(
+        errors
+            .iter()
+            .map(|err| (err.kind(), err.index()))
+            .collect::<Vec<_>>(),
+        vec![
+            (ErrorKind::LimitExceeded, Some(2)),
+            (ErrorKind::LimitExceeded, Some(13)),
+        ]
+    );
+}
+
+#[test]
+#[cfg(feature = "parsed-types")]
+fn parse_lenient_input_len_limit() {
+    let limits = ParserLimits {
+        max_input_len: 4,
+        ..ParserLimits::default()
+    };
+
+    let (list, errors) = Parser::new("1, 2, 3")
+        .with_limits(limits)
+        .parse_list_lenient();
+    assert!(list.is_empty());
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind(), ErrorKind::LimitExceeded);
+    assert_eq!(errors[0].index(), Some(4));
+
+    let (dict, errors) = Parser::new("a, b, c")
+        .with_limits(limits)
+        .parse_dictionary_lenient();
+    assert!(dict.is_empty());
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind(), ErrorKind::LimitExceeded);
+}
@@ -1,13 +1,16 @@
 use crate::{
-    key_ref, string_ref, token_ref, Decimal, DictSerializer, ItemSerializer, ListSerializer,
-    SFVResult,
+    key_ref, string_ref, token_ref, Decimal, DictSerializer, FmtOutput, ItemSerializer,
+    ListSerializer, Output, SFVResult,
 };
 
+#[cfg(feature = "std")]
+use crate::IoOutput;
+
 use std::borrow::BorrowMut;
 
 #[test]
 fn test_fast_serialize_item() {
-    fn check(ser: ItemSerializer<impl BorrowMut<String>>) {
+    fn check(ser: ItemSerializer<impl Output + BorrowMut<String>>) {
         let output = ser
             .bare_item(token_ref("hello"))
             .parameter(key_ref("abc"), true)
@@ -21,7 +24,7 @@ fn test_fast_serialize_item() {
 
 #[test]
 fn test_fast_serialize_list() -> SFVResult<()> {
-    fn check(mut ser: ListSerializer<impl BorrowMut<String>>) -> SFVResult<()> {
+    fn check(mut ser: ListSerializer<impl Output + BorrowMut<String>>) -> SFVResult<()> {
         ser.bare_item(token_ref("hello"))
             .parameter(key_ref("key1"), true)
             .parameter(key_ref("key2"), false);
@@ -50,7 +53,7 @@ fn test_fast_serialize_list() -> SFVResult<()> {
 
 #[test]
 fn test_fast_serialize_dict() -> SFVResult<()> {
-    fn check(mut ser: DictSerializer<impl BorrowMut<String>>) -> SFVResult<()> {
+    fn check(mut ser: DictSerializer<impl Output + BorrowMut<String>>) -> SFVResult<()> {
         ser.bare_item(key_ref("member1"), token_ref("hello"))
             .parameter(key_ref("key1"), true)
             .parameter(key_ref("key2"), false);
@@ -114,3 +117,86 @@ fn test_with_buffer_separator() -> SFVResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_inner_list_separator_with_buffer() {
+    let mut output = String::from("(");
+    {
+        let mut ser = ListSerializer::with_buffer(&mut output);
+        let mut ser = ser.inner_list();
+        ser.bare_item(1);
+        ser.bare_item(2);
+    }
+    assert_eq!(output, "((1 2)");
+}
+
+#[test]
+fn test_serialize_long_byte_sequence() {
+    let value: Vec<u8> = (0..=255).collect();
+    let output = ItemSerializer::new().bare_item(&value[..]).finish();
+    assert_eq!(
+        output,
+        format!(
+            ":{}:",
+            base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &value)
+        )
+    );
+}
+
+#[test]
+fn test_serialize_into_vec() -> SFVResult<()> {
+    let mut ser = DictSerializer::with_output(Vec::new());
+    ser.bare_item(key_ref("a"), string_ref("x"));
+    ser.inner_list(key_ref("b")).bare_item(1);
+    assert_eq!(ser.finish()?, br#"a="x", b=(1)"#);
+    Ok(())
+}
+
+#[test]
+fn test_serialize_into_fmt_write() -> SFVResult<()> {
+    struct Limited(String, usize);
+
+    impl std::fmt::Write for Limited {
+        fn write_str(&mut self, s: &str) -> std::fmt::Result {
+            if self.0.len() + s.len() > self.1 {
+                return Err(std::fmt::Error);
+            }
+            self.0.push_str(s);
+            Ok(())
+        }
+    }
+
+    let mut ser = ListSerializer::with_output(FmtOutput::new(Limited(String::new(), 10)));
+    ser.bare_item(token_ref("abc")).parameter(key_ref("d"), 1);
+    let output = ser.finish()?.into_inner().unwrap();
+    assert_eq!(output.0, "abc;d=1");
+
+    let mut ser = ListSerializer::with_output(FmtOutput::new(Limited(String::new(), 10)));
+    ser.bare_item(token_ref("abc")).parameter(key_ref("d"), 1);
+    ser.bare_item(token_ref("efg"));
+    ser.bare_item(1);
+    assert!(ser.finish()?.into_inner().is_err());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_serialize_into_io_write() -> SFVResult<()> {
+    let mut buf = [0; 8];
+
+    let output = ItemSerializer::with_output(IoOutput::new(&mut buf[..]))
+        .bare_item(1)
+        .parameter(key_ref("a"), 2)
+        .finish();
+    assert!(output.into_inner().is_ok());
+    assert_eq!(&buf[..5], b"1;a=2");
+
+    let output = ItemSerializer::with_output(IoOutput::new(&mut buf[..]))
+        .bare_item(string_ref("too long"))
+        .finish();
+    assert_eq!(
+        output.into_inner().unwrap_err().kind(),
+        std::io::ErrorKind::WriteZero
+    );
+    Ok(())
+}
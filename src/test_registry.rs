@@ -0,0 +1,106 @@
+use crate::registry::{self, FieldType, FieldValue, Registry};
+use crate::{ErrorKind, Parser, SFVResult, SerializeValue};
+
+#[test]
+fn test_fields_sorted() {
+    for window in registry::FIELDS.windows(2) {
+        assert!(window[0].0 < window[1].0, "{}", window[1].0);
+    }
+    for (name, field_type) in registry::FIELDS {
+        assert_eq!(*name, name.to_ascii_lowercase());
+        assert_eq!(registry::field_type(name), Some(*field_type));
+    }
+}
+
+#[test]
+fn test_field_type() {
+    assert_eq!(
+        registry::field_type("Cache-Control"),
+        Some(FieldType::Dictionary)
+    );
+    assert_eq!(
+        registry::field_type("ACCEPT-ENCODING"),
+        Some(FieldType::List)
+    );
+    assert_eq!(registry::field_type("Content-Type"), Some(FieldType::Item));
+    assert_eq!(registry::field_type("cache-controls"), None);
+    assert_eq!(registry::field_type("date"), None);
+    assert_eq!(registry::field_type(""), None);
+}
+
+#[test]
+fn test_parse_field() -> SFVResult<()> {
+    assert_eq!(
+        registry::parse_field("Cache-Control", "max-age=60, private")?,
+        FieldValue::Dictionary(Parser::new("max-age=60, private").parse_dictionary()?)
+    );
+    assert_eq!(
+        registry::parse_field("vary", "accept-encoding, origin")?,
+        FieldValue::List(Parser::new("accept-encoding, origin").parse_list()?)
+    );
+    assert_eq!(
+        registry::parse_field("content-type", "text/html;charset=utf-8")?,
+        FieldValue::Item(Parser::new("text/html;charset=utf-8").parse_item()?)
+    );
+
+    let err = registry::parse_field("x-unknown", "1").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TypeMismatch);
+    assert_eq!(err.to_string(), "unknown structured field name");
+
+    let err = registry::parse_field("content-type", "a, b").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TrailingCharacters);
+    Ok(())
+}
+
+#[test]
+fn test_field_value() -> SFVResult<()> {
+    let value = registry::parse_field("priority", "u=1,  i")?;
+    assert_eq!(value.field_type(), FieldType::Dictionary);
+    assert_eq!(value.serialize_value()?, "u=1, i");
+    Ok(())
+}
+
+#[test]
+fn test_registry() -> SFVResult<()> {
+    let mut registry = Registry::new();
+    assert_eq!(registry.field_type("Accept"), Some(FieldType::List));
+    assert_eq!(registry.field_type("x-b"), None);
+
+    assert_eq!(registry.insert("X-B", FieldType::Item), None);
+    assert_eq!(registry.insert("x-c", FieldType::List), None);
+    assert_eq!(registry.insert("x-a", FieldType::Dictionary), None);
+    assert_eq!(
+        registry.insert("x-b", FieldType::List),
+        Some(FieldType::Item)
+    );
+    assert_eq!(registry.insert("accept", FieldType::Item), None);
+
+    assert_eq!(registry.field_type("x-a"), Some(FieldType::Dictionary));
+    assert_eq!(registry.field_type("X-B"), Some(FieldType::List));
+    assert_eq!(registry.field_type("x-c"), Some(FieldType::List));
+    assert_eq!(registry.field_type("Accept"), Some(FieldType::Item));
+    assert_eq!(
+        registry.field_type("cache-control"),
+        Some(FieldType::Dictionary)
+    );
+
+    assert_eq!(
+        registry.parse_field("x-a", "a=1")?,
+        FieldValue::Dictionary(Parser::new("a=1").parse_dictionary()?)
+    );
+    assert_eq!(
+        registry.parse_field("x-d", "1").unwrap_err().kind(),
+        ErrorKind::TypeMismatch
+    );
+    Ok(())
+}
+
+#[test]
+fn test_field_type_parse() -> SFVResult<()> {
+    let parser = Parser::new("@1").with_version(crate::Version::Rfc8941);
+    assert_eq!(
+        FieldType::Item.parse(parser).unwrap_err().kind(),
+        ErrorKind::UnsupportedByVersion
+    );
+    Ok(())
+}
@@ -0,0 +1,323 @@
+use crate::{
+    from_str_dictionary, from_str_item, from_str_list, to_string_dictionary, to_string_item,
+    to_string_list, Error, ErrorKind,
+};
+
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::BTreeMap;
+
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+#[test]
+fn serialize_item() -> Result<(), Error> {
+    assert_eq!(to_string_item(&12)?, "12");
+    assert_eq!(to_string_item(&-12_i64)?, "-12");
+    assert_eq!(to_string_item(&12.5)?, "12.5");
+    assert_eq!(to_string_item(&true)?, "?1");
+    assert_eq!(to_string_item(&false)?, "?0");
+    assert_eq!(to_string_item(r#"a"b\c"#)?, r#""a\"b\\c""#);
+    assert_eq!(to_string_item(&'x')?, r#""x""#);
+    assert_eq!(to_string_item(&Bytes(b"hello"))?, ":aGVsbG8=:");
+    assert_eq!(to_string_item(&Some(1))?, "1");
+    Ok(())
+}
+
+#[test]
+fn serialize_item_with_params() -> Result<(), Error> {
+    #[derive(Serialize)]
+    struct Params {
+        q: f64,
+        x: bool,
+        y: Option<i64>,
+    }
+
+    assert_eq!(
+        to_string_item(&(
+            "tok",
+            Params {
+                q: 0.5,
+                x: true,
+                y: None
+            }
+        ))?,
+        r#""tok";q=0.5;x"#
+    );
+    assert_eq!(
+        to_string_item(&(1, BTreeMap::from([("a", 1), ("b", 2)])))?,
+        "1;a=1;b=2"
+    );
+    assert_eq!(
+        to_string_item(&(1, BTreeMap::from([("b", Bytes(b"hi"))])))?,
+        "1;b=:aGk=:"
+    );
+    assert_eq!(to_string_item(&(1, ()))?, "1");
+    Ok(())
+}
+
+#[test]
+fn serialize_enum() -> Result<(), Error> {
+    #[derive(Serialize)]
+    #[serde(rename_all = "kebab-case")]
+    enum Policy {
+        RequireCorp,
+    }
+
+    assert_eq!(to_string_item(&Policy::RequireCorp)?, "require-corp");
+    Ok(())
+}
+
+#[test]
+fn serialize_list() -> Result<(), Error> {
+    assert_eq!(to_string_list(&[1, 2, 3])?, "1, 2, 3");
+    assert_eq!(to_string_list(&(1, "a"))?, r#"1, "a""#);
+    assert_eq!(
+        to_string_list(&[vec![1, 2], vec![], vec![3]])?,
+        "(1 2), (), (3)"
+    );
+    assert_eq!(
+        to_string_list(&[(vec![1, 2], BTreeMap::from([("a", 1)]))])?,
+        "(1 2);a=1"
+    );
+    assert_eq!(
+        to_string_list(&[vec![(1, BTreeMap::from([("a", false)]))]])?,
+        "(1;a=?0)"
+    );
+    assert_eq!(to_string_list(&[Some(1), None, Some(3)])?, "1, 3");
+    Ok(())
+}
+
+#[test]
+fn serialize_dictionary() -> Result<(), Error> {
+    #[derive(Serialize)]
+    struct Dict<'a> {
+        a: i64,
+        b: bool,
+        c: Option<&'a str>,
+        #[serde(rename = "d-e")]
+        d_e: Vec<f64>,
+        missing: Option<i64>,
+        f: (u8, BTreeMap<&'a str, &'a str>),
+    }
+
+    assert_eq!(
+        to_string_dictionary(&Dict {
+            a: 1,
+            b: true,
+            c: Some("x"),
+            d_e: vec![1.5, 2.0],
+            missing: None,
+            f: (0, BTreeMap::from([("p", "q")])),
+        })?,
+        r#"a=1, b, c="x", d-e=(1.5 2.0), f=0;p="q""#
+    );
+
+    assert_eq!(
+        to_string_dictionary(&BTreeMap::from([("b", false), ("a", true)]))?,
+        "a, b=?0"
+    );
+    Ok(())
+}
+
+#[test]
+fn serialize_round_trip() -> Result<(), Error> {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    enum Mode {
+        Fast,
+        Slow,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Params {
+        q: f64,
+        x: Option<bool>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Dict {
+        a: i64,
+        b: bool,
+        c: String,
+        #[serde(serialize_with = "serialize_bytes")]
+        d: Vec<u8>,
+        e: Option<Mode>,
+        f: (Vec<i64>, Params),
+        g: Option<i64>,
+    }
+
+    fn serialize_bytes<S: Serializer>(v: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        Bytes(v).serialize(serializer)
+    }
+
+    let dict = Dict {
+        a: -1,
+        b: false,
+        c: "x y".to_owned(),
+        d: b"hello".to_vec(),
+        e: Some(Mode::Slow),
+        f: (
+            vec![1, 2],
+            Params {
+                q: 0.25,
+                x: Some(true),
+            },
+        ),
+        g: None,
+    };
+    let serialized = to_string_dictionary(&dict)?;
+    assert_eq!(
+        serialized,
+        r#"a=-1, b=?0, c="x y", d=:aGVsbG8=:, e=slow, f=(1 2);q=0.25;x"#
+    );
+    assert_eq!(from_str_dictionary::<Dict>(&serialized)?, dict);
+
+    let list = vec![(Mode::Fast, Params { q: 1.0, x: None })];
+    let serialized = to_string_list(&list)?;
+    assert_eq!(serialized, "fast;q=1.0");
+    assert_eq!(from_str_list::<Vec<(Mode, Params)>>(&serialized)?, list);
+
+    let serialized = to_string_item(&Bytes(&[0, 255]))?;
+    assert_eq!(serialized, ":AP8=:");
+    assert_eq!(from_str_item::<Vec<u8>>(&serialized)?, [0, 255]);
+    Ok(())
+}
+
+#[test]
+fn serialize_misbehaving_impls() {
+    use serde::ser::{SerializeMap, SerializeTuple};
+
+    struct EmptyPair;
+
+    impl Serialize for EmptyPair {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_tuple(2)?.end()
+        }
+    }
+
+    struct ValueWithoutKey;
+
+    impl Serialize for ValueWithoutKey {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut map = serializer.serialize_map(None)?;
+            map.serialize_value(&1)?;
+            map.end()
+        }
+    }
+
+    struct IgnoredError;
+
+    impl Serialize for IgnoredError {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut map = serializer.serialize_map(None)?;
+            let _ = map.serialize_entry("a", &f64::NAN);
+            map.serialize_entry("b", &1)?;
+            map.end()
+        }
+    }
+
+    let message = |result: Result<String, Error>| result.unwrap_err().to_string();
+
+    assert_eq!(
+        message(to_string_item(&EmptyPair)),
+        "expected (value, parameters)"
+    );
+    assert_eq!(
+        message(to_string_dictionary(&ValueWithoutKey)),
+        "expected key before value"
+    );
+    assert_eq!(
+        message(to_string_item(&(1, ValueWithoutKey))),
+        "expected key before value"
+    );
+    assert_eq!(
+        message(to_string_item(&(1, IgnoredError))),
+        "cannot serialize parameters after an error"
+    );
+}
+
+#[test]
+fn serialize_errors() {
+    #[derive(Serialize)]
+    struct InvalidKey {
+        #[serde(rename = "A")]
+        a: i64,
+    }
+
+    assert_eq!(
+        to_string_item(&1_000_000_000_000_000_i64),
+        Err(Error::out_of_range())
+    );
+    assert_eq!(to_string_item(&1e12), Err(Error::out_of_range()));
+    assert_eq!(
+        to_string_item(&f64::NAN),
+        Err(Error::new(ErrorKind::NaN, "NaN"))
+    );
+    assert_eq!(
+        to_string_item("\x7f"),
+        Err(Error::with_index(
+            ErrorKind::InvalidStringCharacter,
+            "invalid character",
+            0
+        ))
+    );
+    assert_eq!(
+        to_string_item(&None::<i64>),
+        Err(Error::new(
+            ErrorKind::TypeMismatch,
+            "serializing None as an item is not allowed"
+        ))
+    );
+    assert_eq!(
+        to_string_item(&vec![1, 2]),
+        Err(Error::new(
+            ErrorKind::TypeMismatch,
+            "cannot serialize inner list as item"
+        ))
+    );
+    assert_eq!(
+        to_string_list(&[[[1]]]),
+        Err(Error::new(
+            ErrorKind::TypeMismatch,
+            "inner lists cannot be nested"
+        ))
+    );
+    assert_eq!(
+        to_string_list(&Vec::<i64>::new()),
+        Err(Error::new(
+            ErrorKind::EmptyList,
+            "serializing empty list is not allowed"
+        ))
+    );
+    assert_eq!(
+        to_string_dictionary(&BTreeMap::<&str, i64>::new()),
+        Err(Error::new(
+            ErrorKind::EmptyDictionary,
+            "serializing empty dictionary is not allowed"
+        ))
+    );
+    assert_eq!(
+        to_string_dictionary(&InvalidKey { a: 1 }),
+        Err(Error::with_index(
+            ErrorKind::InvalidKeyCharacter,
+            "invalid character",
+            0
+        ))
+    );
+    assert_eq!(
+        to_string_dictionary(&BTreeMap::from([(1, 1)])),
+        Err(Error::new(ErrorKind::TypeMismatch, "key must be a string"))
+    );
+    assert_eq!(
+        to_string_dictionary(&[1]),
+        Err(Error::new(
+            ErrorKind::TypeMismatch,
+            "dictionary must be a map or struct"
+        ))
+    );
+}
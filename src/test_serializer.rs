@@ -2,14 +2,17 @@ use crate::serializer::Serializer;
 use crate::{integer, key_ref, string_ref, token_ref, Date, Decimal, Error};
 
 #[cfg(feature = "parsed-types")]
-use crate::{BareItem, Dictionary, InnerList, Item, List, Parameters, SerializeValue};
+use crate::{BareItem, Dictionary, ErrorKind, InnerList, Item, List, Parameters, SerializeValue};
 
 #[test]
 #[cfg(feature = "parsed-types")]
 fn serialize_value_empty_dict() {
     let dict_field_value = Dictionary::new();
     assert_eq!(
-        Err(Error::new("serializing empty dictionary is not allowed")),
+        Err(Error::new(
+            ErrorKind::EmptyDictionary,
+            "serializing empty dictionary is not allowed"
+        )),
         dict_field_value.serialize_value()
     );
 }
@@ -19,7 +22,10 @@ fn serialize_value_empty_dict() {
 fn serialize_value_empty_list() {
     let list_field_value = List::new();
     assert_eq!(
-        Err(Error::new("serializing empty list is not allowed")),
+        Err(Error::new(
+            ErrorKind::EmptyList,
+            "serializing empty list is not allowed"
+        )),
         list_field_value.serialize_value()
     );
 }
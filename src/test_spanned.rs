@@ -0,0 +1,208 @@
+use crate::spanned::{DictionaryMember, Item, ListEntry, Parameter, Spanned};
+use crate::visitor::Ignored;
+use crate::{key_ref, BareItem, DuplicateKeyPolicy, Error, ErrorKind, Parser, ParserLimits};
+
+fn spanned<T>(value: impl Into<T>, span: std::ops::Range<usize>) -> Spanned<T> {
+    Spanned {
+        value: value.into(),
+        span,
+    }
+}
+
+#[test]
+fn parse_item_spanned() -> Result<(), Error> {
+    let item = Parser::new(" 12;a=tok; b ").parse_item_spanned()?;
+    assert_eq!(
+        item,
+        Item {
+            bare_item: spanned(12, 1..3),
+            params: vec![
+                Parameter {
+                    key: spanned(key_ref("a").to_owned(), 4..5),
+                    value: spanned(crate::token_ref("tok"), 6..9),
+                    span: 3..9,
+                },
+                Parameter {
+                    key: spanned(key_ref("b").to_owned(), 11..12),
+                    value: spanned(true, 12..12),
+                    span: 9..12,
+                },
+            ],
+            span: 1..12,
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_list_spanned() -> Result<(), Error> {
+    let input = r#"1,  ( "a"  b;c );d, ()"#;
+    let list = Parser::new(input).parse_list_spanned()?;
+    assert_eq!(list.len(), 3);
+    assert_eq!(&input[list[0].span()], "1");
+    assert_eq!(&input[list[1].span()], r#"( "a"  b;c );d"#);
+    assert_eq!(&input[list[2].span()], "()");
+
+    let ListEntry::InnerList(inner_list) = &list[1] else {
+        panic!("expected inner list");
+    };
+    assert_eq!(inner_list.items.len(), 2);
+    assert_eq!(&input[inner_list.items[0].span.clone()], r#""a""#);
+    assert_eq!(&input[inner_list.items[1].span.clone()], "b;c");
+    assert_eq!(&input[inner_list.params[0].span.clone()], ";d");
+    Ok(())
+}
+
+#[test]
+fn parse_dictionary_spanned() -> Result<(), Error> {
+    let input = "a=?0, b;x=1, a=:AQI=:";
+    let dict = Parser::new(input).parse_dictionary_spanned()?;
+    assert_eq!(dict.len(), 3);
+    assert_eq!(
+        dict[1],
+        DictionaryMember {
+            key: spanned(key_ref("b").to_owned(), 6..7),
+            value: ListEntry::Item(Item {
+                bare_item: spanned(true, 7..7),
+                params: vec![Parameter {
+                    key: spanned(key_ref("x").to_owned(), 8..9),
+                    value: spanned(1, 10..11),
+                    span: 7..11,
+                }],
+                span: 7..11,
+            }),
+            span: 6..11,
+        }
+    );
+    assert_eq!(&input[dict[2].span.clone()], "a=:AQI=:");
+    assert_eq!(
+        dict[2].value,
+        ListEntry::Item(Item {
+            bare_item: spanned(BareItem::ByteSequence(vec![1, 2]), 15..21),
+            params: vec![],
+            span: 15..21,
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_spanned_errors() {
+    assert_eq!(
+        Parser::new("(1 2").parse_list_spanned(),
+        Err(Error::with_index(
+            ErrorKind::InvalidInnerList,
+            "unterminated inner list",
+            4
+        ))
+    );
+    assert_eq!(
+        Parser::new("a=1 b").parse_dictionary_spanned(),
+        Err(Error::with_index(
+            ErrorKind::ExpectedComma,
+            "trailing characters after member",
+            4
+        ))
+    );
+    assert_eq!(
+        Parser::new("1 2").parse_item_spanned(),
+        Err(Error::with_index(
+            ErrorKind::TrailingCharacters,
+            "trailing characters after parsed value",
+            2
+        ))
+    );
+}
+
+#[test]
+fn parse_spanned_nested() -> Result<(), Error> {
+    let input = "a=(1;x  ?0;y=%\"z\");p;q=:AQ==:, b;c=tok, d=()";
+    let dict = Parser::new(input).parse_dictionary_spanned()?;
+    let spans = |span: std::ops::Range<usize>| &input[span];
+
+    assert_eq!(
+        spans(dict[0].span.clone()),
+        r#"a=(1;x  ?0;y=%"z");p;q=:AQ==:"#
+    );
+    let ListEntry::InnerList(inner_list) = &dict[0].value else {
+        panic!("expected inner list");
+    };
+    assert_eq!(
+        spans(inner_list.span.clone()),
+        r#"(1;x  ?0;y=%"z");p;q=:AQ==:"#
+    );
+    assert_eq!(spans(inner_list.items[0].span.clone()), "1;x");
+    assert_eq!(spans(inner_list.items[1].span.clone()), r#"?0;y=%"z""#);
+    assert_eq!(spans(inner_list.items[1].bare_item.span.clone()), "?0");
+    assert_eq!(
+        spans(inner_list.items[1].params[0].value.span.clone()),
+        r#"%"z""#
+    );
+    assert_eq!(spans(inner_list.params[1].span.clone()), ";q=:AQ==:");
+    assert_eq!(spans(inner_list.params[1].key.span.clone()), "q");
+
+    assert_eq!(spans(dict[1].span.clone()), "b;c=tok");
+    assert_eq!(spans(dict[1].value.span()), ";c=tok");
+    assert_eq!(spans(dict[2].span.clone()), "d=()");
+    assert_eq!(spans(dict[2].value.span()), "()");
+    Ok(())
+}
+
+#[test]
+fn parse_spanned_matches_parser() {
+    let limits = ParserLimits {
+        max_params: 1,
+        ..ParserLimits::default()
+    };
+    for input in [
+        "a=1;x;y",
+        "a=(1 2",
+        "a=1, a=2;b;b",
+        "a=?2",
+        "a=:AQ=I:",
+        "a=\"\\x\"",
+        "A=1",
+        "a=1,",
+    ] {
+        for policy in [
+            DuplicateKeyPolicy::LastWins,
+            DuplicateKeyPolicy::FirstWins,
+            DuplicateKeyPolicy::Reject,
+        ] {
+            let parser = || {
+                Parser::new(input)
+                    .with_duplicate_key_policy(policy)
+                    .with_limits(limits)
+            };
+            assert_eq!(
+                parser().parse_dictionary_spanned().map(|_| ()),
+                parser().parse_dictionary_with_visitor(&mut Ignored),
+                "{input}"
+            );
+        }
+    }
+}
+
+#[test]
+fn parse_spanned_duplicate_keys() -> Result<(), Error> {
+    let input = "a=1;x;x=2, b, a=3";
+    let dict = Parser::new(input).parse_dictionary_spanned()?;
+    assert_eq!(dict.len(), 3);
+    assert_eq!(dict[0].value.span(), 2..9);
+    let ListEntry::Item(item) = &dict[0].value else {
+        panic!("expected item");
+    };
+    assert_eq!(item.params.len(), 2);
+
+    let dict = Parser::new(input)
+        .with_duplicate_key_policy(DuplicateKeyPolicy::FirstWins)
+        .parse_dictionary_spanned()?;
+    assert_eq!(dict.len(), 2);
+    assert_eq!(&input[dict[1].span.clone()], "b");
+    let ListEntry::Item(item) = &dict[0].value else {
+        panic!("expected item");
+    };
+    assert_eq!(item.params.len(), 1);
+    assert_eq!(item.span, 2..9);
+    Ok(())
+}
@@ -0,0 +1,306 @@
+use crate::visitor::Ignored;
+use crate::{DuplicateKeyPolicy, Error, ErrorKind, ParserLimits, StreamingParser};
+
+#[cfg(feature = "parsed-types")]
+use crate::{Dictionary, Item, List, Parser, SerializeValue, Version};
+
+fn need_more_input(index: usize) -> Error {
+    Error::with_index(ErrorKind::NeedMoreInput, "incomplete input", index)
+}
+
+#[test]
+#[cfg(feature = "parsed-types")]
+fn streaming_list_members() {
+    let mut parser = StreamingParser::new();
+    let mut len = 0;
+    let mut list = List::new();
+
+    for (chunk, expected_members) in [
+        (" ", 0),
+        ("1", 0),
+        ("2;a", 0),
+        (" ", 1),
+        ("\t,", 1),
+        (" (", 1),
+        ("\"x,y\"", 1),
+        (")", 1),
+        (";b=:AQ", 1),
+        ("I=:,", 2),
+        (" tok", 2),
+    ] {
+        parser.feed(chunk);
+        len += chunk.len();
+        assert_eq!(
+            parser.parse_list_with_visitor(&mut list),
+            Err(need_more_input(len)),
+            "{chunk:?}"
+        );
+        assert_eq!(list.len(), expected_members, "{chunk:?}");
+    }
+
+    parser.finish();
+    assert!(parser.is_finished());
+    assert_eq!(parser.parse_list_with_visitor(&mut list), Ok(()));
+    assert_eq!(list.len(), 3);
+}
+
+#[test]
+fn streaming_errors() {
+    for (chunks, err) in [
+        (&["a=1", "b"][..], ErrorKind::ExpectedComma),
+        (&["a=1, ", "b=?", "2"], ErrorKind::InvalidBoolean),
+        (&["a=1, b=1.", "2345"], ErrorKind::InvalidDecimal),
+        (&["a=\"\x01"], ErrorKind::InvalidStringCharacter),
+        (&["a=(1 2", "x"], ErrorKind::InvalidInnerList),
+        (&["\t"], ErrorKind::InvalidKeyCharacter),
+    ] {
+        let mut parser = StreamingParser::new();
+        let mut result = Ok(());
+        for chunk in chunks {
+            parser.feed(chunk);
+            result = parser.parse_dictionary_with_visitor(&mut Ignored);
+        }
+        assert_eq!(result.map_err(|err| err.kind()), Err(err), "{chunks:?}");
+    }
+}
+
+#[test]
+fn streaming_finish_errors() {
+    for (input, err) in [
+        (
+            "a=1,",
+            Error::with_index(ErrorKind::TrailingComma, "trailing comma", 3),
+        ),
+        (
+            "a=1, ",
+            Error::with_index(ErrorKind::TrailingComma, "trailing comma", 3),
+        ),
+        (
+            "a=1.",
+            Error::with_index(ErrorKind::InvalidDecimal, "trailing decimal point", 3),
+        ),
+        (
+            "a=\"b",
+            Error::with_index(ErrorKind::UnterminatedString, "unterminated string", 4),
+        ),
+    ] {
+        let mut parser = StreamingParser::new();
+        parser.feed(input);
+        assert_eq!(
+            parser.parse_dictionary_with_visitor(&mut Ignored),
+            Err(need_more_input(input.len())),
+            "{input}"
+        );
+        parser.finish();
+        assert_eq!(
+            parser.parse_dictionary_with_visitor(&mut Ignored),
+            Err(err),
+            "{input}"
+        );
+    }
+}
+
+#[test]
+fn streaming_duplicate_keys() {
+    let mut parser = StreamingParser::new().with_duplicate_key_policy(DuplicateKeyPolicy::Reject);
+    parser.feed("ab=1, a");
+    assert_eq!(
+        parser.parse_dictionary_with_visitor(&mut Ignored),
+        Err(need_more_input(7))
+    );
+    parser.feed("b");
+    assert_eq!(
+        parser.parse_dictionary_with_visitor(&mut Ignored),
+        Err(need_more_input(8))
+    );
+    parser.feed("=2");
+    assert_eq!(
+        parser.parse_dictionary_with_visitor(&mut Ignored),
+        Err(Error::duplicate_key(6, 0))
+    );
+}
+
+#[test]
+fn streaming_limits() {
+    let limits = ParserLimits {
+        max_input_len: 12,
+        max_members: 2,
+        ..ParserLimits::default()
+    };
+
+    let mut parser = StreamingParser::new().with_limits(limits);
+    parser.feed("a, b, ");
+    assert_eq!(
+        parser.parse_list_with_visitor(&mut Ignored),
+        Err(need_more_input(6))
+    );
+    parser.feed("c");
+    assert_eq!(
+        parser.parse_list_with_visitor(&mut Ignored),
+        Err(Error::with_index(
+            ErrorKind::LimitExceeded,
+            "too many members",
+            6
+        ))
+    );
+
+    // Input that is too long is rejected before it is complete.
+    let mut parser = StreamingParser::new().with_limits(limits);
+    parser.feed("a=1, b=\"abcdef");
+    assert_eq!(
+        parser.parse_dictionary_with_visitor(&mut Ignored),
+        Err(Error::with_index(
+            ErrorKind::LimitExceeded,
+            "input too long",
+            12
+        ))
+    );
+}
+
+#[test]
+#[should_panic = "input fed to a finished StreamingParser"]
+fn streaming_feed_after_finish() {
+    let mut parser = StreamingParser::new();
+    parser.finish();
+    parser.feed("a");
+}
+
+// Inputs whose parsing in chunks is compared with parsing them whole.
+#[cfg(feature = "parsed-types")]
+const INPUTS: &[&str] = &[
+    "",
+    "   ",
+    "a",
+    "a, b",
+    "a=1, b=2;x=?0, a=3",
+    "a=(1 2);p, b=(), c",
+    r#"a="x,\"y\"", b=%"%c3%a9,", c=:AQI=:;d=@-1"#,
+    "a=1.5, b=-12;c=tok/en:x",
+    "  1,\t2 ,3",
+    "a=1,",
+    "a=1, ",
+    ",a",
+    "a=1 b",
+    "a=1.",
+    "a=1.5555",
+    "a=12345678901234567",
+    "a=:AQ=I:",
+    "a=:AQ,I:",
+    "a=%\"%c3\"",
+    "a=@1.5",
+    "a=(1 2",
+    "a=(1 2)x",
+    "a=?2",
+    "a;b;b=1, a",
+    "a=1;b=2;a=3, b",
+    "A=1",
+    "1",
+    "1, (2 3);a, \"b\"",
+    "1 ",
+    "1 2",
+];
+
+#[cfg(feature = "parsed-types")]
+fn for_each_split(input: &str, mut f: impl FnMut(&[&[u8]])) {
+    let input = input.as_bytes();
+    // Byte by byte.
+    f(&input.chunks(1).collect::<Vec<_>>());
+    // In two chunks at every position.
+    for i in 0..=input.len() {
+        f(&[&input[..i], &input[i..]]);
+    }
+}
+
+#[test]
+#[cfg(feature = "parsed-types")]
+fn streaming_matches_parser() {
+    for policy in [
+        DuplicateKeyPolicy::LastWins,
+        DuplicateKeyPolicy::FirstWins,
+        DuplicateKeyPolicy::Reject,
+    ] {
+        for input in INPUTS {
+            let parser = Parser::new(input).with_duplicate_key_policy(policy);
+            let expected = parser.parse_dictionary();
+
+            for_each_split(input, |chunks| {
+                let mut parser = StreamingParser::new().with_duplicate_key_policy(policy);
+                let mut dict = Dictionary::default();
+                for chunk in chunks {
+                    parser.feed(chunk);
+                    match parser.parse_dictionary_with_visitor(&mut dict) {
+                        Err(err) if err.kind() == ErrorKind::NeedMoreInput => {}
+                        // A final error must be the same as for the whole input.
+                        result => {
+                            assert_eq!(result.map(|()| dict.clone()), expected, "{chunks:?}");
+                            return;
+                        }
+                    }
+                }
+                parser.finish();
+                let result = parser.parse_dictionary_with_visitor(&mut dict);
+                assert_eq!(result.map(|()| dict), expected, "{chunks:?}");
+            });
+
+            let expected = Parser::new(input).parse_list();
+            for_each_split(input, |chunks| {
+                let mut parser = StreamingParser::new();
+                let mut list = List::new();
+                for chunk in chunks {
+                    parser.feed(chunk);
+                    match parser.parse_list_with_visitor(&mut list) {
+                        Err(err) if err.kind() == ErrorKind::NeedMoreInput => {}
+                        result => {
+                            assert_eq!(result.map(|()| list.clone()), expected, "{chunks:?}");
+                            return;
+                        }
+                    }
+                }
+                parser.finish();
+                let result = parser.parse_list_with_visitor(&mut list);
+                assert_eq!(result.map(|()| list), expected, "{chunks:?}");
+            });
+
+            let expected = Parser::new(input).parse_item();
+            for_each_split(input, |chunks| {
+                let mut parser = StreamingParser::new();
+                let mut item = Item::new(false);
+                for chunk in chunks {
+                    parser.feed(chunk);
+                    match parser.parse_item_with_visitor(&mut item) {
+                        Err(err) if err.kind() == ErrorKind::NeedMoreInput => {}
+                        result => {
+                            assert_eq!(result.map(|()| item.clone()), expected, "{chunks:?}");
+                            return;
+                        }
+                    }
+                }
+                parser.finish();
+                let result = parser.parse_item_with_visitor(&mut item);
+                assert_eq!(result.map(|()| item), expected, "{chunks:?}");
+            });
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "parsed-types")]
+fn streaming_version() -> Result<(), Error> {
+    let mut parser = StreamingParser::new().with_version(Version::Rfc8941);
+    parser.feed("a=1, b=@1");
+    assert_eq!(
+        parser
+            .parse_dictionary_with_visitor(&mut Ignored)
+            .unwrap_err()
+            .kind(),
+        ErrorKind::UnsupportedByVersion
+    );
+
+    let mut parser = StreamingParser::new();
+    let mut dict = Dictionary::default();
+    parser.feed("a=1, b=@1");
+    parser.finish();
+    parser.parse_dictionary_with_visitor(&mut dict)?;
+    assert_eq!(dict.serialize_value()?, "a=1, b=@1");
+    Ok(())
+}
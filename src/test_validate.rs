@@ -0,0 +1,122 @@
+use crate::{DiagnosticKind, ErrorKind, Parser, ParserLimits};
+
+fn summarize(input: &str, diagnostics: &[crate::Diagnostic]) -> Vec<(Option<ErrorKind>, String)> {
+    diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let kind = match diagnostic.kind() {
+                DiagnosticKind::Error(err) => Some(err.kind()),
+                DiagnosticKind::DuplicateKey(_) => None,
+            };
+            (kind, input[diagnostic.span()].to_owned())
+        })
+        .collect()
+}
+
+#[test]
+fn validate_valid() {
+    assert!(Parser::new("a=1, b;c=?0, d=(1 2);e")
+        .validate_dictionary()
+        .is_empty());
+    assert!(Parser::new(r#"1, "a, b", (x y), :AQI=:"#)
+        .validate_list()
+        .is_empty());
+    assert!(Parser::new("  @1;a=%\"%c3%bc\"  ")
+        .validate_item()
+        .is_empty());
+    assert!(Parser::new("").validate_list().is_empty());
+    assert!(Parser::new("").validate_dictionary().is_empty());
+}
+
+#[test]
+fn validate_dictionary() {
+    let input = r#"a=1234567890123456, B=2, c=:A*Q=:;x;x, d=(1 ?2 "x, y"z), e="\q", a=?1,"#;
+    assert_eq!(
+        summarize(input, &Parser::new(input).validate_dictionary()),
+        vec![
+            (
+                Some(ErrorKind::IntegerTooLong),
+                "1234567890123456".to_owned()
+            ),
+            (Some(ErrorKind::InvalidKeyCharacter), "B=2".to_owned()),
+            (Some(ErrorKind::InvalidBase64), ":A*Q=:".to_owned()),
+            (None, "x".to_owned()),
+            (Some(ErrorKind::InvalidBoolean), "?2".to_owned()),
+            (Some(ErrorKind::InvalidInnerList), "z".to_owned()),
+            (Some(ErrorKind::InvalidEscapeSequence), r#""\q""#.to_owned()),
+            (None, "a".to_owned()),
+            (Some(ErrorKind::TrailingComma), ",".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn validate_list() {
+    let input = "1 2, (1 2, 3;a;b=, x\t";
+    assert_eq!(
+        summarize(input, &Parser::new(input).validate_list()),
+        vec![
+            (Some(ErrorKind::ExpectedComma), "2".to_owned()),
+            (Some(ErrorKind::InvalidInnerList), "(1 2".to_owned()),
+            (Some(ErrorKind::ExpectedBareItem), "3;a;b=".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn validate_item() {
+    let input = "1;a=2;a, 2";
+    let diagnostics = Parser::new(input).validate_item();
+    assert_eq!(
+        summarize(input, &diagnostics),
+        vec![
+            (None, "a".to_owned()),
+            (Some(ErrorKind::TrailingCharacters), ", 2".to_owned()),
+        ]
+    );
+    assert!(!diagnostics[0].is_error());
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "duplicate key `a` at index 6 replaces earlier value"
+    );
+    assert!(diagnostics[1].is_error());
+
+    let diagnostics = Parser::new("").validate_item();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].span(), 0..0);
+}
+
+#[test]
+fn validate_limits() {
+    let limits = ParserLimits {
+        max_members: 3,
+        max_inner_list_len: 2,
+        max_params: 1,
+        ..ParserLimits::default()
+    };
+    let input = "(1 2 3), a;b;c, (4);d, 5, 6";
+    assert_eq!(
+        summarize(
+            input,
+            &Parser::new(input).with_limits(limits).validate_list()
+        ),
+        vec![
+            (Some(ErrorKind::LimitExceeded), "(1 2 3)".to_owned()),
+            (Some(ErrorKind::LimitExceeded), "a;b;c".to_owned()),
+            (Some(ErrorKind::LimitExceeded), "5, 6".to_owned()),
+        ]
+    );
+
+    let limits = ParserLimits {
+        max_input_len: 4,
+        ..ParserLimits::default()
+    };
+    let input = "a=1, b=2";
+    assert_eq!(
+        summarize(
+            input,
+            &Parser::new(input).with_limits(limits).validate_dictionary()
+        ),
+        vec![(Some(ErrorKind::LimitExceeded), " b=2".to_owned())]
+    );
+}
@@ -1,8 +1,10 @@
-use crate::error::{Error, NonEmptyStringError};
+use crate::error::{Error, ErrorKind, NonEmptyStringError};
 use crate::utils;
 
-use std::borrow::Borrow;
-use std::fmt;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use core::borrow::Borrow;
+use core::fmt;
 
 /// An owned structured field value [token].
 ///
@@ -37,14 +39,20 @@ const fn validate(v: &[u8]) -> Result<(), NonEmptyStringError> {
     }
 
     if !utils::is_allowed_start_token_char(v[0]) {
-        return Err(NonEmptyStringError::invalid_character(0));
+        return Err(NonEmptyStringError::invalid_character(
+            ErrorKind::InvalidTokenCharacter,
+            0,
+        ));
     }
 
     let mut index = 1;
 
     while index < v.len() {
         if !utils::is_allowed_inner_token_char(v[index]) {
-            return Err(NonEmptyStringError::invalid_character(index));
+            return Err(NonEmptyStringError::invalid_character(
+                ErrorKind::InvalidTokenCharacter,
+                index,
+            ));
         }
         index += 1;
     }
@@ -106,7 +114,7 @@ impl Borrow<TokenRef> for Token {
     }
 }
 
-impl std::ops::Deref for Token {
+impl core::ops::Deref for Token {
     type Target = TokenRef;
 
     fn deref(&self) -> &TokenRef {
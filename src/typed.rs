@@ -0,0 +1,588 @@
+//! Contains traits for converting between structured field values and Rust
+//! types.
+//!
+//! [`FromBareItem`] and [`ToBareItem`] are implemented for the bare-item types
+//! in this crate as well as for `bool`, the primitive integer types, `f64`, and
+//! [`std::string::String`]. Any such type can be used as an item (ignoring its
+//! parameters), and a `Vec` of items can be used as an inner list.
+//!
+//! With the `derive` feature, the remaining traits can be derived for structs:
+//!
+//! - [`FromSfvDictionary`] and [`ToSfvDictionary`] map each field to a
+//!   dictionary member whose key is the field's name.
+//! - [`FromSfvList`] and [`ToSfvList`] map a struct with a single `Vec` field
+//!   to a list whose members are the elements of the `Vec`.
+//! - [`FromSfvItem`] and [`ToSfvItem`] map a struct with a single bare-item
+//!   field and any number of parameter fields to an item.
+//!
+//! The following field attributes are supported:
+//!
+//! - `#[sfv(key = "...")]` uses the given key instead of the field's name.
+//! - `#[sfv(default)]` uses [`Default::default`] for a missing member or
+//!   parameter instead of failing.
+//! - `#[sfv(param)]` marks a field of an item struct as a parameter.
+//! - `#[sfv(token)]` converts a string-like field (`From<String>` and
+//!   `AsRef<str>`) from and to a token instead of a string.
+//! - `#[sfv(bytes)]` converts a byte-buffer-like field (`From<Vec<u8>>` and
+//!   `AsRef<[u8]>`) from and to a byte sequence.
+//!
+//! Fields of type `Option<T>` are optional: they are `None` when the member or
+//! parameter is missing and are skipped during serialization when `None`.
+//! Unknown members and parameters are ignored. Duplicate keys are handled
+//! according to the parser's [`DuplicateKeyPolicy`][crate::DuplicateKeyPolicy].
+//!
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # fn main() -> Result<(), sfv::Error> {
+//! use sfv::typed::{FromSfvDictionary, FromSfvItem, ToSfvDictionary, ToSfvItem};
+//! use sfv::Parser;
+//!
+//! #[derive(Debug, PartialEq, FromSfvItem, ToSfvItem)]
+//! struct Endpoint {
+//!     #[sfv(token)]
+//!     name: String,
+//!     #[sfv(param)]
+//!     weight: Option<u8>,
+//! }
+//!
+//! #[derive(Debug, PartialEq, FromSfvDictionary, ToSfvDictionary)]
+//! struct Config {
+//!     #[sfv(key = "max-age")]
+//!     max_age: u32,
+//!     #[sfv(default)]
+//!     shared: bool,
+//!     primary: Endpoint,
+//!     fallbacks: Option<Vec<Endpoint>>,
+//! }
+//!
+//! let config = Config::from_sfv_dictionary(Parser::new(
+//!     "max-age=60, primary=a;weight=2, fallbacks=(b c)",
+//! ))?;
+//! assert_eq!(
+//!     config,
+//!     Config {
+//!         max_age: 60,
+//!         shared: false,
+//!         primary: Endpoint { name: "a".into(), weight: Some(2) },
+//!         fallbacks: Some(vec![
+//!             Endpoint { name: "b".into(), weight: None },
+//!             Endpoint { name: "c".into(), weight: None },
+//!         ]),
+//!     }
+//! );
+//! assert_eq!(
+//!     config.to_sfv_dictionary()?,
+//!     "max-age=60, shared=?0, primary=a;weight=2, fallbacks=(b c)"
+//! );
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "derive"))]
+//! # fn main() {}
+//! ```
+#![cfg_attr(
+    feature = "derive",
+    doc = r##"
+
+Keys must be valid and distinct among the members of a dictionary or the
+parameters of an item, which is checked at compile time:
+
+```compile_fail
+use sfv::typed::FromSfvItem;
+
+#[derive(FromSfvItem)]
+struct Item {
+    value: i64,
+    #[sfv(param, key = "a")]
+    first: bool,
+    #[sfv(param, key = "a")]
+    second: bool,
+}
+```
+"##
+)]
+
+use crate::borrowed;
+use crate::{
+    BareItemFromInput, Date, Decimal, DictSerializer, Error, ErrorKind, Integer, ItemSerializer,
+    ListSerializer, Output, ParameterSerializer, Parser, RefBareItem, SFVResult, StringRef, Token,
+};
+
+use alloc::borrow::ToOwned;
+use alloc::string::String as StdString;
+use alloc::vec::Vec;
+
+#[cfg(feature = "derive")]
+pub use sfv_derive::{
+    FromSfvDictionary, FromSfvItem, FromSfvList, ToSfvDictionary, ToSfvItem, ToSfvList,
+};
+
+/// A type that can be converted from a bare item.
+pub trait FromBareItem: Sized {
+    /// Converts the given bare item to `Self`.
+    fn from_bare_item(bare_item: BareItemFromInput<'_>) -> SFVResult<Self>;
+}
+
+/// A type that can be converted to a bare item.
+pub trait ToBareItem {
+    /// Converts `self` to a bare item.
+    fn to_bare_item(&self) -> SFVResult<RefBareItem<'_>>;
+}
+
+impl FromBareItem for Integer {
+    fn from_bare_item(bare_item: BareItemFromInput<'_>) -> SFVResult<Self> {
+        match bare_item {
+            BareItemFromInput::Integer(val) => Ok(val),
+            _ => Err(Error::new(ErrorKind::TypeMismatch, "expected integer")),
+        }
+    }
+}
+
+impl ToBareItem for Integer {
+    fn to_bare_item(&self) -> SFVResult<RefBareItem<'_>> {
+        Ok(RefBareItem::Integer(*self))
+    }
+}
+
+impl FromBareItem for Decimal {
+    fn from_bare_item(bare_item: BareItemFromInput<'_>) -> SFVResult<Self> {
+        match bare_item {
+            BareItemFromInput::Decimal(val) => Ok(val),
+            _ => Err(Error::new(ErrorKind::TypeMismatch, "expected decimal")),
+        }
+    }
+}
+
+impl ToBareItem for Decimal {
+    fn to_bare_item(&self) -> SFVResult<RefBareItem<'_>> {
+        Ok(RefBareItem::Decimal(*self))
+    }
+}
+
+impl FromBareItem for Date {
+    fn from_bare_item(bare_item: BareItemFromInput<'_>) -> SFVResult<Self> {
+        match bare_item {
+            BareItemFromInput::Date(val) => Ok(val),
+            _ => Err(Error::new(ErrorKind::TypeMismatch, "expected date")),
+        }
+    }
+}
+
+impl ToBareItem for Date {
+    fn to_bare_item(&self) -> SFVResult<RefBareItem<'_>> {
+        Ok(RefBareItem::Date(*self))
+    }
+}
+
+impl FromBareItem for bool {
+    fn from_bare_item(bare_item: BareItemFromInput<'_>) -> SFVResult<Self> {
+        match bare_item {
+            BareItemFromInput::Boolean(val) => Ok(val),
+            _ => Err(Error::new(ErrorKind::TypeMismatch, "expected boolean")),
+        }
+    }
+}
+
+impl ToBareItem for bool {
+    fn to_bare_item(&self) -> SFVResult<RefBareItem<'_>> {
+        Ok(RefBareItem::Boolean(*self))
+    }
+}
+
+impl FromBareItem for crate::String {
+    fn from_bare_item(bare_item: BareItemFromInput<'_>) -> SFVResult<Self> {
+        match bare_item {
+            BareItemFromInput::String(val) => Ok(val.into_owned()),
+            _ => Err(Error::new(ErrorKind::TypeMismatch, "expected string")),
+        }
+    }
+}
+
+impl ToBareItem for crate::String {
+    fn to_bare_item(&self) -> SFVResult<RefBareItem<'_>> {
+        Ok(RefBareItem::String(self))
+    }
+}
+
+impl FromBareItem for Token {
+    fn from_bare_item(bare_item: BareItemFromInput<'_>) -> SFVResult<Self> {
+        match bare_item {
+            BareItemFromInput::Token(val) => Ok(val.to_owned()),
+            _ => Err(Error::new(ErrorKind::TypeMismatch, "expected token")),
+        }
+    }
+}
+
+impl ToBareItem for Token {
+    fn to_bare_item(&self) -> SFVResult<RefBareItem<'_>> {
+        Ok(RefBareItem::Token(self))
+    }
+}
+
+/// Converts from a string or a display string.
+impl FromBareItem for StdString {
+    fn from_bare_item(bare_item: BareItemFromInput<'_>) -> SFVResult<Self> {
+        match bare_item {
+            BareItemFromInput::String(val) => Ok(val.into_owned().into()),
+            BareItemFromInput::DisplayString(val) => Ok(val.into_owned()),
+            _ => Err(Error::new(ErrorKind::TypeMismatch, "expected string")),
+        }
+    }
+}
+
+/// Converts to a string, failing if `self` contains characters that are not
+/// allowed in one.
+impl ToBareItem for StdString {
+    fn to_bare_item(&self) -> SFVResult<RefBareItem<'_>> {
+        StringRef::from_str(self).map(RefBareItem::String)
+    }
+}
+
+impl FromBareItem for f64 {
+    fn from_bare_item(bare_item: BareItemFromInput<'_>) -> SFVResult<Self> {
+        Decimal::from_bare_item(bare_item).map(f64::from)
+    }
+}
+
+impl ToBareItem for f64 {
+    fn to_bare_item(&self) -> SFVResult<RefBareItem<'_>> {
+        Decimal::try_from(*self).map(RefBareItem::Decimal)
+    }
+}
+
+macro_rules! impl_integer_conversions {
+    ($($t: ty,)+) => {
+        $(
+            impl FromBareItem for $t {
+                fn from_bare_item(bare_item: BareItemFromInput<'_>) -> SFVResult<Self> {
+                    Integer::from_bare_item(bare_item)?
+                        .try_into()
+                        .map_err(|_| Error::out_of_range())
+                }
+            }
+
+            impl ToBareItem for $t {
+                fn to_bare_item(&self) -> SFVResult<RefBareItem<'_>> {
+                    Integer::try_from(*self)
+                        .map(RefBareItem::Integer)
+                        .map_err(|_| Error::out_of_range())
+                }
+            }
+        )+
+    }
+}
+
+impl_integer_conversions! {
+    i8, i16, i32, i64, isize,
+    u8, u16, u32, u64, usize,
+}
+
+/// A type that can be converted from an item.
+///
+/// This is implemented for every type implementing [`FromBareItem`], in which
+/// case the item's parameters are ignored, and can be derived for structs with
+/// the `derive` feature.
+pub trait FromSfvItem: Sized {
+    #[doc(hidden)]
+    fn from_item_value(item: borrowed::Item<'_>) -> SFVResult<Self>;
+
+    /// Parses a structured field value of `Item` type into `Self`.
+    fn from_sfv_item(parser: Parser<'_>) -> SFVResult<Self> {
+        let mut item = borrowed::Item::default();
+        parser.parse_item_with_visitor(&mut item)?;
+        Self::from_item_value(item)
+    }
+}
+
+impl<T: FromBareItem> FromSfvItem for T {
+    fn from_item_value(item: borrowed::Item<'_>) -> SFVResult<Self> {
+        T::from_bare_item(item.bare_item)
+    }
+}
+
+/// A type that can be converted to an item.
+///
+/// This is implemented for every type implementing [`ToBareItem`], in which
+/// case the item has no parameters, and can be derived for structs with the
+/// `derive` feature.
+pub trait ToSfvItem {
+    #[doc(hidden)]
+    fn serialize_item<W: Output>(
+        &self,
+        bare_item: impl FnOnce(RefBareItem<'_>) -> ParameterSerializer<W>,
+    ) -> SFVResult<ParameterSerializer<W>>;
+
+    /// Serializes `self` as a structured field value of `Item` type.
+    fn to_sfv_item(&self) -> SFVResult<StdString> {
+        let ser = self.serialize_item(|bare_item| ItemSerializer::new().bare_item(bare_item))?;
+        Ok(ser.finish())
+    }
+}
+
+impl<T: ?Sized + ToBareItem> ToSfvItem for T {
+    fn serialize_item<W: Output>(
+        &self,
+        bare_item: impl FnOnce(RefBareItem<'_>) -> ParameterSerializer<W>,
+    ) -> SFVResult<ParameterSerializer<W>> {
+        Ok(bare_item(self.to_bare_item()?))
+    }
+}
+
+/// A type that can be converted from a member of a list or dictionary.
+///
+/// This is implemented for every type implementing [`FromSfvItem`], and for
+/// `Vec<T>`, which is converted from an inner list whose parameters are
+/// ignored.
+pub trait FromSfvEntry: Sized {
+    #[doc(hidden)]
+    fn from_entry_value(entry: borrowed::ListEntry<'_>) -> SFVResult<Self>;
+}
+
+impl<T: FromSfvItem> FromSfvEntry for T {
+    fn from_entry_value(entry: borrowed::ListEntry<'_>) -> SFVResult<Self> {
+        match entry {
+            borrowed::ListEntry::Item(item) => T::from_item_value(item),
+            borrowed::ListEntry::InnerList(_) => Err(Error::new(
+                ErrorKind::TypeMismatch,
+                "expected item, found inner list",
+            )),
+        }
+    }
+}
+
+impl<T: FromSfvItem> FromSfvEntry for Vec<T> {
+    fn from_entry_value(entry: borrowed::ListEntry<'_>) -> SFVResult<Self> {
+        match entry {
+            borrowed::ListEntry::InnerList(inner_list) => inner_list
+                .items
+                .into_iter()
+                .map(T::from_item_value)
+                .collect(),
+            borrowed::ListEntry::Item(_) => Err(Error::new(
+                ErrorKind::TypeMismatch,
+                "expected inner list, found item",
+            )),
+        }
+    }
+}
+
+/// A type that can be converted to a member of a list or dictionary.
+///
+/// This is implemented for every type implementing [`ToSfvItem`], and for
+/// `Vec<T>`, which is converted to an inner list without parameters.
+pub trait ToSfvEntry {
+    #[doc(hidden)]
+    fn serialize_entry<W: Output>(&self, ser: __private::EntrySerializer<'_, W>) -> SFVResult<()>;
+}
+
+impl<T: ?Sized + ToSfvItem> ToSfvEntry for T {
+    fn serialize_entry<W: Output>(&self, ser: __private::EntrySerializer<'_, W>) -> SFVResult<()> {
+        self.serialize_item(|bare_item| ser.bare_item(bare_item))?;
+        Ok(())
+    }
+}
+
+impl<T: ToSfvItem> ToSfvEntry for Vec<T> {
+    fn serialize_entry<W: Output>(&self, ser: __private::EntrySerializer<'_, W>) -> SFVResult<()> {
+        let mut inner_list = ser.inner_list();
+        for item in self {
+            item.serialize_item(|bare_item| inner_list.bare_item(bare_item))?;
+        }
+        Ok(())
+    }
+}
+
+/// A type that can be converted from a dictionary.
+///
+/// This can be derived for structs with the `derive` feature.
+pub trait FromSfvDictionary: Sized {
+    /// Parses a structured field value of `Dictionary` type into `Self`.
+    fn from_sfv_dictionary(parser: Parser<'_>) -> SFVResult<Self>;
+}
+
+/// A type that can be converted to a dictionary.
+///
+/// This can be derived for structs with the `derive` feature.
+pub trait ToSfvDictionary {
+    /// Serializes the members of `self` into the given serializer.
+    fn serialize_dictionary<W: Output>(&self, ser: &mut DictSerializer<W>) -> SFVResult<()>;
+
+    /// Serializes `self` as a structured field value of `Dictionary` type.
+    ///
+    /// This fails if `self` has no members to serialize.
+    fn to_sfv_dictionary(&self) -> SFVResult<StdString> {
+        let mut ser = DictSerializer::new();
+        self.serialize_dictionary(&mut ser)?;
+        ser.finish()
+    }
+}
+
+/// A type that can be converted from a list.
+///
+/// This is implemented for `Vec<T>` and can be derived for structs wrapping a
+/// `Vec` with the `derive` feature.
+pub trait FromSfvList: Sized {
+    /// Parses a structured field value of `List` type into `Self`.
+    fn from_sfv_list(parser: Parser<'_>) -> SFVResult<Self>;
+}
+
+impl<T: FromSfvEntry> FromSfvList for Vec<T> {
+    fn from_sfv_list(parser: Parser<'_>) -> SFVResult<Self> {
+        let mut list = borrowed::List::default();
+        parser.parse_list_with_visitor(&mut list)?;
+        list.into_iter().map(T::from_entry_value).collect()
+    }
+}
+
+/// A type that can be converted to a list.
+///
+/// This is implemented for `Vec<T>` and can be derived for structs wrapping a
+/// `Vec` with the `derive` feature.
+pub trait ToSfvList {
+    /// Serializes the members of `self` into the given serializer.
+    fn serialize_list<W: Output>(&self, ser: &mut ListSerializer<W>) -> SFVResult<()>;
+
+    /// Serializes `self` as a structured field value of `List` type.
+    ///
+    /// This fails if `self` has no members to serialize.
+    fn to_sfv_list(&self) -> SFVResult<StdString> {
+        let mut ser = ListSerializer::new();
+        self.serialize_list(&mut ser)?;
+        ser.finish()
+    }
+}
+
+impl<T: ToSfvEntry> ToSfvList for Vec<T> {
+    fn serialize_list<W: Output>(&self, ser: &mut ListSerializer<W>) -> SFVResult<()> {
+        for member in self {
+            member.serialize_entry(__private::EntrySerializer::List(ser))?;
+        }
+        Ok(())
+    }
+}
+
+// Support code for the derive macros. Not public API.
+#[doc(hidden)]
+pub mod __private {
+    use super::{FromBareItem, FromSfvEntry, ToBareItem};
+    use crate::borrowed;
+    use crate::{
+        BareItemFromInput, DictSerializer, Error, ErrorKind, InnerListSerializer, KeyRef,
+        ListSerializer, Output, ParameterSerializer, RefBareItem, SFVResult, TokenRef,
+    };
+
+    use alloc::borrow::ToOwned;
+    use alloc::vec::Vec;
+
+    pub use alloc::string::String as StdString;
+
+    pub enum EntrySerializer<'a, W> {
+        List(&'a mut ListSerializer<W>),
+        Dictionary(&'a mut DictSerializer<W>, &'a KeyRef),
+    }
+
+    impl<'a, W: Output> EntrySerializer<'a, W> {
+        pub(super) fn bare_item(
+            self,
+            bare_item: RefBareItem<'_>,
+        ) -> ParameterSerializer<&'a mut W> {
+            match self {
+                Self::List(ser) => ser.bare_item(bare_item),
+                Self::Dictionary(ser, key) => ser.bare_item(key, bare_item),
+            }
+        }
+
+        pub(super) fn inner_list(self) -> InnerListSerializer<'a, W> {
+            match self {
+                Self::List(ser) => ser.inner_list(),
+                Self::Dictionary(ser, key) => ser.inner_list(key),
+            }
+        }
+    }
+
+    pub struct Token<T>(pub T);
+
+    impl<T: From<StdString>> FromBareItem for Token<T> {
+        fn from_bare_item(bare_item: BareItemFromInput<'_>) -> SFVResult<Self> {
+            match bare_item {
+                BareItemFromInput::Token(val) => Ok(Self(T::from(val.as_str().to_owned()))),
+                _ => Err(Error::new(ErrorKind::TypeMismatch, "expected token")),
+            }
+        }
+    }
+
+    impl<T: ?Sized + AsRef<str>> ToBareItem for Token<&T> {
+        fn to_bare_item(&self) -> SFVResult<RefBareItem<'_>> {
+            TokenRef::from_str(self.0.as_ref()).map(RefBareItem::Token)
+        }
+    }
+
+    pub struct ByteSequence<T>(pub T);
+
+    impl<T: From<Vec<u8>>> FromBareItem for ByteSequence<T> {
+        fn from_bare_item(bare_item: BareItemFromInput<'_>) -> SFVResult<Self> {
+            match bare_item {
+                BareItemFromInput::ByteSequence(val) => Ok(Self(T::from(val))),
+                _ => Err(Error::new(
+                    ErrorKind::TypeMismatch,
+                    "expected byte sequence",
+                )),
+            }
+        }
+    }
+
+    impl<T: ?Sized + AsRef<[u8]>> ToBareItem for ByteSequence<&T> {
+        fn to_bare_item(&self) -> SFVResult<RefBareItem<'_>> {
+            Ok(RefBareItem::ByteSequence(self.0.as_ref()))
+        }
+    }
+
+    pub fn member<T: FromSfvEntry>(
+        entry: Option<borrowed::ListEntry<'_>>,
+        key: &str,
+    ) -> SFVResult<T> {
+        match entry {
+            Some(entry) => convert(T::from_entry_value(entry), "dictionary member", key),
+            None => Err(Error::custom(format_args!(
+                "missing dictionary member `{key}`"
+            ))),
+        }
+    }
+
+    pub fn optional_member<T: FromSfvEntry>(
+        entry: Option<borrowed::ListEntry<'_>>,
+        key: &str,
+    ) -> SFVResult<Option<T>> {
+        entry
+            .map(|entry| convert(T::from_entry_value(entry), "dictionary member", key))
+            .transpose()
+    }
+
+    pub fn take_param<'a>(
+        params: &mut borrowed::Parameters<'a>,
+        key: &str,
+    ) -> Option<BareItemFromInput<'a>> {
+        params.take(key)
+    }
+
+    pub fn param<T: FromBareItem>(value: Option<BareItemFromInput<'_>>, key: &str) -> SFVResult<T> {
+        match value {
+            Some(value) => convert(T::from_bare_item(value), "parameter", key),
+            None => Err(Error::custom(format_args!("missing parameter `{key}`"))),
+        }
+    }
+
+    pub fn optional_param<T: FromBareItem>(
+        value: Option<BareItemFromInput<'_>>,
+        key: &str,
+    ) -> SFVResult<Option<T>> {
+        value
+            .map(|value| convert(T::from_bare_item(value), "parameter", key))
+            .transpose()
+    }
+
+    fn convert<T>(result: SFVResult<T>, kind: &str, key: &str) -> SFVResult<T> {
+        result.map_err(|err| {
+            Error::custom_with_kind(err.kind(), format_args!("invalid {kind} `{key}`: {err}"))
+        })
+    }
+}
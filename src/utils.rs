@@ -1,4 +1,5 @@
 use base64::engine;
+use core::ops::Range;
 
 pub(crate) const BASE64: engine::GeneralPurpose = engine::GeneralPurpose::new(
     &base64::alphabet::STANDARD,
@@ -8,6 +9,59 @@ pub(crate) const BASE64: engine::GeneralPurpose = engine::GeneralPurpose::new(
         .with_encode_padding(true),
 );
 
+// Returns whether the input would be decoded successfully by `BASE64`, without
+// allocating.
+//
+// The input is decoded in chunks of whole base64 quanta into a fixed buffer.
+// This matches decoding it all at once as long as padding only occurs in the
+// last chunk.
+pub(crate) fn is_valid_base64(input: &[u8]) -> bool {
+    const CHUNK_LEN: usize = 1024;
+
+    let mut buf = [0; CHUNK_LEN / 4 * 3];
+    let mut chunks = input.chunks(CHUNK_LEN).peekable();
+    while let Some(chunk) = chunks.next() {
+        if chunks.peek().is_some() && chunk.contains(&b'=') {
+            return false;
+        }
+        if base64::Engine::decode_slice(&BASE64, chunk, &mut buf).is_err() {
+            return false;
+        }
+    }
+    true
+}
+
+// Returns the span of `s`, which is borrowed from `input`.
+pub(crate) fn span_of(input: &[u8], s: &str) -> Range<usize> {
+    let start = s.as_ptr() as usize - input.as_ptr() as usize;
+    start..start + s.len()
+}
+
+// Character classes, looked up in `CHAR_CLASSES` so that the parser can scan
+// runs of characters with a single table lookup per byte.
+const TOKEN_CHAR: u8 = 1 << 0;
+const KEY_CHAR: u8 = 1 << 1;
+const UNESCAPED_STRING_CHAR: u8 = 1 << 2;
+
+const CHAR_CLASSES: [u8; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < table.len() {
+        let c = i as u8;
+        if is_tchar(c) || c == b':' || c == b'/' {
+            table[i] |= TOKEN_CHAR;
+        }
+        if c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, b'_' | b'-' | b'*' | b'.') {
+            table[i] |= KEY_CHAR;
+        }
+        if matches!(c, 0x20..=0x7e) && c != b'"' && c != b'\\' {
+            table[i] |= UNESCAPED_STRING_CHAR;
+        }
+        i += 1;
+    }
+    table
+};
+
 const fn is_tchar(c: u8) -> bool {
     // See tchar values list in https://tools.ietf.org/html/rfc7230#section-3.2.6
     matches!(
@@ -34,7 +88,7 @@ pub(crate) const fn is_allowed_start_token_char(c: u8) -> bool {
 }
 
 pub(crate) const fn is_allowed_inner_token_char(c: u8) -> bool {
-    is_tchar(c) || c == b':' || c == b'/'
+    CHAR_CLASSES[c as usize] & TOKEN_CHAR != 0
 }
 
 pub(crate) const fn is_allowed_start_key_char(c: u8) -> bool {
@@ -42,5 +96,10 @@ pub(crate) const fn is_allowed_start_key_char(c: u8) -> bool {
 }
 
 pub(crate) const fn is_allowed_inner_key_char(c: u8) -> bool {
-    c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, b'_' | b'-' | b'*' | b'.')
+    CHAR_CLASSES[c as usize] & KEY_CHAR != 0
+}
+
+// Returns whether the character can appear in a string without being escaped.
+pub(crate) const fn is_unescaped_string_char(c: u8) -> bool {
+    CHAR_CLASSES[c as usize] & UNESCAPED_STRING_CHAR != 0
 }
@@ -0,0 +1,311 @@
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::convert::Infallible;
+use core::fmt;
+use core::ops::Range;
+
+use crate::parser::{parse_comma_separated, parse_dictionary_member, parse_item, SeenKeys};
+use crate::utils;
+use crate::visitor::{
+    DictionaryVisitor, EntryVisitor, InnerListVisitor, ItemVisitor, ParameterVisitor,
+};
+use crate::{BareItemFromInput, Error, ErrorKind, Key, KeyRef, Parser, SFVResult};
+
+/// A problem found in a structured field value by
+/// [`Parser::validate_item`], [`Parser::validate_list`], or
+/// [`Parser::validate_dictionary`].
+#[derive(Debug)]
+pub struct Diagnostic {
+    kind: DiagnosticKind,
+    span: Range<usize>,
+}
+
+/// The category of a [`Diagnostic`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DiagnosticKind {
+    /// The input violates the grammar, so the entire field would fail to
+    /// parse.
+    Error(Error),
+    /// A dictionary member or parameter has the same key as an earlier one,
+    /// whose value it silently replaces.
+    DuplicateKey(Key),
+}
+
+impl Diagnostic {
+    /// Returns the category of the diagnostic.
+    pub fn kind(&self) -> &DiagnosticKind {
+        &self.kind
+    }
+
+    /// Returns the range of bytes in the input that the diagnostic applies to.
+    ///
+    /// For errors, this covers the malformed key, bare item, or member, up to
+    /// the point at which validation resumed. For duplicate keys, this covers
+    /// the later occurrence of the key.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns whether the diagnostic causes the field to be invalid, as
+    /// opposed to being a warning.
+    pub fn is_error(&self) -> bool {
+        matches!(self.kind, DiagnosticKind::Error(_))
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            DiagnosticKind::Error(err) => fmt::Display::fmt(err, f),
+            DiagnosticKind::DuplicateKey(key) => write!(
+                f,
+                "duplicate key `{}` at index {} replaces earlier value",
+                key, self.span.start
+            ),
+        }
+    }
+}
+
+fn is_value_delimiter(c: u8) -> bool {
+    matches!(c, b' ' | b',' | b';' | b')')
+}
+
+impl Parser<'_> {
+    // Records an error, or returns it unless the parser is collecting
+    // diagnostics.
+    pub(crate) fn report(&self, err: Error, span: Range<usize>) -> SFVResult<()> {
+        match self.diagnostics {
+            Some(diagnostics) => {
+                diagnostics.borrow_mut().push(Diagnostic {
+                    kind: DiagnosticKind::Error(err),
+                    span,
+                });
+                Ok(())
+            }
+            None => Err(err),
+        }
+    }
+
+    // Records an error in the part of the input starting at `start`, and
+    // resumes parsing at the next delimiter, or returns the error unless the
+    // parser is collecting diagnostics.
+    //
+    // The error's span extends from `start` to the delimiter, excluding
+    // trailing whitespace, or covers the byte at `start` if that would be
+    // empty.
+    pub(crate) fn recover(
+        &mut self,
+        err: Error,
+        start: usize,
+        is_delimiter: fn(u8) -> bool,
+    ) -> SFVResult<()> {
+        if self.diagnostics.is_none() {
+            return Err(err);
+        }
+
+        self.index = start;
+        self.skip_until(is_delimiter);
+        // Always make progress, but leave commas for the caller to handle.
+        if self.index == start && !matches!(self.peek(), None | Some(b',')) {
+            self.next();
+        }
+
+        let mut end = self.index;
+        while end > start && matches!(self.input[end - 1], b' ' | b'\t') {
+            end -= 1;
+        }
+        if end == start && start < self.input.len() {
+            end += 1;
+        }
+        self.report(err, start..end)
+    }
+
+    // Like `recover`, for an error in a bare item, key, or inner list
+    // delimiter, after which parsing resumes at the next value delimiter.
+    //
+    // If the error is at the end of the member, the error is returned so that
+    // it is recorded for the member as a whole.
+    pub(crate) fn recover_value(&mut self, err: Error, start: usize) -> SFVResult<()> {
+        match self.input.get(start) {
+            None | Some(b',') => Err(err),
+            Some(_) => self.recover(err, start, is_value_delimiter),
+        }
+    }
+}
+
+// Reports keys that are duplicated within a dictionary or parameters, which
+// the parser only passes to visitors under `DuplicateKeyPolicy::LastWins`.
+struct DuplicateKeys<'a> {
+    input: &'a [u8],
+    diagnostics: &'a RefCell<Vec<Diagnostic>>,
+    seen: BTreeSet<&'a KeyRef>,
+}
+
+impl<'a> DuplicateKeys<'a> {
+    fn new(input: &'a [u8], diagnostics: &'a RefCell<Vec<Diagnostic>>) -> Self {
+        Self {
+            input,
+            diagnostics,
+            seen: BTreeSet::new(),
+        }
+    }
+
+    // Returns a visitor for a nested dictionary entry, list entry, or item,
+    // whose parameters are checked separately.
+    fn nested(&self) -> Self {
+        Self::new(self.input, self.diagnostics)
+    }
+
+    fn check(&mut self, key: &'a KeyRef) {
+        if !self.seen.insert(key) {
+            self.diagnostics.borrow_mut().push(Diagnostic {
+                kind: DiagnosticKind::DuplicateKey(key.to_owned()),
+                span: utils::span_of(self.input, key.as_str()),
+            });
+        }
+    }
+}
+
+impl<'a> ParameterVisitor<'a> for DuplicateKeys<'a> {
+    type Error = Infallible;
+
+    fn parameter(
+        &mut self,
+        key: &'a KeyRef,
+        _value: BareItemFromInput<'a>,
+    ) -> Result<(), Self::Error> {
+        self.check(key);
+        Ok(())
+    }
+}
+
+impl<'a> ItemVisitor<'a> for DuplicateKeys<'a> {
+    type Error = Infallible;
+
+    fn bare_item(
+        self,
+        _bare_item: BareItemFromInput<'a>,
+    ) -> Result<impl ParameterVisitor<'a>, Self::Error> {
+        Ok(self)
+    }
+}
+
+impl<'a> EntryVisitor<'a> for DuplicateKeys<'a> {
+    fn inner_list(self) -> Result<impl InnerListVisitor<'a>, Self::Error> {
+        Ok(self)
+    }
+}
+
+impl<'a> InnerListVisitor<'a> for DuplicateKeys<'a> {
+    type Error = Infallible;
+
+    fn item(&mut self) -> Result<impl ItemVisitor<'a>, Self::Error> {
+        Ok(self.nested())
+    }
+
+    fn finish(self) -> Result<impl ParameterVisitor<'a>, Self::Error> {
+        Ok(self)
+    }
+}
+
+impl<'a> DictionaryVisitor<'a> for DuplicateKeys<'a> {
+    type Error = Infallible;
+
+    fn entry(&mut self, key: &'a KeyRef) -> Result<impl EntryVisitor<'a>, Self::Error> {
+        self.check(key);
+        Ok(self.nested())
+    }
+}
+
+impl Parser<'_> {
+    // Parses the input with `f`, which is given a visitor reporting duplicate
+    // keys, and collects diagnostics for the errors that the parser resumes
+    // after, as well as for the first one that it does not.
+    fn validate(
+        self,
+        f: impl for<'c> FnOnce(&mut Parser<'c>, DuplicateKeys<'c>) -> SFVResult<()>,
+    ) -> Vec<Diagnostic> {
+        let diagnostics = RefCell::new(Vec::new());
+        let mut parser = self.with_diagnostics(&diagnostics);
+        let visitor = DuplicateKeys::new(parser.input, &diagnostics);
+
+        let result = parser.check_input_len().and_then(|()| {
+            parser.consume_sp_chars();
+            f(&mut parser, visitor)
+        });
+        let result = result.and_then(|()| {
+            parser.consume_sp_chars();
+            match parser.peek() {
+                None => Ok(()),
+                Some(_) => parser.error(
+                    ErrorKind::TrailingCharacters,
+                    "trailing characters after parsed value",
+                ),
+            }
+        });
+
+        if let Err(err) = result {
+            let start = err.index().unwrap_or(parser.index);
+            // This cannot fail, since the parser collects diagnostics.
+            let _ = parser.recover(err, start, |_| false);
+        }
+
+        diagnostics.into_inner()
+    }
+
+    /// Validates input as a structured field value of `Item` type, reporting
+    /// every problem found instead of stopping at the first error.
+    ///
+    /// The input is valid if none of the returned diagnostics are
+    /// [errors][Diagnostic::is_error].
+    pub fn validate_item(self) -> Vec<Diagnostic> {
+        self.validate(|parser, visitor| parse_item(parser, visitor))
+    }
+
+    /// Validates input as a structured field value of `List` type, reporting
+    /// every problem found instead of stopping at the first error.
+    ///
+    /// The input is valid if none of the returned diagnostics are
+    /// [errors][Diagnostic::is_error].
+    pub fn validate_list(self) -> Vec<Diagnostic> {
+        self.validate(|parser, visitor| {
+            parse_comma_separated(parser, |parser| parser.parse_list_entry(visitor.nested()))
+        })
+    }
+
+    /// Validates input as a structured field value of `Dictionary` type,
+    /// reporting every problem found instead of stopping at the first error.
+    ///
+    /// The input is valid if none of the returned diagnostics are
+    /// [errors][Diagnostic::is_error]. Duplicate keys, which are permitted
+    /// but cause earlier values to be discarded, are reported as warnings.
+    /// Under a [`DuplicateKeyPolicy`][crate::DuplicateKeyPolicy] other than
+    /// the default, they are instead ignored or reported as errors, as when
+    /// parsing.
+    ///
+    /// ```
+    /// # use sfv::{DiagnosticKind, ErrorKind, Parser};
+    /// let input = "a=1234567890123456, B=2, c=:A*Q=:;x;x, a=?1";
+    /// let diagnostics = Parser::new(input).validate_dictionary();
+    ///
+    /// let spans: Vec<_> = diagnostics.iter().map(|d| &input[d.span()]).collect();
+    /// assert_eq!(spans, ["1234567890123456", "B=2", ":A*Q=:", "x", "a"]);
+    ///
+    /// assert!(matches!(
+    ///     diagnostics[0].kind(),
+    ///     DiagnosticKind::Error(err) if err.kind() == ErrorKind::IntegerTooLong
+    /// ));
+    /// assert!(!diagnostics[4].is_error());
+    /// ```
+    pub fn validate_dictionary(self) -> Vec<Diagnostic> {
+        self.validate(|parser, mut visitor| {
+            let mut seen = SeenKeys::new();
+            parse_comma_separated(parser, |parser| {
+                parse_dictionary_member(parser, &mut visitor, &mut seen)
+            })
+        })
+    }
+}
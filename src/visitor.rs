@@ -23,7 +23,7 @@ struct Visitor<'a> {
 }
 
 impl<'a> ItemVisitor<'a> for &mut Visitor<'a> {
-  type Error = std::convert::Infallible;
+  type Error = core::convert::Infallible;
 
   fn bare_item(self, bare_item: BareItemFromInput<'a>) -> Result<impl ParameterVisitor<'a>, Self::Error> {
       self.token =
@@ -52,9 +52,21 @@ sfv::Parser::new(input).parse_item_with_visitor(&mut visitor)?;
 */
 
 use crate::{BareItemFromInput, KeyRef};
-use std::convert::Infallible;
+use core::convert::Infallible;
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
 use std::error::Error;
 
+/// The bound on visitor error types when the `std` feature is disabled, in lieu
+/// of `std::error::Error`.
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+pub trait Error: fmt::Debug + fmt::Display {}
+
+#[cfg(not(feature = "std"))]
+impl<T: ?Sized + fmt::Debug + fmt::Display> Error for T {}
+
 /// A visitor whose methods are called during parameter parsing.
 ///
 /// The lifetime `'a` is the lifetime of the input.
@@ -0,0 +1,230 @@
+use sfv::typed::{
+    FromSfvDictionary, FromSfvItem, FromSfvList, ToSfvDictionary, ToSfvItem, ToSfvList,
+};
+use sfv::{token_ref, Date, Decimal, DuplicateKeyPolicy, Error, ErrorKind, Parser, Token};
+
+#[derive(Debug, PartialEq, FromSfvItem, ToSfvItem)]
+struct Coep {
+    #[sfv(token)]
+    policy: String,
+    #[sfv(param, key = "report-to")]
+    report_to: Option<String>,
+}
+
+#[derive(Debug, PartialEq, FromSfvDictionary, ToSfvDictionary)]
+struct CacheControl {
+    #[sfv(key = "max-age")]
+    max_age: Option<u32>,
+    #[sfv(default)]
+    private: bool,
+    #[sfv(key = "no-cache")]
+    no_cache: Option<Vec<Token>>,
+}
+
+#[derive(Debug, PartialEq, FromSfvList, ToSfvList)]
+struct Coeps(Vec<Coep>);
+
+#[test]
+fn test_item() -> Result<(), Error> {
+    let coep = Coep::from_sfv_item(Parser::new(r#"require-corp;report-to="coep";x=1"#))?;
+    assert_eq!(
+        coep,
+        Coep {
+            policy: "require-corp".to_owned(),
+            report_to: Some("coep".to_owned()),
+        }
+    );
+    assert_eq!(coep.to_sfv_item()?, r#"require-corp;report-to="coep""#);
+
+    let coep = Coep::from_sfv_item(Parser::new("credentialless"))?;
+    assert_eq!(coep.report_to, None);
+    assert_eq!(coep.to_sfv_item()?, "credentialless");
+    Ok(())
+}
+
+#[test]
+fn test_item_attributes() -> Result<(), Error> {
+    #[derive(Debug, PartialEq, FromSfvItem, ToSfvItem)]
+    struct Signature(
+        #[sfv(bytes)] Vec<u8>,
+        #[sfv(param, key = "created")] Date,
+        #[sfv(param, key = "alg", token)] Option<String>,
+        #[sfv(param, key = "q", default)] Decimal,
+    );
+
+    let signature = Signature::from_sfv_item(Parser::new(":AQI=:;created=@10;alg=ed25519"))?;
+    assert_eq!(
+        signature,
+        Signature(
+            vec![1, 2],
+            Date::from_unix_seconds(10.into()),
+            Some("ed25519".to_owned()),
+            Decimal::ZERO,
+        )
+    );
+    assert_eq!(
+        signature.to_sfv_item()?,
+        ":AQI=:;created=@10;alg=ed25519;q=0.0"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_dictionary() -> Result<(), Error> {
+    let cache_control = CacheControl::from_sfv_dictionary(Parser::new(
+        "max-age=10, unknown=(1 2), no-cache=(a b), max-age=60",
+    ))?;
+    assert_eq!(
+        cache_control,
+        CacheControl {
+            max_age: Some(60),
+            private: false,
+            no_cache: Some(vec![token_ref("a").to_owned(), token_ref("b").to_owned()]),
+        }
+    );
+    assert_eq!(
+        cache_control.to_sfv_dictionary()?,
+        "max-age=60, private=?0, no-cache=(a b)"
+    );
+
+    let cache_control = CacheControl::from_sfv_dictionary(Parser::new("private"))?;
+    assert!(cache_control.private);
+    assert_eq!(cache_control.to_sfv_dictionary()?, "private");
+    Ok(())
+}
+
+#[test]
+fn test_nested_items() -> Result<(), Error> {
+    #[derive(Debug, PartialEq, FromSfvDictionary, ToSfvDictionary)]
+    struct Policies {
+        default: Coep,
+        overrides: Vec<Coep>,
+    }
+
+    let input = r#"default=unsafe-none, overrides=(require-corp;report-to="a" credentialless)"#;
+    let policies = Policies::from_sfv_dictionary(Parser::new(input))?;
+    assert_eq!(policies.default.policy, "unsafe-none");
+    assert_eq!(policies.overrides.len(), 2);
+    assert_eq!(policies.overrides[0].report_to.as_deref(), Some("a"));
+    assert_eq!(policies.to_sfv_dictionary()?, input);
+    Ok(())
+}
+
+#[test]
+fn test_list() -> Result<(), Error> {
+    let coeps = Coeps::from_sfv_list(Parser::new(r#"require-corp;report-to="a", unsafe-none"#))?;
+    assert_eq!(
+        coeps,
+        Coeps(vec![
+            Coep {
+                policy: "require-corp".to_owned(),
+                report_to: Some("a".to_owned()),
+            },
+            Coep {
+                policy: "unsafe-none".to_owned(),
+                report_to: None,
+            },
+        ])
+    );
+    assert_eq!(
+        coeps.to_sfv_list()?,
+        r#"require-corp;report-to="a", unsafe-none"#
+    );
+
+    assert_eq!(Vec::<i64>::from_sfv_list(Parser::new("1, 2"))?, [1, 2]);
+    assert!(Coeps(vec![]).to_sfv_list().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_duplicate_keys() -> Result<(), Error> {
+    let item = r#"require-corp;report-to="a";report-to="b""#;
+    let dict = "max-age=10, max-age=60";
+
+    let parse = |policy| -> Result<_, Error> {
+        Ok((
+            Coep::from_sfv_item(Parser::new(item).with_duplicate_key_policy(policy))?.report_to,
+            CacheControl::from_sfv_dictionary(Parser::new(dict).with_duplicate_key_policy(policy))?
+                .max_age,
+        ))
+    };
+
+    assert_eq!(
+        parse(DuplicateKeyPolicy::LastWins)?,
+        (Some("b".to_owned()), Some(60))
+    );
+    assert_eq!(
+        parse(DuplicateKeyPolicy::FirstWins)?,
+        (Some("a".to_owned()), Some(10))
+    );
+
+    assert_eq!(
+        Coep::from_sfv_item(
+            Parser::new(item).with_duplicate_key_policy(DuplicateKeyPolicy::Reject)
+        )
+        .unwrap_err()
+        .kind(),
+        ErrorKind::DuplicateKey
+    );
+    assert_eq!(
+        CacheControl::from_sfv_dictionary(
+            Parser::new(dict).with_duplicate_key_policy(DuplicateKeyPolicy::Reject)
+        )
+        .unwrap_err()
+        .kind(),
+        ErrorKind::DuplicateKey
+    );
+    Ok(())
+}
+
+#[test]
+fn test_errors() {
+    #[derive(Debug, FromSfvDictionary)]
+    #[allow(dead_code)]
+    struct Required {
+        a: i64,
+    }
+
+    assert_eq!(
+        Required::from_sfv_dictionary(Parser::new("b=1"))
+            .unwrap_err()
+            .to_string(),
+        "missing dictionary member `a`"
+    );
+    assert_eq!(
+        Required::from_sfv_dictionary(Parser::new("a=x"))
+            .unwrap_err()
+            .to_string(),
+        "invalid dictionary member `a`: expected integer"
+    );
+    assert_eq!(
+        Required::from_sfv_dictionary(Parser::new("a=x"))
+            .unwrap_err()
+            .kind(),
+        ErrorKind::TypeMismatch
+    );
+    assert_eq!(
+        Required::from_sfv_dictionary(Parser::new("a=(1)"))
+            .unwrap_err()
+            .to_string(),
+        "invalid dictionary member `a`: expected item, found inner list"
+    );
+    assert_eq!(
+        Coep::from_sfv_item(Parser::new(r#""require-corp""#))
+            .unwrap_err()
+            .to_string(),
+        "expected token"
+    );
+    assert_eq!(
+        Coep::from_sfv_item(Parser::new("require-corp;report-to=1"))
+            .unwrap_err()
+            .to_string(),
+        "invalid parameter `report-to`: expected string"
+    );
+    assert!(Coep {
+        policy: "not a token".to_owned(),
+        report_to: None,
+    }
+    .to_sfv_item()
+    .is_err());
+}